@@ -0,0 +1,322 @@
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short, token,
+    vec, xdr::ToXdr, Address, BytesN, Env, IntoVal, Symbol, Vec,
+};
+
+mod test;
+
+// One page of `list_games` at most, so a read never walks the whole registry.
+const MAX_PAGE: u32 = 50;
+// Status reported for a game whose phase cannot be read.
+const UNREADABLE: Symbol = symbol_short!("unknown");
+
+// Stable codes, so clients can tell the factory's failures apart.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum FactoryError {
+    AlreadyInitialized = 1,
+    NotAdmin = 2,
+    NotInitialized = 3,
+    GameWasmNotSet = 4,
+    UnknownGame = 5,
+}
+
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+pub enum FactoryKey {
+    Admin,
+    GameWasm,
+    GameCount,
+    Game(u32),
+    HostGame(Address),
+    Treasury,
+    Deployed(Address),
+    PlayerGames(Address),
+}
+
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+pub struct Treasury {
+    pub account: Address,
+    pub token: Address,
+}
+
+// Mirror of the game's `OperatorMetrics`; contracttype encodes structs by field name.
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+pub struct GameMetrics {
+    pub phase: Symbol,
+    pub players: u32,
+    pub players_today: u32,
+    pub proofs_verified: u32,
+    pub proof_failures: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FactoryMetrics {
+    pub games: u32,
+    pub active_games: u32,
+    pub players_today: u32,
+    pub proofs_verified: u32,
+    pub proof_failures: u32,
+    pub treasury_balance: i128,
+}
+
+#[contract]
+pub struct GameFactory;
+
+impl GameFactory {
+    fn require_admin(env: &Env, caller: &Address) {
+        caller.require_auth();
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&FactoryKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(env, FactoryError::NotInitialized));
+        if admin != *caller {
+            panic_with_error!(env, FactoryError::NotAdmin);
+        }
+    }
+
+    fn game_count(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&FactoryKey::GameCount)
+            .unwrap_or(0)
+    }
+
+    // Seat hooks are only taken from games this factory deployed, on their own authority.
+    fn require_deployed(env: &Env, game: &Address) {
+        game.require_auth();
+        if !env
+            .storage()
+            .persistent()
+            .has(&FactoryKey::Deployed(game.clone()))
+        {
+            panic_with_error!(env, FactoryError::UnknownGame);
+        }
+    }
+
+    // Read through a try call, so a game that cannot answer is reported as unreadable
+    // instead of failing the whole read.
+    fn game_metrics(env: &Env, game: &Address) -> Option<GameMetrics> {
+        match env.try_invoke_contract::<GameMetrics, soroban_sdk::Error>(
+            game,
+            &Symbol::new(env, "get_operator_metrics"),
+            Vec::new(env),
+        ) {
+            Ok(Ok(metrics)) => Some(metrics),
+            _ => None,
+        }
+    }
+
+    fn drop_player_game(env: &Env, game: &Address, player: Address) {
+        let mut games = Self::player_games(env, &player);
+        if let Some(index) = games.first_index_of(game) {
+            games.remove(index);
+            env.storage()
+                .persistent()
+                .set(&FactoryKey::PlayerGames(player), &games);
+        }
+    }
+
+    fn player_games(env: &Env, player: &Address) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&FactoryKey::PlayerGames(player.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+
+    // The host is mixed into the salt, so nobody can take a host's address by deploying first.
+    fn game_salt(env: &Env, host: &Address, salt: BytesN<32>) -> BytesN<32> {
+        let mut seed = host.clone().to_xdr(env);
+        seed.append(&salt.into());
+        env.crypto().sha256(&seed)
+    }
+}
+
+#[contractimpl]
+impl GameFactory {
+    pub fn init(env: Env, admin: Address, game_wasm: BytesN<32>) {
+        admin.require_auth();
+        if env.storage().instance().has(&FactoryKey::Admin) {
+            panic_with_error!(&env, FactoryError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&FactoryKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&FactoryKey::GameWasm, &game_wasm);
+    }
+
+    // Only games created afterwards run the new code; deployed games keep theirs.
+    pub fn set_game_wasm(env: Env, caller: Address, game_wasm: BytesN<32>) {
+        Self::require_admin(&env, &caller);
+        env.storage()
+            .instance()
+            .set(&FactoryKey::GameWasm, &game_wasm);
+        env.events()
+            .publish((symbol_short!("game_wasm"), caller), game_wasm);
+    }
+
+    // Deploys and initializes in one call, so no one can `init` the fresh game before its
+    // host; the host authorizes both and becomes the game's admin.
+    pub fn create_game(env: Env, host: Address, salt: BytesN<32>, impostor_count: u32) -> Address {
+        host.require_auth();
+        let wasm: BytesN<32> = env
+            .storage()
+            .instance()
+            .get(&FactoryKey::GameWasm)
+            .unwrap_or_else(|| panic_with_error!(&env, FactoryError::GameWasmNotSet));
+        let game = env
+            .deployer()
+            .with_current_contract(Self::game_salt(&env, &host, salt))
+            .deploy(wasm);
+        env.invoke_contract::<()>(
+            &game,
+            &Symbol::new(&env, "init"),
+            vec![&env, host.into_val(&env), impostor_count.into_val(&env)],
+        );
+        env.storage()
+            .persistent()
+            .set(&FactoryKey::Deployed(game.clone()), &true);
+        env.invoke_contract::<()>(
+            &game,
+            &Symbol::new(&env, "bind_factory"),
+            vec![
+                &env,
+                host.into_val(&env),
+                env.current_contract_address().into_val(&env),
+            ],
+        );
+
+        let index = Self::game_count(&env);
+        env.storage()
+            .persistent()
+            .set(&FactoryKey::Game(index), &game);
+        env.storage()
+            .instance()
+            .set(&FactoryKey::GameCount, &(index + 1));
+        env.storage()
+            .persistent()
+            .set(&FactoryKey::HostGame(host.clone()), &game);
+        env.events()
+            .publish((symbol_short!("created"), host), (index, game.clone()));
+        game
+    }
+
+    // Games in creation order, starting at `start`; `limit` is capped at 50.
+    pub fn list_games(env: Env, start: u32, limit: u32) -> Vec<Address> {
+        let end = start
+            .saturating_add(limit.min(MAX_PAGE))
+            .min(Self::game_count(&env));
+        let mut games = Vec::new(&env);
+        for index in start..end {
+            if let Some(game) = env.storage().persistent().get(&FactoryKey::Game(index)) {
+                games.push_back(game);
+            }
+        }
+        games
+    }
+
+    // Called by a deployed game when it seats a player. Only the newest 50 seats are kept,
+    // so the index stays one page.
+    pub fn record_seat(env: Env, game: Address, player: Address) {
+        Self::require_deployed(&env, &game);
+        let mut games = Self::player_games(&env, &player);
+        if games.contains(&game) {
+            return;
+        }
+        if games.len() >= MAX_PAGE {
+            games.pop_front();
+        }
+        games.push_back(game);
+        env.storage()
+            .persistent()
+            .set(&FactoryKey::PlayerGames(player), &games);
+    }
+
+    pub fn record_unseat(env: Env, game: Address, player: Address) {
+        Self::require_deployed(&env, &game);
+        Self::drop_player_game(&env, &game, player);
+    }
+
+    // Called by a deployed game as its match ends: a finished match holds nobody's seat.
+    pub fn record_game_end(env: Env, game: Address, players: Vec<Address>) {
+        Self::require_deployed(&env, &game);
+        for player in players.iter() {
+            Self::drop_player_game(&env, &game, player);
+        }
+    }
+
+    // Games the player holds a seat in, oldest first, for session restore, each with its
+    // phase; a game that cannot be read reports `unknown`.
+    pub fn get_games_for(env: Env, player: Address) -> Vec<(Address, Symbol)> {
+        let mut games = Vec::new(&env);
+        for game in Self::player_games(&env, &player).iter() {
+            let status = Self::game_metrics(&env, &game)
+                .map(|metrics| metrics.phase)
+                .unwrap_or(UNREADABLE);
+            games.push_back((game, status));
+        }
+        games
+    }
+
+    pub fn set_treasury(env: Env, caller: Address, account: Address, token: Address) {
+        Self::require_admin(&env, &caller);
+        env.storage()
+            .instance()
+            .set(&FactoryKey::Treasury, &Treasury { account, token });
+    }
+
+    // Sums the newest 50 games, the same bound as one `list_games` page; older games are
+    // taken to be finished, and a game that cannot be read is left out. The balance is 0
+    // until a treasury is set.
+    pub fn get_operator_metrics(env: Env) -> FactoryMetrics {
+        let count = Self::game_count(&env);
+        let mut metrics = FactoryMetrics {
+            games: count,
+            ..FactoryMetrics::default()
+        };
+        let ended = symbol_short!("ended");
+        for index in count.saturating_sub(MAX_PAGE)..count {
+            let Some(game) = env.storage().persistent().get(&FactoryKey::Game(index)) else {
+                continue;
+            };
+            let Some(game) = Self::game_metrics(&env, &game) else {
+                continue;
+            };
+            if game.phase != ended {
+                metrics.active_games += 1;
+            }
+            metrics.players_today += game.players_today;
+            metrics.proofs_verified += game.proofs_verified;
+            metrics.proof_failures += game.proof_failures;
+        }
+        if let Some(treasury) = env
+            .storage()
+            .instance()
+            .get::<_, Treasury>(&FactoryKey::Treasury)
+        {
+            metrics.treasury_balance =
+                token::Client::new(&env, &treasury.token).balance(&treasury.account);
+        }
+        metrics
+    }
+
+    pub fn get_game_count(env: Env) -> u32 {
+        Self::game_count(&env)
+    }
+
+    // The host's most recent game.
+    pub fn get_game_by_host(env: Env, host: Address) -> Option<Address> {
+        env.storage().persistent().get(&FactoryKey::HostGame(host))
+    }
+
+    pub fn get_game_wasm(env: Env) -> Option<BytesN<32>> {
+        env.storage().instance().get(&FactoryKey::GameWasm)
+    }
+}
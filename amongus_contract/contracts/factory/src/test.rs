@@ -0,0 +1,207 @@
+#![cfg(test)]
+extern crate std;
+
+use super::*;
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::token;
+use std::path::PathBuf;
+use std::process::Command;
+
+// Builds the game's release WASM into the same side target dir as the game's size test.
+fn game_wasm() -> std::vec::Vec<u8> {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = manifest_dir.join("../../target/wasm-size");
+    let status = Command::new(env!("CARGO"))
+        .current_dir(&manifest_dir)
+        .args([
+            "build",
+            "--release",
+            "--target",
+            "wasm32-unknown-unknown",
+            "-p",
+            "game",
+            "--target-dir",
+        ])
+        .arg(&target_dir)
+        .status()
+        .expect("failed to run cargo");
+    assert!(status.success(), "game wasm build failed");
+    std::fs::read(target_dir.join("wasm32-unknown-unknown/release/game.wasm")).unwrap()
+}
+
+fn game_admin(env: &Env, game: &Address) -> Option<Address> {
+    env.invoke_contract(game, &Symbol::new(env, "get_admin"), Vec::new(env))
+}
+
+#[test]
+fn created_games_are_initialized_and_indexed() {
+    let env = Env::default();
+    env.mock_all_auths();
+    // Instantiating the real game WASM three times outruns the default test budget.
+    env.budget().reset_unlimited();
+    let wasm = env.deployer().upload_contract_wasm(game_wasm().as_slice());
+    let factory_id = env.register_contract(None, GameFactory);
+    let factory = GameFactoryClient::new(&env, &factory_id);
+    factory.init(&Address::generate(&env), &wasm);
+
+    let host_a = Address::generate(&env);
+    let host_b = Address::generate(&env);
+    let salt = BytesN::from_array(&env, &[1; 32]);
+    let first = factory.create_game(&host_a, &salt, &1);
+    // Same salt, different host: the host is part of the address.
+    let second = factory.create_game(&host_b, &salt, &2);
+    let third = factory.create_game(&host_a, &BytesN::from_array(&env, &[2; 32]), &1);
+
+    assert_ne!(first, second);
+    assert_eq!(game_admin(&env, &first), Some(host_a.clone()));
+    assert_eq!(game_admin(&env, &second), Some(host_b.clone()));
+    assert_eq!(factory.get_game_count(), 3);
+    assert_eq!(
+        factory.list_games(&0, &10),
+        vec![&env, first, second.clone(), third.clone()]
+    );
+    assert_eq!(factory.list_games(&1, &1), vec![&env, second.clone()]);
+    assert_eq!(factory.get_game_by_host(&host_a), Some(third));
+    assert_eq!(factory.get_game_by_host(&host_b), Some(second));
+    assert_eq!(factory.get_game_by_host(&Address::generate(&env)), None);
+}
+
+#[test]
+fn operator_metrics_sum_recent_games_and_read_the_treasury() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+    let wasm = env.deployer().upload_contract_wasm(game_wasm().as_slice());
+    let factory_id = env.register_contract(None, GameFactory);
+    let factory = GameFactoryClient::new(&env, &factory_id);
+    let admin = Address::generate(&env);
+    factory.init(&admin, &wasm);
+    let quiet = factory.create_game(
+        &Address::generate(&env),
+        &BytesN::from_array(&env, &[1; 32]),
+        &1,
+    );
+    let busy = factory.create_game(
+        &Address::generate(&env),
+        &BytesN::from_array(&env, &[2; 32]),
+        &1,
+    );
+    let player = Address::generate(&env);
+    let hash = BytesN::from_array(&env, &[7; 32]);
+    env.invoke_contract::<()>(
+        &busy,
+        &Symbol::new(&env, "join_game"),
+        vec![
+            &env,
+            player.into_val(&env),
+            symbol_short!("Red").into_val(&env),
+            symbol_short!("P").into_val(&env),
+            hash.clone().into_val(&env),
+            hash.into_val(&env),
+        ],
+    );
+    assert_ne!(quiet, busy);
+
+    let treasury = Address::generate(&env);
+    let token_id = env.register_stellar_asset_contract(Address::generate(&env));
+    token::StellarAssetClient::new(&env, &token_id).mint(&treasury, &40);
+    factory.set_treasury(&admin, &treasury, &token_id);
+
+    let metrics = factory.get_operator_metrics();
+    assert_eq!((metrics.games, metrics.active_games), (2, 2));
+    assert_eq!(metrics.players_today, 1);
+    assert_eq!((metrics.proofs_verified, metrics.proof_failures), (0, 0));
+    assert_eq!(metrics.treasury_balance, 40);
+}
+
+#[test]
+fn games_report_seat_changes_to_the_player_index() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+    let wasm = env.deployer().upload_contract_wasm(game_wasm().as_slice());
+    let factory_id = env.register_contract(None, GameFactory);
+    let factory = GameFactoryClient::new(&env, &factory_id);
+    factory.init(&Address::generate(&env), &wasm);
+    let host = Address::generate(&env);
+    let first = factory.create_game(&host, &BytesN::from_array(&env, &[1; 32]), &1);
+    let second = factory.create_game(&host, &BytesN::from_array(&env, &[2; 32]), &1);
+    let bound: Option<Address> =
+        env.invoke_contract(&first, &Symbol::new(&env, "get_factory"), Vec::new(&env));
+    assert_eq!(bound, Some(factory_id));
+
+    let player = Address::generate(&env);
+    for (game, seed) in [(&first, 7u8), (&second, 8u8)] {
+        let hash = BytesN::from_array(&env, &[seed; 32]);
+        env.invoke_contract::<()>(
+            game,
+            &Symbol::new(&env, "join_game"),
+            vec![
+                &env,
+                player.into_val(&env),
+                symbol_short!("Red").into_val(&env),
+                symbol_short!("P").into_val(&env),
+                hash.clone().into_val(&env),
+                hash.into_val(&env),
+            ],
+        );
+    }
+    let lobby = symbol_short!("lobby");
+    assert_eq!(
+        factory.get_games_for(&player),
+        vec![
+            &env,
+            (first.clone(), lobby.clone()),
+            (second.clone(), lobby.clone())
+        ]
+    );
+
+    env.invoke_contract::<()>(
+        &first,
+        &Symbol::new(&env, "leave_game"),
+        vec![&env, player.into_val(&env), ().into_val(&env)],
+    );
+    assert_eq!(
+        factory.get_games_for(&player),
+        vec![&env, (second.clone(), lobby)]
+    );
+    assert!(factory.get_games_for(&Address::generate(&env)).is_empty());
+
+    // A finished match unseats everyone it still held.
+    env.invoke_contract::<()>(
+        &second,
+        &Symbol::new(&env, "end_game_admin"),
+        vec![
+            &env,
+            host.into_val(&env),
+            symbol_short!("crew").into_val(&env),
+        ],
+    );
+    assert!(factory.get_games_for(&player).is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #5)")]
+fn only_deployed_games_may_record_seats() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let factory_id = env.register_contract(None, GameFactory);
+    let (game, player) = (Address::generate(&env), Address::generate(&env));
+    env.as_contract(&factory_id, || {
+        GameFactory::record_seat(env.clone(), game, player)
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn only_the_admin_may_swap_the_game_wasm() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let factory_id = env.register_contract(None, GameFactory);
+    let factory = GameFactoryClient::new(&env, &factory_id);
+    let wasm = BytesN::from_array(&env, &[1; 32]);
+    factory.init(&Address::generate(&env), &wasm);
+    env.as_contract(&factory_id, || {
+        GameFactory::set_game_wasm(env.clone(), Address::generate(&env), wasm)
+    });
+}
@@ -0,0 +1,208 @@
+//! Per-entrypoint cost report against the release WASM. Prints one JSON object
+//! per entrypoint and player count with the host budget spent by that call and
+//! the resulting state size:
+//!
+//!     cargo build -p game --release --target wasm32-unknown-unknown
+//!     cargo bench -p game --bench cost_report
+//!
+//! `scripts/cost_report.sh` repeats the run for each feature combination.
+
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, testutils::Address as _, vec, xdr::ToXdr,
+    Address, Bytes, BytesN, Env, IntoVal, Map, Symbol, TryFromVal, Val, Vec,
+};
+
+const PLAYER_COUNTS: [u32; 3] = [4, 8, 15];
+// A BN254 Groth16 proof, so verifier calls are costed with realistic argument sizes.
+const PROOF_LEN: usize = 256;
+const WASM: &str = "../../target/wasm32-unknown-unknown/release/game.wasm";
+
+// Mirrors of the contract's argument types; contracttype encodes by field name.
+#[contracttype]
+pub struct ProofInput {
+    pub proof: Bytes,
+    pub nullifier: BytesN<32>,
+    pub public_inputs: Vec<BytesN<32>>,
+}
+
+#[contracttype]
+pub struct VoteInput {
+    pub target_hash: BytesN<32>,
+    pub proof: Bytes,
+    pub nullifier: BytesN<32>,
+}
+
+#[contract]
+pub struct BenchVerifier;
+
+#[contractimpl]
+impl BenchVerifier {
+    pub fn verify(_env: Env, _proof: Bytes, _public_inputs: Vec<BytesN<32>>) -> bool {
+        true
+    }
+}
+
+struct Game<'a> {
+    env: &'a Env,
+    id: Address,
+    features: String,
+    players: u32,
+}
+
+impl Game<'_> {
+    fn call<T: TryFromVal<Env, Val>>(&self, func: &str, args: Vec<Val>) -> T {
+        self.env
+            .invoke_contract(&self.id, &Symbol::new(self.env, func), args)
+    }
+
+    fn measure(&self, func: &str, args: Vec<Val>) {
+        let mut budget = self.env.budget();
+        budget.reset_unlimited();
+        let _: Val = self.call(func, args);
+        let cpu = budget.cpu_instruction_cost();
+        let mem = budget.memory_bytes_cost();
+        let players: Map<Address, Val> = self.call("get_players", vec![self.env]);
+        let state: Val = self.call("get_game_state", vec![self.env]);
+        let state_bytes = players.to_xdr(self.env).len() + state.to_xdr(self.env).len();
+        println!(
+            "{{\"entrypoint\":\"{func}\",\"players\":{},\"features\":[{}],\"cpu_insns\":{cpu},\"mem_bytes\":{mem},\"state_bytes\":{state_bytes}}}",
+            self.players, self.features
+        );
+    }
+}
+
+fn hash(env: &Env, seed: u32, tag: u8) -> BytesN<32> {
+    let mut bytes = [tag; 32];
+    bytes[..4].copy_from_slice(&seed.to_be_bytes());
+    BytesN::from_array(env, &bytes)
+}
+
+fn proof(env: &Env, nullifier: u32) -> ProofInput {
+    ProofInput {
+        proof: Bytes::from_array(env, &[8; PROOF_LEN]),
+        nullifier: hash(env, nullifier, 0xAA),
+        public_inputs: Vec::new(env),
+    }
+}
+
+fn run(wasm: &[u8], player_count: u32) {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+    let mut game = Game {
+        env: &env,
+        id: env.register_contract_wasm(None, wasm),
+        features: String::new(),
+        players: player_count,
+    };
+    let admin = Address::generate(&env);
+    let verifier = env.register_contract(None, BenchVerifier);
+    let _: () = game.call(
+        "init",
+        vec![&env, admin.into_val(&env), 1u32.into_val(&env)],
+    );
+    let _: () = game.call(
+        "configure_game",
+        vec![
+            &env,
+            admin.into_val(&env),
+            PLAYER_COUNTS[2].into_val(&env),
+            1_000u32.into_val(&env),
+        ],
+    );
+    let _: () = game.call(
+        "set_verifier",
+        vec![&env, admin.into_val(&env), verifier.into_val(&env)],
+    );
+    let capabilities: Vec<Symbol> = game.call("get_capabilities", vec![&env]);
+    game.features = capabilities
+        .iter()
+        .map(|c| format!("\"{}\"", c.to_string()))
+        .collect::<std::vec::Vec<_>>()
+        .join(",");
+
+    let players: std::vec::Vec<Address> =
+        (0..player_count).map(|_| Address::generate(&env)).collect();
+    for (i, player) in players.iter().enumerate() {
+        let args = vec![
+            &env,
+            player.into_val(&env),
+            // Colors are unique per lobby, and 15 seats outnumber the palette.
+            Symbol::new(&env, &format!("C{i}")).into_val(&env),
+            symbol_short!("P").into_val(&env),
+            hash(&env, i as u32, 1).into_val(&env),
+            hash(&env, i as u32, 2).into_val(&env),
+        ];
+        if i + 1 == players.len() {
+            game.measure("join_game", args);
+        } else {
+            let _: () = game.call("join_game", args);
+        }
+    }
+    game.measure("start_game", vec![&env, admin.into_val(&env)]);
+    game.measure(
+        "submit_move",
+        vec![
+            &env,
+            players[0].into_val(&env),
+            3u32.into_val(&env),
+            4u32.into_val(&env),
+        ],
+    );
+    game.measure(
+        "submit_task_proof",
+        vec![
+            &env,
+            players[0].into_val(&env),
+            proof(&env, 1).into_val(&env),
+        ],
+    );
+    game.measure(
+        "submit_kill_proof",
+        vec![
+            &env,
+            players[1].into_val(&env),
+            players[2].into_val(&env),
+            proof(&env, 2).into_val(&env),
+            0u32.into_val(&env),
+        ],
+    );
+    game.measure("get_players", vec![&env]);
+    game.measure(
+        "get_nearby_players",
+        vec![
+            &env,
+            0u32.into_val(&env),
+            0u32.into_val(&env),
+            10u32.into_val(&env),
+        ],
+    );
+    game.measure("start_meeting", vec![&env, players[0].into_val(&env)]);
+    let vote = VoteInput {
+        target_hash: hash(&env, 1, 1),
+        proof: Bytes::from_array(&env, &[8; PROOF_LEN]),
+        nullifier: hash(&env, 3, 0xAA),
+    };
+    game.measure(
+        "submit_vote",
+        vec![&env, players[0].into_val(&env), vote.into_val(&env)],
+    );
+    game.measure(
+        "finalize_meeting",
+        vec![
+            &env,
+            admin.into_val(&env),
+            hash(&env, 1, 1).into_val(&env),
+            None::<ProofInput>.into_val(&env),
+        ],
+    );
+}
+
+fn main() {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(WASM);
+    let wasm = std::fs::read(&path)
+        .unwrap_or_else(|_| panic!("{} missing; build the release wasm first", path.display()));
+    for player_count in PLAYER_COUNTS {
+        run(&wasm, player_count);
+    }
+}
@@ -0,0 +1,193 @@
+use soroban_sdk::{
+    contractimpl, contracttype, symbol_short, vec, xdr::ToXdr, Address, Bytes, BytesN, Env, Symbol,
+    Vec,
+};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::{fail, CIRCUIT_ROLE, PHASE_MEETING, PHASE_PLAYING};
+use crate::{
+    clock, AmongUsContract, AmongUsContractClient, DataKey, GameError, ModuleError, ProofInput,
+};
+
+const DEFAULT_GRANT_TTL: u64 = 300;
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum AccessKey {
+    GrantTtl,
+    // Keyed by a blinded handle, so stored grants never name the impostor who holds one.
+    ReadGrant(BytesN<32>),
+    RoundGrants(u32),
+}
+
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+pub struct ReadGrant {
+    pub scope: Symbol,
+    pub round: u32,
+    pub expires_at: u64,
+}
+
+impl AmongUsContract {
+    // Commits to the unspent role commitments, so a proof can open one of them as impostor
+    // without saying which. `alive_only` drops dead seats for actions the dead cannot take.
+    pub(crate) fn role_root(env: &Env, alive_only: bool) -> BytesN<32> {
+        let mut seats = Vec::new(env);
+        for (_, p) in Self::read_players(env).iter() {
+            if (p.alive || !alive_only)
+                && !env
+                    .storage()
+                    .instance()
+                    .has(&DataKey::SpentRole(p.role_hash.clone()))
+            {
+                seats.push_back(p.role_hash);
+            }
+        }
+        env.crypto().sha256(&seats.to_xdr(env))
+    }
+
+    // sha256(holder address XDR || salt); only someone holding the salt can link a handle
+    // back to the address.
+    fn grant_handle(env: &Env, holder: &Address, salt: BytesN<32>) -> BytesN<32> {
+        let mut preimage = holder.clone().to_xdr(env);
+        preimage.append(&Bytes::from(salt));
+        env.crypto().sha256(&preimage)
+    }
+
+    // Gates contract reads only: ledger entries stay public, so this keeps honest clients
+    // from rendering role-revealing data rather than hiding it from a determined reader.
+    pub(crate) fn has_read_grant(
+        env: &Env,
+        caller: &Address,
+        grant_salt: Option<BytesN<32>>,
+        scope: Symbol,
+    ) -> bool {
+        caller.require_auth();
+        if Self::is_operator(env, caller) {
+            return true;
+        }
+        let grant: Option<ReadGrant> = grant_salt.and_then(|salt| {
+            env.storage()
+                .instance()
+                .get(&AccessKey::ReadGrant(Self::grant_handle(env, caller, salt)))
+        });
+        match grant {
+            Some(grant) => grant.scope == scope && Self::ledger_now(env) <= grant.expires_at,
+            None => false,
+        }
+    }
+
+    pub(crate) fn ensure_read_grant(
+        env: &Env,
+        caller: &Address,
+        grant_salt: Option<BytesN<32>>,
+        scope: Symbol,
+    ) {
+        if !Self::has_read_grant(env, caller, grant_salt, scope) {
+            fail(env, ModuleError::ReadGrantRequired);
+        }
+    }
+
+    pub(crate) fn clear_round_grants(env: &Env, round: u32) {
+        let key = AccessKey::RoundGrants(round);
+        let handles: Vec<BytesN<32>> = env.storage().instance().get(&key).unwrap_or(Vec::new(env));
+        for handle in handles.iter() {
+            env.storage()
+                .instance()
+                .remove(&AccessKey::ReadGrant(handle));
+        }
+        env.storage().instance().remove(&key);
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    pub fn configure_read_grants(env: Env, caller: Address, ttl_seconds: u64) {
+        Self::require_operator(&env, &caller);
+        if ttl_seconds == 0 {
+            fail(&env, GameError::InvalidConfig);
+        }
+        env.storage()
+            .instance()
+            .set(&AccessKey::GrantTtl, &ttl_seconds);
+    }
+
+    // Anonymous like `start_meeting_anon`: the proof opens one of the alive role commitments
+    // as impostor and binds `handle`, so neither the call nor its event names the holder.
+    pub fn claim_read_grant(
+        env: Env,
+        handle: BytesN<32>,
+        proof: ProofInput,
+    ) -> Result<u64, GameError> {
+        Self::ensure_not_ended(&env);
+        Self::ensure_not_casual(&env)?;
+        let state = Self::read_state(&env);
+        if state.phase != PHASE_PLAYING && state.phase != PHASE_MEETING {
+            return Err(GameError::WrongPhase);
+        }
+        if env
+            .storage()
+            .instance()
+            .has(&DataKey::UsedNullifier(proof.nullifier.clone()))
+        {
+            return Err(GameError::NullifierUsed);
+        }
+        if env
+            .storage()
+            .instance()
+            .has(&AccessKey::ReadGrant(handle.clone()))
+        {
+            fail(&env, ModuleError::GrantHandleUsed);
+        }
+
+        let public_inputs = vec![
+            &env,
+            Self::role_root(&env, true),
+            Self::field_input(&env, state.round),
+            handle.clone(),
+            proof.nullifier.clone(),
+        ];
+        let ttl: u64 = env
+            .storage()
+            .instance()
+            .get(&AccessKey::GrantTtl)
+            .unwrap_or(DEFAULT_GRANT_TTL);
+        let expires_at = clock::deadline(Self::ledger_now(&env), ttl);
+        Self::consume_nullifier(&env, proof.nullifier);
+        env.storage().instance().set(
+            &AccessKey::ReadGrant(handle.clone()),
+            &ReadGrant {
+                scope: symbol_short!("impostor"),
+                round: state.round,
+                expires_at,
+            },
+        );
+        let round_key = AccessKey::RoundGrants(state.round);
+        let mut handles: Vec<BytesN<32>> = env
+            .storage()
+            .instance()
+            .get(&round_key)
+            .unwrap_or(Vec::new(&env));
+        handles.push_back(handle);
+        env.storage().instance().set(&round_key, &handles);
+        if !Self::verify_circuit(&env, CIRCUIT_ROLE, proof.proof, public_inputs) {
+            return Err(GameError::InvalidProof);
+        }
+
+        env.events().publish((symbol_short!("grant"),), expires_at);
+        Ok(expires_at)
+    }
+
+    pub fn get_role_root(env: Env, alive_only: bool) -> BytesN<32> {
+        Self::role_root(&env, alive_only)
+    }
+
+    pub fn get_grant_handle(env: Env, holder: Address, salt: BytesN<32>) -> BytesN<32> {
+        Self::grant_handle(&env, &holder, salt)
+    }
+
+    pub fn get_read_grant(env: Env, handle: BytesN<32>) -> Option<ReadGrant> {
+        env.storage().instance().get(&AccessKey::ReadGrant(handle))
+    }
+}
@@ -0,0 +1,69 @@
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, Env};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::fail;
+use crate::{AmongUsContract, AmongUsContractClient, DataKey, GameError};
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum AdminKey {
+    Operator,
+}
+
+impl AmongUsContract {
+    fn read_operator(env: &Env) -> Option<Address> {
+        env.storage().instance().get(&AdminKey::Operator)
+    }
+
+    // The cold admin keeps every operator power, so losing the hot key never locks a match.
+    pub(crate) fn is_operator(env: &Env, caller: &Address) -> bool {
+        let admin: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+        admin.as_ref() == Some(caller) || Self::read_operator(env).as_ref() == Some(caller)
+    }
+
+    pub(crate) fn apply_operator(env: &Env, caller: &Address, operator: Address) {
+        env.storage().instance().set(&AdminKey::Operator, &operator);
+        env.events()
+            .publish((symbol_short!("operator"), caller.clone()), operator);
+    }
+
+    pub(crate) fn require_operator(env: &Env, caller: &Address) {
+        caller.require_auth();
+        if !Self::is_operator(env, caller) {
+            fail(env, GameError::NotAdmin);
+        }
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    pub fn set_admin(env: Env, caller: Address, new_admin: Address) {
+        Self::require_admin(&env, &caller);
+        new_admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+        env.events()
+            .publish((symbol_short!("admin"), caller), new_admin);
+    }
+
+    // The operator may hand its role to a fresh key; the cold admin can always replace it.
+    pub fn set_operator(env: Env, caller: Address, operator: Address) {
+        Self::require_operator(&env, &caller);
+        Self::apply_operator(&env, &caller, operator);
+    }
+
+    pub fn revoke_operator(env: Env, caller: Address) {
+        Self::require_admin(&env, &caller);
+        env.storage().instance().remove(&AdminKey::Operator);
+        env.events()
+            .publish((symbol_short!("op_revoke"), caller), ());
+    }
+
+    pub fn get_admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Admin)
+    }
+
+    pub fn get_operator(env: Env) -> Option<Address> {
+        Self::read_operator(&env)
+    }
+}
@@ -0,0 +1,47 @@
+use soroban_sdk::{contractimpl, contracttype, Address, Env};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::{clock, AmongUsContract, AmongUsContractClient};
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum AfkKey {
+    AfkTimeout,
+}
+
+impl AmongUsContract {
+    fn afk_timeout(env: &Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&AfkKey::AfkTimeout)
+            .unwrap_or(0)
+    }
+
+    // Ledger seconds until `player` counts as AFK; None while the timeout is off or the seat
+    // is empty. Every action that stamps `last_action_at` starts it over.
+    pub(crate) fn afk_deadline_in(env: &Env, player: &Address) -> Option<u64> {
+        let timeout = Self::afk_timeout(env);
+        if timeout == 0 {
+            return None;
+        }
+        let entry = Self::read_players(env).get(player.clone())?;
+        let deadline = clock::deadline(entry.last_action_at, timeout);
+        Some(deadline.saturating_sub(Self::ledger_now(env)))
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    // 0 turns the timeout off. Past it, anyone may `kick_player` the idle seat.
+    pub fn configure_afk_timeout(env: Env, caller: Address, timeout_secs: u64) {
+        Self::require_operator(&env, &caller);
+        env.storage()
+            .instance()
+            .set(&AfkKey::AfkTimeout, &timeout_secs);
+    }
+
+    pub fn get_afk_timeout(env: Env) -> u64 {
+        Self::afk_timeout(&env)
+    }
+}
@@ -0,0 +1,110 @@
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, Env, Vec};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::fail;
+use crate::{AmongUsContract, AmongUsContractClient, GameError, ModuleError};
+
+const MAX_ALLOWLIST_BATCH: u32 = 50;
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum AllowlistKey {
+    AllowlistOn,
+    AllowlistManager,
+    Allowed(Address),
+}
+
+impl AmongUsContract {
+    fn allowlist_enabled(env: &Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&AllowlistKey::AllowlistOn)
+            .unwrap_or(false)
+    }
+
+    fn is_allowed(env: &Env, player: &Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&AllowlistKey::Allowed(player.clone()))
+    }
+
+    pub(crate) fn ensure_allowlisted(env: &Env, player: &Address) {
+        if Self::allowlist_enabled(env) && !Self::is_allowed(env, player) {
+            fail(env, ModuleError::NotAllowlisted);
+        }
+    }
+
+    // A league contract set as manager authorizes as its own address when it calls in.
+    fn require_allowlist_manager(env: &Env, caller: &Address) {
+        caller.require_auth();
+        let manager: Option<Address> = env
+            .storage()
+            .instance()
+            .get(&AllowlistKey::AllowlistManager);
+        if manager.as_ref() != Some(caller) && !Self::is_operator(env, caller) {
+            fail(env, GameError::NotAdmin);
+        }
+    }
+
+    pub(crate) fn apply_allowlist_add(env: &Env, caller: &Address, players: &Vec<Address>) {
+        Self::ensure_allowlist_batch(env, players);
+        for player in players.iter() {
+            env.storage()
+                .persistent()
+                .set(&AllowlistKey::Allowed(player), &true);
+        }
+        env.events()
+            .publish((symbol_short!("allow_add"), caller.clone()), players.len());
+    }
+
+    fn ensure_allowlist_batch(env: &Env, players: &Vec<Address>) {
+        if players.is_empty() || players.len() > MAX_ALLOWLIST_BATCH {
+            fail(env, ModuleError::InvalidBatch);
+        }
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    pub fn configure_allowlist(env: Env, caller: Address, enabled: bool) {
+        Self::require_operator(&env, &caller);
+        env.storage()
+            .instance()
+            .set(&AllowlistKey::AllowlistOn, &enabled);
+    }
+
+    pub fn set_allowlist_manager(env: Env, caller: Address, manager: Address) {
+        Self::require_operator(&env, &caller);
+        env.storage()
+            .instance()
+            .set(&AllowlistKey::AllowlistManager, &manager);
+    }
+
+    pub fn allowlist_add(env: Env, caller: Address, players: Vec<Address>) {
+        Self::require_allowlist_manager(&env, &caller);
+        Self::apply_allowlist_add(&env, &caller, &players);
+    }
+
+    pub fn allowlist_remove(env: Env, caller: Address, players: Vec<Address>) {
+        Self::require_allowlist_manager(&env, &caller);
+        Self::ensure_allowlist_batch(&env, &players);
+        for player in players.iter() {
+            env.storage()
+                .persistent()
+                .remove(&AllowlistKey::Allowed(player));
+        }
+        env.events()
+            .publish((symbol_short!("allow_rm"), caller), players.len());
+    }
+
+    pub fn is_allowlisted(env: Env, player: Address) -> bool {
+        Self::is_allowed(&env, &player)
+    }
+
+    pub fn get_allowlist_manager(env: Env) -> Option<Address> {
+        env.storage()
+            .instance()
+            .get(&AllowlistKey::AllowlistManager)
+    }
+}
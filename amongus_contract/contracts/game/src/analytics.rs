@@ -0,0 +1,110 @@
+use soroban_sdk::{
+    contractimpl, contracttype, symbol_short, vec, Address, Env, IntoVal, Symbol, Vec,
+};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::WINNER_CREW;
+use crate::{AmongUsContract, AmongUsContractClient, MatchReport, Repair};
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum AnalyticsKey {
+    AnalyticsSink,
+    RoundKills,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MatchAnalytics {
+    pub crew_won: bool,
+    pub winner: Symbol,
+    pub rounds: u32,
+    pub players: u32,
+    pub impostors: u32,
+    pub kills_per_round: Vec<u32>,
+    pub tasks_done: u32,
+    pub task_completion_bps: u32,
+    pub repairs: Vec<Repair>,
+}
+
+impl AmongUsContract {
+    fn round_kills(env: &Env) -> Vec<u32> {
+        env.storage()
+            .instance()
+            .get(&AnalyticsKey::RoundKills)
+            .unwrap_or(Vec::new(env))
+    }
+
+    pub(crate) fn note_kill(env: &Env, round: u32) {
+        let mut kills = Self::round_kills(env);
+        while kills.len() < round {
+            kills.push_back(0);
+        }
+        let index = round - 1;
+        kills.set(index, kills.get(index).unwrap_or(0) + 1);
+        env.storage()
+            .instance()
+            .set(&AnalyticsKey::RoundKills, &kills);
+    }
+
+    // The sink is advisory: a failing or missing sink never blocks the end of a match.
+    pub(crate) fn report_analytics(env: &Env, report: &MatchReport) {
+        let Some(sink) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&AnalyticsKey::AnalyticsSink)
+        else {
+            return;
+        };
+        let mut kills_per_round = Self::round_kills(env);
+        while kills_per_round.len() < report.rounds {
+            kills_per_round.push_back(0);
+        }
+        let tasks_to_win = Self::read_config(env).tasks_to_win;
+        let analytics = MatchAnalytics {
+            crew_won: report.winner == WINNER_CREW,
+            winner: report.winner.clone(),
+            rounds: report.rounds,
+            players: report.players,
+            impostors: Self::read_state(env).impostor_count,
+            kills_per_round,
+            tasks_done: report.tasks_done,
+            task_completion_bps: (report.tasks_done.min(tasks_to_win) * 10_000) / tasks_to_win,
+            repairs: report.repairs.clone(),
+        };
+        let sent = env.try_invoke_contract::<(), soroban_sdk::Error>(
+            &sink,
+            &Symbol::new(env, "record_match"),
+            vec![
+                env,
+                env.current_contract_address().into_val(env),
+                analytics.into_val(env),
+            ],
+        );
+        if !matches!(sent, Ok(Ok(()))) {
+            env.events().publish((symbol_short!("sink_fail"),), sink);
+        }
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    pub fn set_analytics_sink(env: Env, caller: Address, sink: Option<Address>) {
+        Self::require_operator(&env, &caller);
+        match sink {
+            Some(sink) => env
+                .storage()
+                .instance()
+                .set(&AnalyticsKey::AnalyticsSink, &sink),
+            None => env
+                .storage()
+                .instance()
+                .remove(&AnalyticsKey::AnalyticsSink),
+        }
+    }
+
+    pub fn get_analytics_sink(env: Env) -> Option<Address> {
+        env.storage().instance().get(&AnalyticsKey::AnalyticsSink)
+    }
+}
@@ -0,0 +1,258 @@
+use soroban_sdk::{
+    contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, BytesN, Env, Map, Vec,
+};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::{
+    fail, CIRCUIT_MEMBER, CIRCUIT_SUSPECT, CIRCUIT_VOTE, PHASE_MEETING, PHASE_PLAYING,
+};
+use crate::{
+    AmongUsContract, AmongUsContractClient, DataKey, GameError, ModuleError, ProofInput, Receipt,
+    VoteInput,
+};
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum AnonKey {
+    AnonMeetings,
+    AnonVoting,
+    VoteTally(u32),
+    SuspicionSignals,
+    SuspicionHeat(u32),
+}
+
+impl AmongUsContract {
+    pub(crate) fn roster_root(env: &Env) -> BytesN<32> {
+        let mut alive = Vec::new(env);
+        for (_, p) in Self::read_players(env).iter() {
+            if p.alive {
+                alive.push_back(p.player_hash);
+            }
+        }
+        env.crypto().sha256(&alive.to_xdr(env))
+    }
+
+    pub(crate) fn anonymous_voting(env: &Env) -> bool {
+        env.storage().instance().has(&AnonKey::AnonVoting)
+    }
+
+    pub(crate) fn anon_tally(env: &Env, round: u32) -> Map<BytesN<32>, u32> {
+        env.storage()
+            .instance()
+            .get(&AnonKey::VoteTally(round))
+            .unwrap_or(Map::new(env))
+    }
+
+    fn suspicion_heat(env: &Env, round: u32) -> Map<BytesN<32>, u32> {
+        env.storage()
+            .instance()
+            .get(&AnonKey::SuspicionHeat(round))
+            .unwrap_or(Map::new(env))
+    }
+
+    pub(crate) fn anon_votes_for(env: &Env, round: u32, target_hash: &BytesN<32>) -> u32 {
+        Self::anon_tally(env, round)
+            .get(target_hash.clone())
+            .unwrap_or(0)
+    }
+
+    // Proves "some alive player" without naming one; the nullifier stops one member acting twice.
+    pub(crate) fn verify_anonymous_member(
+        env: &Env,
+        round: u32,
+        proof: ProofInput,
+    ) -> Result<(), GameError> {
+        Self::ensure_not_casual(env)?;
+        if !env.storage().instance().has(&AnonKey::AnonMeetings) {
+            fail(env, ModuleError::FeatureDisabled);
+        }
+        if env
+            .storage()
+            .instance()
+            .has(&DataKey::UsedNullifier(proof.nullifier.clone()))
+        {
+            return Err(GameError::NullifierUsed);
+        }
+        let mut public_inputs = Vec::new(env);
+        public_inputs.push_back(Self::roster_root(env));
+        public_inputs.push_back(Self::field_input(env, round));
+        public_inputs.push_back(proof.nullifier.clone());
+        Self::consume_nullifier(env, proof.nullifier);
+        if !Self::verify_circuit(env, CIRCUIT_MEMBER, proof.proof, public_inputs) {
+            return Err(GameError::InvalidProof);
+        }
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    pub fn configure_anonymous_meetings(env: Env, caller: Address, enabled: bool) {
+        Self::require_operator(&env, &caller);
+        if enabled {
+            env.storage().instance().set(&AnonKey::AnonMeetings, &true);
+        } else {
+            env.storage().instance().remove(&AnonKey::AnonMeetings);
+        }
+    }
+
+    // Anonymous and attributed votes are exclusive per match, so nobody can vote once in each mode.
+    pub fn configure_anonymous_voting(env: Env, caller: Address, enabled: bool) {
+        Self::require_operator(&env, &caller);
+        Self::ensure_lobby(&env, true);
+        if enabled {
+            env.storage().instance().set(&AnonKey::AnonVoting, &true);
+        } else {
+            env.storage().instance().remove(&AnonKey::AnonVoting);
+        }
+    }
+
+    pub fn start_meeting_anon(env: Env, proof: ProofInput) -> Result<Receipt, GameError> {
+        Self::ensure_not_ended(&env);
+        Self::ensure_not_paused(&env);
+        let mut state = Self::read_state(&env);
+        if state.phase != PHASE_PLAYING {
+            return Err(GameError::WrongPhase);
+        }
+        if state.button_used_round == state.round {
+            return Err(GameError::ButtonUsed);
+        }
+        Self::ensure_meeting_allowed(&env, None)?;
+        Self::verify_anonymous_member(&env, state.round, proof)?;
+        Self::open_meeting(&env, &mut state, true);
+        let receipt = Self::record_action(
+            &env,
+            symbol_short!("meeting"),
+            BytesN::from_array(&env, &[0; 32]),
+        );
+        env.events().publish(
+            (symbol_short!("meeting"), symbol_short!("anon")),
+            state.round,
+        );
+        Ok(receipt)
+    }
+
+    // The proof binds `[roster_root, round, target_hash, nullifier]`; only the tally is stored.
+    pub fn submit_vote_anon(env: Env, vote: VoteInput) -> Result<Receipt, GameError> {
+        Self::ensure_not_ended(&env);
+        Self::ensure_not_paused(&env);
+        Self::ensure_not_casual(&env)?;
+        if !Self::anonymous_voting(&env) {
+            fail(&env, ModuleError::FeatureDisabled);
+        }
+        let state = Self::read_state(&env);
+        if state.phase != PHASE_MEETING {
+            return Err(GameError::WrongPhase);
+        }
+        if env
+            .storage()
+            .instance()
+            .has(&DataKey::UsedNullifier(vote.nullifier.clone()))
+        {
+            return Err(GameError::NullifierUsed);
+        }
+        let mut tally = Self::anon_tally(&env, state.round);
+        let cast: u32 = tally.values().iter().sum();
+        if cast >= Self::count_alive(&Self::read_players(&env)) {
+            return Err(GameError::QuotaReached);
+        }
+
+        let mut public_inputs = Vec::new(&env);
+        public_inputs.push_back(Self::roster_root(&env));
+        public_inputs.push_back(Self::field_input(&env, state.round));
+        public_inputs.push_back(vote.target_hash.clone());
+        public_inputs.push_back(vote.nullifier.clone());
+        Self::consume_nullifier(&env, vote.nullifier);
+        let votes = tally.get(vote.target_hash.clone()).unwrap_or(0);
+        tally.set(vote.target_hash.clone(), votes + 1);
+        env.storage()
+            .instance()
+            .set(&AnonKey::VoteTally(state.round), &tally);
+        if !Self::verify_circuit(&env, CIRCUIT_VOTE, vote.proof, public_inputs) {
+            return Err(GameError::InvalidProof);
+        }
+
+        let receipt = Self::record_action(
+            &env,
+            symbol_short!("vote"),
+            BytesN::from_array(&env, &[0; 32]),
+        );
+        env.events().publish(
+            (symbol_short!("voted"), symbol_short!("anon")),
+            vote.target_hash,
+        );
+        Ok(receipt)
+    }
+
+    pub fn configure_suspicion_signals(env: Env, caller: Address, enabled: bool) {
+        Self::require_operator(&env, &caller);
+        if enabled {
+            env.storage()
+                .instance()
+                .set(&AnonKey::SuspicionSignals, &true);
+        } else {
+            env.storage().instance().remove(&AnonKey::SuspicionSignals);
+        }
+    }
+
+    // A non-binding "I suspect this player" from some alive member, proved like an anonymous
+    // vote over `[roster_root, round, target_hash, nullifier]`. Only per-target counts are
+    // kept, so the heat map shows how the room leans without showing who leans where.
+    pub fn signal_suspicion(env: Env, signal: VoteInput) -> Result<u32, GameError> {
+        Self::ensure_not_ended(&env);
+        Self::ensure_not_paused(&env);
+        Self::ensure_not_casual(&env)?;
+        if !env.storage().instance().has(&AnonKey::SuspicionSignals) {
+            fail(&env, ModuleError::FeatureDisabled);
+        }
+        let state = Self::read_state(&env);
+        if state.phase != PHASE_MEETING {
+            return Err(GameError::MeetingNotActive);
+        }
+        if env
+            .storage()
+            .instance()
+            .has(&DataKey::UsedNullifier(signal.nullifier.clone()))
+        {
+            return Err(GameError::NullifierUsed);
+        }
+        let mut heat = Self::suspicion_heat(&env, state.round);
+        let sent: u32 = heat.values().iter().sum();
+        if sent >= Self::count_alive(&Self::read_players(&env)) {
+            return Err(GameError::QuotaReached);
+        }
+
+        let mut public_inputs = Vec::new(&env);
+        public_inputs.push_back(Self::roster_root(&env));
+        public_inputs.push_back(Self::field_input(&env, state.round));
+        public_inputs.push_back(signal.target_hash.clone());
+        public_inputs.push_back(signal.nullifier.clone());
+        Self::consume_nullifier(&env, signal.nullifier);
+        let signals = heat.get(signal.target_hash.clone()).unwrap_or(0) + 1;
+        heat.set(signal.target_hash.clone(), signals);
+        env.storage()
+            .instance()
+            .set(&AnonKey::SuspicionHeat(state.round), &heat);
+        if !Self::verify_circuit(&env, CIRCUIT_SUSPECT, signal.proof, public_inputs) {
+            return Err(GameError::InvalidProof);
+        }
+        env.events().publish(
+            (symbol_short!("suspect"), signal.target_hash),
+            (state.round, signals),
+        );
+        Ok(signals)
+    }
+
+    pub fn get_suspicion_heat(env: Env, round: u32) -> Map<BytesN<32>, u32> {
+        Self::suspicion_heat(&env, round)
+    }
+
+    pub fn get_anon_tally(env: Env, round: u32) -> Map<BytesN<32>, u32> {
+        Self::anon_tally(&env, round)
+    }
+
+    pub fn get_roster_root(env: Env) -> BytesN<32> {
+        Self::roster_root(&env)
+    }
+}
@@ -0,0 +1,65 @@
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, Env, Vec};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::fail;
+use crate::{AmongUsContract, AmongUsContractClient, DataKey, MapConfig, ModuleError};
+
+const MAX_BATCH_OPS: u32 = 20;
+
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+pub enum AdminOp {
+    ConfigureGame(u32, u32),
+    ConfigureQuotas(u32, u32),
+    ConfigureKillRange(u32),
+    ConfigureKillCooldown(u64),
+    ConfigureVotingDeadline(u64),
+    ConfigureGhostTasks(bool),
+    ConfigureTaskDuration(u64),
+    RegisterMap(u32, MapConfig),
+    SelectMap(u32),
+    SetVerifier(Address),
+    SetOperator(Address),
+    AllowlistAdd(Vec<Address>),
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    // Ops run in order under a single cold-admin authorization; any failure reverts the
+    // whole batch, so tournament setup lands all at once or not at all.
+    pub fn batch_admin(env: Env, admin: Address, ops: Vec<AdminOp>) {
+        Self::require_admin(&env, &admin);
+        if ops.is_empty() || ops.len() > MAX_BATCH_OPS {
+            fail(&env, ModuleError::InvalidBatch);
+        }
+        for op in ops.iter() {
+            match op {
+                AdminOp::ConfigureGame(max_players, tasks_to_win) => {
+                    Self::apply_game_config(&env, max_players, tasks_to_win)
+                }
+                AdminOp::ConfigureQuotas(max_tasks, max_kills) => {
+                    Self::apply_quotas(&env, max_tasks, max_kills)
+                }
+                AdminOp::ConfigureKillRange(kill_range) => Self::apply_kill_range(&env, kill_range),
+                AdminOp::ConfigureKillCooldown(secs) => Self::apply_kill_cooldown(&env, secs),
+                AdminOp::ConfigureVotingDeadline(secs) => Self::apply_voting_deadline(&env, secs),
+                AdminOp::ConfigureGhostTasks(enabled) => Self::apply_ghost_tasks(&env, enabled),
+                AdminOp::ConfigureTaskDuration(min_seconds) => {
+                    Self::apply_task_duration(&env, min_seconds)
+                }
+                AdminOp::RegisterMap(map_id, map) => {
+                    Self::apply_register_map(&env, &admin, map_id, &map);
+                }
+                AdminOp::SelectMap(map_id) => Self::apply_select_map(&env, &admin, map_id),
+                AdminOp::SetVerifier(verifier) => {
+                    env.storage().instance().set(&DataKey::Verifier, &verifier)
+                }
+                AdminOp::SetOperator(operator) => Self::apply_operator(&env, &admin, operator),
+                AdminOp::AllowlistAdd(players) => Self::apply_allowlist_add(&env, &admin, &players),
+            }
+        }
+        env.events()
+            .publish((symbol_short!("batch"), admin), ops.len());
+    }
+}
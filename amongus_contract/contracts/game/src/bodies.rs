@@ -0,0 +1,108 @@
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, BytesN, Env, Vec};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::{fail, PHASE_PLAYING};
+use crate::{AmongUsContract, AmongUsContractClient, GameError, ModuleError, Receipt};
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum BodyKey {
+    // Killed players nobody has reported since the last meeting.
+    Bodies,
+    ReportRange,
+}
+
+impl AmongUsContract {
+    fn bodies(env: &Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&BodyKey::Bodies)
+            .unwrap_or(Vec::new(env))
+    }
+
+    pub(crate) fn note_body(env: &Env, victim: &Address) {
+        let mut bodies = Self::bodies(env);
+        bodies.push_back(victim.clone());
+        env.storage().instance().set(&BodyKey::Bodies, &bodies);
+    }
+
+    // Every meeting sweeps the map, so a body is reported at most once.
+    pub(crate) fn clear_bodies(env: &Env) {
+        env.storage().instance().remove(&BodyKey::Bodies);
+    }
+
+    // Dead players cannot move, so the victim's stored position is where the body lies.
+    pub(crate) fn apply_report_body(
+        env: &Env,
+        reporter: Address,
+        victim: Address,
+    ) -> Result<Receipt, GameError> {
+        Self::ensure_not_ended(env);
+        Self::ensure_not_paused(env);
+        let mut state = Self::read_state(env);
+        if state.phase != PHASE_PLAYING {
+            return Err(GameError::WrongPhase);
+        }
+        let players = Self::read_players(env);
+        let reporter_entry = players
+            .get(reporter.clone())
+            .ok_or(GameError::PlayerNotFound)?;
+        if !reporter_entry.alive {
+            return Err(GameError::PlayerDead);
+        }
+        let body = players
+            .get(victim.clone())
+            .ok_or(GameError::PlayerNotFound)?;
+        if body.alive || !Self::bodies(env).contains(&victim) {
+            fail(env, ModuleError::NoBody);
+        }
+        // Under fog the stored positions go stale, so only the kill itself was range-checked.
+        #[cfg(feature = "fog-of-war")]
+        let positions_public = !Self::fog_enabled(env);
+        #[cfg(not(feature = "fog-of-war"))]
+        let positions_public = true;
+        let range: u32 = env
+            .storage()
+            .instance()
+            .get(&BodyKey::ReportRange)
+            .unwrap_or(0);
+        if positions_public && range > 0 {
+            let dx = reporter_entry.x.abs_diff(body.x) as u64;
+            let dy = reporter_entry.y.abs_diff(body.y) as u64;
+            if dx * dx + dy * dy > range as u64 * range as u64 {
+                fail(env, ModuleError::OutOfReach);
+            }
+        }
+
+        Self::open_meeting(env, &mut state, false);
+        Self::meter(env, &reporter, None);
+        let receipt = Self::record_action(env, symbol_short!("report"), body.player_hash);
+        env.events()
+            .publish((symbol_short!("reported"), reporter), (victim, state.round));
+        Ok(receipt)
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    // Largest reporter-to-body distance in map units; 0 leaves reports unbounded.
+    pub fn configure_report_range(env: Env, caller: Address, report_range: u32) {
+        Self::require_operator(&env, &caller);
+        env.storage()
+            .instance()
+            .set(&BodyKey::ReportRange, &report_range);
+    }
+
+    pub fn report_body(env: Env, reporter: Address, victim: Address) -> Result<Receipt, GameError> {
+        reporter.require_auth();
+        Self::apply_report_body(&env, reporter, victim)
+    }
+
+    // Vitals: the players killed since the last meeting whose bodies nobody has reported.
+    // Only the killers know those before a report, so this takes an impostor read grant.
+    pub fn get_vitals(env: Env, caller: Address, grant_salt: Option<BytesN<32>>) -> Vec<Address> {
+        Self::ensure_read_grant(&env, &caller, grant_salt, symbol_short!("impostor"));
+        Self::bodies(&env)
+    }
+}
@@ -0,0 +1,161 @@
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, BytesN, Env, Vec};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::PHASE_ENDED;
+use crate::{AmongUsContract, AmongUsContractClient, GameError};
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum BountyKey {
+    Bounties(BytesN<32>),
+    FirstVoter(BytesN<32>),
+}
+
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+pub struct Bounty {
+    pub poster: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+impl AmongUsContract {
+    fn read_bounties(env: &Env, target_hash: &BytesN<32>) -> Vec<Bounty> {
+        env.storage()
+            .instance()
+            .get(&BountyKey::Bounties(target_hash.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+
+    fn write_bounties(env: &Env, target_hash: &BytesN<32>, bounties: &Vec<Bounty>) {
+        if bounties.is_empty() {
+            env.storage()
+                .instance()
+                .remove(&BountyKey::Bounties(target_hash.clone()));
+        } else {
+            env.storage()
+                .instance()
+                .set(&BountyKey::Bounties(target_hash.clone()), bounties);
+        }
+    }
+
+    pub(crate) fn note_bounty_vote(
+        env: &Env,
+        target_hash: &BytesN<32>,
+        voter: &Address,
+        round: u32,
+    ) {
+        if !env
+            .storage()
+            .instance()
+            .has(&BountyKey::Bounties(target_hash.clone()))
+        {
+            return;
+        }
+        let key = BountyKey::FirstVoter(target_hash.clone());
+        let first: Option<(u32, Address)> = env.storage().instance().get(&key);
+        if first.map(|(r, _)| r != round).unwrap_or(true) {
+            env.storage().instance().set(&key, &(round, voter.clone()));
+        }
+    }
+
+    pub(crate) fn pay_bounties(env: &Env, target_hash: &BytesN<32>, round: u32) {
+        let bounties = Self::read_bounties(env, target_hash);
+        if bounties.is_empty() {
+            return;
+        }
+        let first: Option<(u32, Address)> = env
+            .storage()
+            .instance()
+            .get(&BountyKey::FirstVoter(target_hash.clone()));
+        let Some((voted_round, hunter)) = first else {
+            return;
+        };
+        if voted_round != round {
+            return;
+        }
+
+        let mut total = 0i128;
+        for bounty in bounties.iter() {
+            Self::escrow_release(env, &hunter, &bounty.token, bounty.amount);
+            total += bounty.amount;
+        }
+        Self::write_bounties(env, target_hash, &Vec::new(env));
+        env.storage()
+            .instance()
+            .remove(&BountyKey::FirstVoter(target_hash.clone()));
+        env.events().publish(
+            (symbol_short!("bounty"), hunter),
+            (target_hash.clone(), total),
+        );
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    pub fn post_bounty(
+        env: Env,
+        poster: Address,
+        target_hash: BytesN<32>,
+        token: Address,
+        amount: i128,
+    ) -> Result<(), GameError> {
+        poster.require_auth();
+        Self::ensure_not_ended(&env);
+        Self::ensure_not_casual(&env)?;
+        if amount <= 0 {
+            return Err(GameError::AmountNotPositive);
+        }
+        let target_alive = Self::read_players(&env)
+            .iter()
+            .any(|(_, p)| p.player_hash == target_hash && p.alive);
+        if !target_alive {
+            return Err(GameError::PlayerNotFound);
+        }
+
+        Self::escrow_deposit(&env, &poster, &token, amount);
+        let mut bounties = Self::read_bounties(&env, &target_hash);
+        bounties.push_back(Bounty {
+            poster: poster.clone(),
+            token,
+            amount,
+        });
+        Self::write_bounties(&env, &target_hash, &bounties);
+        env.events()
+            .publish((symbol_short!("bnty_post"), poster), (target_hash, amount));
+        Ok(())
+    }
+
+    pub fn refund_bounties(
+        env: Env,
+        poster: Address,
+        target_hash: BytesN<32>,
+    ) -> Result<i128, GameError> {
+        poster.require_auth();
+        if Self::read_state(&env).phase != PHASE_ENDED {
+            return Err(GameError::GameNotEnded);
+        }
+
+        let mut remaining = Vec::new(&env);
+        let mut refunded = 0i128;
+        for bounty in Self::read_bounties(&env, &target_hash).iter() {
+            if bounty.poster == poster {
+                Self::escrow_release(&env, &poster, &bounty.token, bounty.amount);
+                refunded += bounty.amount;
+            } else {
+                remaining.push_back(bounty);
+            }
+        }
+        Self::write_bounties(&env, &target_hash, &remaining);
+        env.events().publish(
+            (symbol_short!("bnty_rfnd"), poster),
+            (target_hash, refunded),
+        );
+        Ok(refunded)
+    }
+
+    pub fn get_bounties(env: Env, target_hash: BytesN<32>) -> Vec<Bounty> {
+        Self::read_bounties(&env, &target_hash)
+    }
+}
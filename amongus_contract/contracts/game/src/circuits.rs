@@ -0,0 +1,254 @@
+use soroban_sdk::{
+    contractimpl, contracttype, symbol_short, Address, BytesN, Env, String, Symbol, Val, Vec,
+};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::{fail, CIRCUIT_IDS};
+use crate::{clock, AmongUsContract, AmongUsContractClient, DataKey, GameError};
+
+// A candidate must shadow live traffic this long before it can be promoted.
+const MIN_SHADOW_SECS: u64 = 24 * 60 * 60;
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum CircuitKey {
+    VkCount(Symbol),
+    VkEntry(Symbol, u32),
+    CandidateVk(Symbol),
+    ActiveVk(Symbol),
+}
+
+// The verifier contract embeds the verification key; the hashes let anyone rebuild the
+// circuit and setup and check they produce that key.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VkRegistration {
+    pub verifier: Address,
+    pub circuit_hash: BytesN<32>,
+    pub toolchain: String,
+    pub transcript_hash: BytesN<32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VkInfo {
+    pub version: u32,
+    pub verifier: Address,
+    pub circuit_hash: BytesN<32>,
+    pub toolchain: String,
+    pub transcript_hash: BytesN<32>,
+    pub registered_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ShadowVk {
+    pub vk: VkInfo,
+    pub expires_at: u64,
+}
+
+impl AmongUsContract {
+    fn vk_info(env: &Env, version: u32, vk: VkRegistration) -> VkInfo {
+        VkInfo {
+            version,
+            verifier: vk.verifier,
+            circuit_hash: vk.circuit_hash,
+            toolchain: vk.toolchain,
+            transcript_hash: vk.transcript_hash,
+            registered_at: Self::ledger_now(env),
+        }
+    }
+
+    fn ensure_known_circuit(env: &Env, circuit_id: &Symbol) {
+        if !CIRCUIT_IDS.contains(circuit_id) {
+            fail(env, GameError::InvalidConfig);
+        }
+    }
+
+    fn vk_count(env: &Env, circuit_id: &Symbol) -> u32 {
+        env.storage()
+            .instance()
+            .get(&CircuitKey::VkCount(circuit_id.clone()))
+            .unwrap_or(0)
+    }
+
+    pub(crate) fn read_vk(env: &Env, circuit_id: &Symbol, version: u32) -> Option<VkInfo> {
+        env.storage()
+            .persistent()
+            .get(&CircuitKey::VkEntry(circuit_id.clone(), version))
+    }
+
+    fn active_version(env: &Env, circuit_id: &Symbol) -> Option<u32> {
+        env.storage()
+            .instance()
+            .get(&CircuitKey::ActiveVk(circuit_id.clone()))
+            .or_else(|| Self::vk_count(env, circuit_id).checked_sub(1))
+    }
+
+    pub(crate) fn active_vk(env: &Env, circuit_id: &Symbol) -> Option<VkInfo> {
+        Self::active_version(env, circuit_id)
+            .and_then(|version| Self::read_vk(env, circuit_id, version))
+    }
+
+    // Entries are persistent and append-only, so every key a circuit was ever judged
+    // against stays readable through `get_vk_log`; the newest entry becomes active.
+    fn append_vk(env: &Env, circuit_id: &Symbol, vk: VkRegistration) -> VkInfo {
+        let version = Self::vk_count(env, circuit_id);
+        let info = Self::vk_info(env, version, vk);
+        env.storage()
+            .persistent()
+            .set(&CircuitKey::VkEntry(circuit_id.clone(), version), &info);
+        env.storage()
+            .instance()
+            .set(&CircuitKey::VkCount(circuit_id.clone()), &(version + 1));
+        env.storage()
+            .instance()
+            .set(&CircuitKey::ActiveVk(circuit_id.clone()), &version);
+        env.events()
+            .publish((symbol_short!("vk_set"), circuit_id.clone()), info.clone());
+        info
+    }
+
+    // Circuits without a registered key fall back to the game-wide verifier.
+    pub(crate) fn circuit_verifier(env: &Env, circuit_id: &Symbol) -> Address {
+        match Self::active_vk(env, circuit_id) {
+            Some(info) => info.verifier,
+            None => env
+                .storage()
+                .instance()
+                .get(&DataKey::Verifier)
+                .unwrap_or_else(|| fail(env, GameError::VerifierNotConfigured)),
+        }
+    }
+
+    fn read_candidate(env: &Env, circuit_id: &Symbol) -> Option<ShadowVk> {
+        env.storage()
+            .instance()
+            .get(&CircuitKey::CandidateVk(circuit_id.clone()))
+    }
+
+    // The candidate sees the same call as the active key but never decides it. A rejected
+    // proof reverts the whole call, so logged disagreements are proofs the active key
+    // accepted and the candidate did not.
+    pub(crate) fn shadow_verify(env: &Env, circuit_id: &Symbol, args: Vec<Val>, verified: bool) {
+        let Some(candidate) = Self::read_candidate(env, circuit_id) else {
+            return;
+        };
+        if Self::ledger_now(env) >= candidate.expires_at {
+            return;
+        }
+        let shadow = Self::invoke_verifier(env, &candidate.vk.verifier, args);
+        env.events().publish(
+            (symbol_short!("shadow"), circuit_id.clone()),
+            (verified, shadow),
+        );
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    pub fn set_circuit_vk(
+        env: Env,
+        caller: Address,
+        circuit_id: Symbol,
+        vk: VkRegistration,
+    ) -> u32 {
+        Self::require_admin(&env, &caller);
+        Self::ensure_known_circuit(&env, &circuit_id);
+        Self::append_vk(&env, &circuit_id, vk).version
+    }
+
+    pub fn set_candidate_vk(
+        env: Env,
+        caller: Address,
+        circuit_id: Symbol,
+        vk: VkRegistration,
+        shadow_secs: u64,
+    ) {
+        Self::require_admin(&env, &caller);
+        Self::ensure_known_circuit(&env, &circuit_id);
+        // A window shorter than the shadow period could never be promoted.
+        if shadow_secs < MIN_SHADOW_SECS {
+            fail(&env, GameError::InvalidConfig);
+        }
+        let vk = Self::vk_info(&env, Self::vk_count(&env, &circuit_id), vk);
+        let candidate = ShadowVk {
+            expires_at: vk.registered_at.saturating_add(shadow_secs),
+            vk,
+        };
+        env.storage()
+            .instance()
+            .set(&CircuitKey::CandidateVk(circuit_id.clone()), &candidate);
+        env.events()
+            .publish((symbol_short!("vk_cand"), circuit_id), candidate);
+    }
+
+    pub fn clear_candidate_vk(env: Env, caller: Address, circuit_id: Symbol) {
+        Self::require_admin(&env, &caller);
+        env.storage()
+            .instance()
+            .remove(&CircuitKey::CandidateVk(circuit_id.clone()));
+        env.events()
+            .publish((symbol_short!("vk_clear"), circuit_id), ());
+    }
+
+    // Only time spent inside the shadow window counts towards the minimum.
+    pub fn promote_candidate_vk(env: Env, caller: Address, circuit_id: Symbol) -> u32 {
+        Self::require_admin(&env, &caller);
+        let candidate = Self::read_candidate(&env, &circuit_id)
+            .unwrap_or_else(|| fail(&env, GameError::InvalidConfig));
+        let shadowed_until = Self::ledger_now(&env).min(candidate.expires_at);
+        if !clock::has_elapsed(candidate.vk.registered_at, MIN_SHADOW_SECS, shadowed_until) {
+            fail(&env, GameError::InvalidConfig);
+        }
+        let from = Self::active_version(&env, &circuit_id);
+        let vk = candidate.vk;
+        let promoted = Self::append_vk(
+            &env,
+            &circuit_id,
+            VkRegistration {
+                verifier: vk.verifier,
+                circuit_hash: vk.circuit_hash,
+                toolchain: vk.toolchain,
+                transcript_hash: vk.transcript_hash,
+            },
+        );
+        env.storage()
+            .instance()
+            .remove(&CircuitKey::CandidateVk(circuit_id.clone()));
+        env.events().publish(
+            (symbol_short!("vk_promo"), circuit_id),
+            (from, promoted.version),
+        );
+        promoted.version
+    }
+
+    // Steps the active key back one version in the log; the log itself is untouched.
+    pub fn rollback_vk(env: Env, caller: Address, circuit_id: Symbol) -> u32 {
+        Self::require_admin(&env, &caller);
+        let from = Self::active_version(&env, &circuit_id)
+            .unwrap_or_else(|| fail(&env, GameError::InvalidConfig));
+        let to = from
+            .checked_sub(1)
+            .unwrap_or_else(|| fail(&env, GameError::InvalidConfig));
+        env.storage()
+            .instance()
+            .set(&CircuitKey::ActiveVk(circuit_id.clone()), &to);
+        env.events()
+            .publish((symbol_short!("vk_roll"), circuit_id), (from, to));
+        to
+    }
+
+    pub fn get_candidate_vk(env: Env, circuit_id: Symbol) -> Option<ShadowVk> {
+        Self::read_candidate(&env, &circuit_id)
+    }
+
+    pub fn get_vk_info(env: Env, circuit_id: Symbol) -> Option<VkInfo> {
+        Self::active_vk(&env, &circuit_id)
+    }
+
+    pub fn get_vk_log(env: Env, circuit_id: Symbol, version: u32) -> Option<VkInfo> {
+        Self::read_vk(&env, &circuit_id, version)
+    }
+}
@@ -0,0 +1,30 @@
+use game_core::TimeSource;
+use soroban_sdk::Env;
+
+use crate::{AmongUsContract, MatchPauseKey};
+
+// The window arithmetic lives in `game_core`, shared with simulators that drive it from a
+// virtual clock; the contract adapter reads the ledger.
+pub(crate) use game_core::clock::{deadline, elapsed, has_elapsed, paused_clock, within_window};
+
+pub struct LedgerClock<'a>(pub &'a Env);
+
+impl TimeSource for LedgerClock<'_> {
+    fn now(&self) -> u64 {
+        self.0.ledger().timestamp()
+    }
+}
+
+impl AmongUsContract {
+    pub(crate) fn ledger_now(env: &Env) -> u64 {
+        LedgerClock(env).now()
+    }
+
+    pub(crate) fn match_time(env: &Env) -> u64 {
+        paused_clock(
+            Self::ledger_now(env),
+            env.storage().instance().get(&MatchPauseKey::MatchPausedAt),
+            Self::paused_total(env),
+        )
+    }
+}
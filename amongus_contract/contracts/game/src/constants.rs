@@ -0,0 +1,71 @@
+use soroban_sdk::{panic_with_error, symbol_short, Env, Error, Symbol};
+
+pub(crate) const PHASE_LOBBY: Symbol = symbol_short!("lobby");
+pub(crate) const PHASE_PLAYING: Symbol = symbol_short!("playing");
+pub(crate) const PHASE_MEETING: Symbol = symbol_short!("meeting");
+pub(crate) const PHASE_ENDED: Symbol = symbol_short!("ended");
+
+pub(crate) const WINNER_NONE: Symbol = symbol_short!("none");
+pub(crate) const WINNER_CREW: Symbol = symbol_short!("crew");
+pub(crate) const WINNER_IMPOSTOR: Symbol = symbol_short!("impost");
+// A match called off by `void_match`: stakes were refunded and nobody won.
+pub(crate) const WINNER_VOID: Symbol = symbol_short!("void");
+
+pub(crate) const COSMETIC_NONE: Symbol = symbol_short!("none");
+
+// Reserved vote target: a vote for it counts toward keeping everyone on board. No player may
+// join under it.
+pub(crate) const SKIP_VOTE_TARGET: [u8; 32] = [0xff; 32];
+
+// Auto-assignment walks this order, starting after the color that was asked for.
+pub(crate) const COLOR_PALETTE: [Symbol; 12] = [
+    symbol_short!("Red"),
+    symbol_short!("Blu"),
+    symbol_short!("Gre"),
+    symbol_short!("Pnk"),
+    symbol_short!("Org"),
+    symbol_short!("Yel"),
+    symbol_short!("Blk"),
+    symbol_short!("Wht"),
+    symbol_short!("Pur"),
+    symbol_short!("Brn"),
+    symbol_short!("Cyn"),
+    symbol_short!("Lim"),
+];
+
+// One id per proof kind; each can be routed to its own verification key.
+pub(crate) const CIRCUIT_ROLE: Symbol = symbol_short!("role");
+pub(crate) const CIRCUIT_TASK: Symbol = symbol_short!("task");
+pub(crate) const CIRCUIT_VOTE: Symbol = symbol_short!("vote");
+pub(crate) const CIRCUIT_KILL: Symbol = symbol_short!("kill");
+pub(crate) const CIRCUIT_MOVE: Symbol = symbol_short!("move");
+pub(crate) const CIRCUIT_DECOY: Symbol = symbol_short!("decoy");
+pub(crate) const CIRCUIT_MEMBER: Symbol = symbol_short!("member");
+pub(crate) const CIRCUIT_HUMAN: Symbol = symbol_short!("human");
+pub(crate) const CIRCUIT_SUSPECT: Symbol = symbol_short!("suspect");
+pub(crate) const CIRCUIT_REVEAL: Symbol = symbol_short!("reveal");
+pub(crate) const CIRCUIT_FIX: Symbol = symbol_short!("fix");
+// Comfortably above a Groth16 or PLONK proof; `configure_max_proof_len` raises it for larger schemes.
+pub(crate) const DEFAULT_MAX_PROOF_LEN: u32 = 2048;
+// Stats bucket for direct `verify_zk_proof` calls; not a routable circuit.
+pub(crate) const CIRCUIT_DIRECT: Symbol = symbol_short!("direct");
+pub(crate) const CIRCUIT_IDS: [Symbol; 11] = [
+    CIRCUIT_ROLE,
+    CIRCUIT_TASK,
+    CIRCUIT_VOTE,
+    CIRCUIT_KILL,
+    CIRCUIT_MOVE,
+    CIRCUIT_DECOY,
+    CIRCUIT_MEMBER,
+    CIRCUIT_HUMAN,
+    CIRCUIT_SUSPECT,
+    CIRCUIT_REVEAL,
+    CIRCUIT_FIX,
+];
+
+// Single failure path for coded errors, `GameError` or `ModuleError`: the client sees
+// `Error(Contract, code)` whether the error is raised here or returned from an entry point.
+#[cold]
+pub(crate) fn fail(env: &Env, error: impl Into<Error>) -> ! {
+    panic_with_error!(env, error)
+}
@@ -0,0 +1,24 @@
+use soroban_sdk::{contractimpl, symbol_short, Address, Env, Symbol};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::fail;
+use crate::{AmongUsContract, AmongUsContractClient, GameError};
+
+#[contractimpl]
+impl AmongUsContract {
+    pub fn set_cosmetics(env: Env, player: Address, hat: Symbol, skin: Symbol) {
+        player.require_auth();
+        Self::ensure_lobby(&env, false);
+        let mut players = Self::read_players(&env);
+        let mut entry = players
+            .get(player.clone())
+            .unwrap_or_else(|| fail(&env, GameError::PlayerNotFound));
+        entry.hat = hat.clone();
+        entry.skin = skin.clone();
+        players.set(player.clone(), entry);
+        Self::write_players(&env, &players);
+        env.events()
+            .publish((symbol_short!("cosmetic"), player), (hat, skin));
+    }
+}
@@ -0,0 +1,110 @@
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, Env, Vec};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::fail;
+use crate::{
+    clock, AmongUsContract, AmongUsContractClient, GameError, ModuleError, Receipt, VoteInput,
+};
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum DelegationKey {
+    VoteDelegate(Address),
+    DelegateAfter,
+    MeetingOpenedAt,
+    DelegatedVotes(u32),
+}
+
+impl AmongUsContract {
+    pub(crate) fn note_meeting_opened(env: &Env) {
+        env.storage()
+            .instance()
+            .set(&DelegationKey::MeetingOpenedAt, &Self::match_time(env));
+    }
+
+    fn delegated_votes(env: &Env, round: u32) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DelegationKey::DelegatedVotes(round))
+            .unwrap_or(Vec::new(env))
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    pub fn configure_delegation(env: Env, caller: Address, delegate_after: u64) {
+        Self::require_operator(&env, &caller);
+        env.storage()
+            .instance()
+            .set(&DelegationKey::DelegateAfter, &delegate_after);
+    }
+
+    pub fn delegate_vote(env: Env, player: Address, delegate: Address) -> Result<(), GameError> {
+        player.require_auth();
+        Self::ensure_lobby(&env, false);
+        if !Self::read_players(&env).contains_key(player.clone()) {
+            return Err(GameError::PlayerNotFound);
+        }
+        if delegate == player {
+            fail(&env, ModuleError::SelfTarget);
+        }
+        env.storage()
+            .instance()
+            .set(&DelegationKey::VoteDelegate(player.clone()), &delegate);
+        env.events()
+            .publish((symbol_short!("delegated"), player), delegate);
+        Ok(())
+    }
+
+    pub fn submit_delegated_vote(
+        env: Env,
+        delegate: Address,
+        player: Address,
+        vote: VoteInput,
+    ) -> Result<Receipt, GameError> {
+        delegate.require_auth();
+        let assigned: Address = env
+            .storage()
+            .instance()
+            .get(&DelegationKey::VoteDelegate(player.clone()))
+            .unwrap_or_else(|| fail(&env, ModuleError::NotDelegate));
+        if assigned != delegate {
+            fail(&env, ModuleError::NotDelegate);
+        }
+        let delegate_after: u64 = env
+            .storage()
+            .instance()
+            .get(&DelegationKey::DelegateAfter)
+            .unwrap_or_else(|| fail(&env, ModuleError::FeatureDisabled));
+        let opened_at: u64 = env
+            .storage()
+            .instance()
+            .get(&DelegationKey::MeetingOpenedAt)
+            .unwrap_or(0);
+        if !clock::has_elapsed(opened_at, delegate_after, Self::match_time(&env)) {
+            fail(&env, ModuleError::DelegateWindowClosed);
+        }
+
+        let receipt = Self::cast_vote(&env, &player, vote)?;
+        let round = Self::read_state(&env).round;
+        let mut delegated = Self::delegated_votes(&env, round);
+        delegated.push_back(player.clone());
+        env.storage()
+            .instance()
+            .set(&DelegationKey::DelegatedVotes(round), &delegated);
+        env.events()
+            .publish((symbol_short!("dlg_vote"), delegate), player);
+        Ok(receipt)
+    }
+
+    pub fn get_vote_delegate(env: Env, player: Address) -> Option<Address> {
+        env.storage()
+            .instance()
+            .get(&DelegationKey::VoteDelegate(player))
+    }
+
+    pub fn get_delegated_votes(env: Env, round: u32) -> Vec<Address> {
+        Self::delegated_votes(&env, round)
+    }
+}
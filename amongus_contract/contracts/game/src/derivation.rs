@@ -0,0 +1,82 @@
+use soroban_sdk::{contractimpl, contracttype, xdr::ToXdr, Address, Bytes, BytesN, Env, Symbol};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::fail;
+use crate::{AmongUsContract, AmongUsContractClient, ModuleError};
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum DerivationKey {
+    NullifierCheck,
+}
+
+impl AmongUsContract {
+    fn nullifier_check(env: &Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DerivationKey::NullifierCheck)
+            .unwrap_or(false)
+    }
+
+    // sha256(game_scope || commitment || action symbol XDR || round || seq), with round and
+    // seq as big-endian u32s
+    fn derived_nullifier(
+        env: &Env,
+        commitment: &BytesN<32>,
+        action: Symbol,
+        round: u32,
+        seq: u32,
+    ) -> BytesN<32> {
+        let mut preimage = Bytes::from(Self::game_scope(env));
+        preimage.append(&commitment.clone().into());
+        preimage.append(&action.to_xdr(env));
+        preimage.append(&Bytes::from_array(env, &round.to_be_bytes()));
+        preimage.append(&Bytes::from_array(env, &seq.to_be_bytes()));
+        env.crypto().sha256(&preimage)
+    }
+
+    // With the check on, a nullifier is no longer the prover's choice: it must be the
+    // derivation of the actor's commitment for the current round. `seq` counts the actor's
+    // earlier uses of the action this round, so quotas above one still get fresh nullifiers.
+    pub(crate) fn ensure_derived_nullifier(
+        env: &Env,
+        action: Symbol,
+        commitment: Option<BytesN<32>>,
+        seq: u32,
+        nullifier: &BytesN<32>,
+    ) {
+        if !Self::nullifier_check(env) {
+            return;
+        }
+        let commitment = commitment.unwrap_or_else(|| fail(env, ModuleError::NullifierMismatch));
+        let round = Self::read_state(env).round;
+        if Self::derived_nullifier(env, &commitment, action, round, seq) != *nullifier {
+            fail(env, ModuleError::NullifierMismatch);
+        }
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    pub fn configure_nullifier_check(env: Env, caller: Address, enforced: bool) {
+        Self::require_operator(&env, &caller);
+        env.storage()
+            .instance()
+            .set(&DerivationKey::NullifierCheck, &enforced);
+    }
+
+    pub fn derive_nullifier(
+        env: Env,
+        role_secret_commitment: BytesN<32>,
+        action: Symbol,
+        round: u32,
+        seq: u32,
+    ) -> BytesN<32> {
+        Self::derived_nullifier(&env, &role_secret_commitment, action, round, seq)
+    }
+
+    pub fn get_nullifier_check(env: Env) -> bool {
+        Self::nullifier_check(&env)
+    }
+}
@@ -0,0 +1,108 @@
+use soroban_sdk::{contractimpl, contracttype, Address, Env};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::{clock, AmongUsContract, AmongUsContractClient, GameError};
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum EmergencyKey {
+    MaxEmergencyMeetings,
+    MeetingCooldown,
+    // Match time the last meeting closed at, by end or finalize.
+    MeetingClosedAt,
+    EmergencyMeetingsUsed(Address),
+}
+
+impl AmongUsContract {
+    fn max_emergency_meetings(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&EmergencyKey::MaxEmergencyMeetings)
+            .unwrap_or(0)
+    }
+
+    pub(crate) fn emergency_meetings_used(env: &Env, player: &Address) -> u32 {
+        env.storage()
+            .instance()
+            .get(&EmergencyKey::EmergencyMeetingsUsed(player.clone()))
+            .unwrap_or(0)
+    }
+
+    // Runs on match time, like the sabotage cooldown, so a pause holds it.
+    fn meeting_cooldown_left(env: &Env) -> u64 {
+        let storage = env.storage().instance();
+        let Some(closed_at) = storage.get::<_, u64>(&EmergencyKey::MeetingClosedAt) else {
+            return 0;
+        };
+        let cooldown: u64 = storage.get(&EmergencyKey::MeetingCooldown).unwrap_or(0);
+        clock::deadline(closed_at, cooldown).saturating_sub(Self::match_time(env))
+    }
+
+    // `None` for anonymous calls, which only the cooldown can hold back.
+    pub(crate) fn ensure_meeting_allowed(
+        env: &Env,
+        caller: Option<&Address>,
+    ) -> Result<(), GameError> {
+        if Self::meeting_cooldown_left(env) > 0 {
+            return Err(GameError::MeetingCooldown);
+        }
+        let max = Self::max_emergency_meetings(env);
+        match caller {
+            Some(caller) if max > 0 && Self::emergency_meetings_used(env, caller) >= max => {
+                Err(GameError::ButtonUsed)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn note_emergency_meeting(env: &Env, caller: &Address) {
+        let used = Self::emergency_meetings_used(env, caller) + 1;
+        env.storage()
+            .instance()
+            .set(&EmergencyKey::EmergencyMeetingsUsed(caller.clone()), &used);
+    }
+
+    pub(crate) fn note_meeting_closed(env: &Env) {
+        env.storage()
+            .instance()
+            .set(&EmergencyKey::MeetingClosedAt, &Self::match_time(env));
+    }
+
+    // Meetings left for `player` this round: the button caps it at one either way.
+    pub(crate) fn emergency_meetings_left(env: &Env, player: &Address, button_free: bool) -> u32 {
+        if !button_free {
+            return 0;
+        }
+        match Self::max_emergency_meetings(env) {
+            0 => 1,
+            max => max
+                .saturating_sub(Self::emergency_meetings_used(env, player))
+                .min(1),
+        }
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    // Each player may call `max_per_player` emergency meetings per match, 0 for no limit.
+    // No emergency meeting may open until `cooldown_secs` of match time after the last
+    // meeting closed; body reports are exempt from both.
+    pub fn configure_emergency_meetings(
+        env: Env,
+        caller: Address,
+        max_per_player: u32,
+        cooldown_secs: u64,
+    ) {
+        Self::require_operator(&env, &caller);
+        let storage = env.storage().instance();
+        storage.set(&EmergencyKey::MaxEmergencyMeetings, &max_per_player);
+        storage.set(&EmergencyKey::MeetingCooldown, &cooldown_secs);
+    }
+
+    // Match seconds until an emergency meeting may open again. A failed call's events are
+    // discarded, so clients read the wait here.
+    pub fn get_meeting_cooldown(env: Env) -> u64 {
+        Self::meeting_cooldown_left(&env)
+    }
+}
@@ -0,0 +1,120 @@
+use soroban_sdk::contracterror;
+
+// Stable codes for the failures clients need to tell apart. The contract spec caps an
+// error enum at 50 cases, so the core match rules live here and the optional modules get
+// `ModuleError`, numbered on from 51 so no code means two things. Operator-only failures
+// share the nearest code (a missing admin or operator role is `NotAdmin`, bad settings are
+// `InvalidConfig`).
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum GameError {
+    AlreadyInitialized = 1,
+    NotAdmin = 2,
+    GameEnded = 3,
+    GamePaused = 4,
+    MatchPaused = 5,
+    WrongPhase = 6,
+    LobbyFull = 7,
+    LobbyHasOpenSlots = 8,
+    JoinQueueFull = 9,
+    AlreadyJoined = 10,
+    AlreadyQueued = 11,
+    NotQueued = 12,
+    DuplicatePlayerHash = 13,
+    ColorTaken = 14,
+    NoFreeColors = 15,
+    NotEnoughPlayers = 16,
+    PlayerNotFound = 17,
+    PlayerDead = 18,
+    VictimDead = 19,
+    AlreadyVoted = 20,
+    VotesAnonymous = 21,
+    NoAliveVoters = 22,
+    ButtonUsed = 23,
+    MeetingNotActive = 24,
+    QuotaReached = 25,
+    NullifierUsed = 26,
+    RoleSpent = 27,
+    InvalidProof = 28,
+    VerifierNotConfigured = 29,
+    VerifierDisabled = 30,
+    VerifierFailed = 31,
+    InvalidConfig = 32,
+    GameNotEnded = 33,
+    EconomyNotConfigured = 34,
+    AmountNotPositive = 35,
+    InsufficientEscrow = 36,
+    FogDisabled = 37,
+    SubmitterSlashed = 38,
+    InvalidCheckpoint = 39,
+    OutOfKillRange = 40,
+    KillCooldown = 41,
+    VictimMoved = 42,
+    SabotageActive = 43,
+    SabotageCooldown = 44,
+    MeetingCooldown = 45,
+    PayoutClaimed = 46,
+    NotAtConsole = 47,
+    TaskTooFast = 48,
+    SabotageNotActive = 49,
+    CasualMatch = 50,
+}
+
+// Entry points declare `GameError`; these are raised through `fail`, and reach the client
+// as the same `Error(Contract, code)`.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ModuleError {
+    LobbyNotEmpty = 51,
+    NothingToReveal = 52,
+    ProofTooLarge = 53,
+    ReadGrantRequired = 54,
+    GrantHandleUsed = 55,
+    NotAllowlisted = 56,
+    InvalidBatch = 57,
+    FeatureDisabled = 58,
+    NoBody = 59,
+    OutOfReach = 60,
+    NullifierMismatch = 61,
+    FlavorNotAllowed = 62,
+    PositionNotCommitted = 63,
+    PositionsCommitted = 64,
+    PositionsNotBound = 65,
+    PlayerAlive = 66,
+    RateLimited = 67,
+    NoCoveringIncident = 68,
+    IdentityInUse = 69,
+    ReservationsHeld = 70,
+    OffMap = 71,
+    MapNotFound = 72,
+    InvalidMap = 73,
+    NameRejected = 74,
+    NotAttested = 75,
+    AlreadyAttested = 76,
+    HashNotOpened = 77,
+    NotProfileOwner = 78,
+    ReferralCodeTaken = 79,
+    UnknownReferralCode = 80,
+    SelfTarget = 81,
+    PoolFunded = 82,
+    AlreadyCollected = 83,
+    RetentionNotOver = 84,
+    RewardsClaimed = 85,
+    ScenarioNotFound = 86,
+    ScenarioCompleted = 87,
+    ObjectiveNotMet = 88,
+    NoPendingResult = 89,
+    ResultPending = 90,
+    ChallengeWindowClosed = 91,
+    ChallengeWindowOpen = 92,
+    SameResult = 93,
+    NotYourTurn = 94,
+    UnknownTask = 95,
+    TaskNotStarted = 96,
+    NotDelegate = 97,
+    DelegateWindowClosed = 98,
+    UnknownSabotage = 99,
+    StakeUnclaimed = 100,
+}
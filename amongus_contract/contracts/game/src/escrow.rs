@@ -0,0 +1,374 @@
+use soroban_sdk::{contractimpl, contracttype, symbol_short, token, Address, Env};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::{fail, CIRCUIT_ROLE, PHASE_ENDED, WINNER_IMPOSTOR, WINNER_VOID};
+use crate::{
+    AmongUsContract, AmongUsContractClient, DataKey, GameError, ModuleError, ProofInput, VaultKey,
+};
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum EscrowKey {
+    Economy,
+    StakePot,
+    PayoutClaimed(Address),
+    Rake,
+    RakeTaken,
+    // Insurance share of the rake moved into the pool when the match started.
+    RakeInsured,
+}
+
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+pub struct EconomyConfig {
+    pub stake_token: Address,
+    pub stake_amount: i128,
+    pub reward_token: Address,
+    pub mint_rewards: bool,
+    pub reward_minter: Address,
+    pub reward_per_winner: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+pub struct RakeConfig {
+    pub treasury: Address,
+    pub rake_bps: u32,
+    pub burn_bps: u32,
+}
+
+impl AmongUsContract {
+    pub(crate) fn read_economy(env: &Env) -> Option<EconomyConfig> {
+        env.storage().instance().get(&EscrowKey::Economy)
+    }
+
+    fn escrow_burn(env: &Env, token: &Address, amount: i128) {
+        let balance = Self::escrow_balance(env, token);
+        if amount > balance {
+            fail(env, GameError::InsufficientEscrow);
+        }
+        env.storage()
+            .instance()
+            .set(&VaultKey::EscrowBalance(token.clone()), &(balance - amount));
+        token::Client::new(env, token).burn(&env.current_contract_address(), &amount);
+    }
+
+    // The insurance share of the rake leaves the pot as it locks at the start, so a match
+    // voided before it ends is already covered by its own rake.
+    pub(crate) fn reserve_insurance(env: &Env) {
+        let Some(rake) = env
+            .storage()
+            .instance()
+            .get::<_, RakeConfig>(&EscrowKey::Rake)
+        else {
+            return;
+        };
+        let pot: i128 = env
+            .storage()
+            .instance()
+            .get(&EscrowKey::StakePot)
+            .unwrap_or(0);
+        let total = pot * rake.rake_bps as i128 / 10_000;
+        let insured = total * Self::insurance_bps(env) as i128 / 10_000;
+        if insured == 0 {
+            return;
+        }
+        env.storage()
+            .instance()
+            .set(&EscrowKey::StakePot, &(pot - insured));
+        env.storage()
+            .instance()
+            .set(&EscrowKey::RakeInsured, &insured);
+        Self::add_to_insurance_pool(env, insured);
+        env.events()
+            .publish((symbol_short!("ins_rsrv"), rake.treasury), insured);
+    }
+
+    fn take_rake(env: &Env, config: &EconomyConfig) {
+        if env.storage().instance().has(&EscrowKey::RakeTaken) {
+            return;
+        }
+        env.storage().instance().set(&EscrowKey::RakeTaken, &true);
+        let Some(rake) = env
+            .storage()
+            .instance()
+            .get::<_, RakeConfig>(&EscrowKey::Rake)
+        else {
+            return;
+        };
+
+        let pot: i128 = env
+            .storage()
+            .instance()
+            .get(&EscrowKey::StakePot)
+            .unwrap_or(0);
+        let reserved: i128 = env
+            .storage()
+            .instance()
+            .get(&EscrowKey::RakeInsured)
+            .unwrap_or(0);
+        let total = ((pot + reserved) * rake.rake_bps as i128 / 10_000).max(reserved);
+        if total == 0 {
+            return;
+        }
+        let insured = (total * Self::insurance_bps(env) as i128 / 10_000).max(reserved);
+        let burned = (total - insured) * rake.burn_bps as i128 / 10_000;
+        let to_treasury = total - insured - burned;
+        env.storage()
+            .instance()
+            .set(&EscrowKey::StakePot, &(pot - (total - reserved)));
+        if burned > 0 {
+            Self::escrow_burn(env, &config.stake_token, burned);
+        }
+        if to_treasury > 0 {
+            Self::escrow_release(env, &rake.treasury, &config.stake_token, to_treasury);
+        }
+        if insured > reserved {
+            Self::add_to_insurance_pool(env, insured - reserved);
+        }
+        env.events().publish(
+            (symbol_short!("rake"), rake.treasury),
+            (to_treasury, burned, insured),
+        );
+    }
+
+    // Payouts stop once `gc` collects the match, so what the winners left unclaimed goes to
+    // the rake treasury. Without a treasury there is nowhere to send it and `gc` must wait.
+    pub(crate) fn sweep_unclaimed_stake(env: &Env) {
+        let Some(config) = Self::read_economy(env) else {
+            return;
+        };
+        if Self::read_state(env).winner == WINNER_VOID {
+            return;
+        }
+        Self::take_rake(env, &config);
+        let pot: i128 = env
+            .storage()
+            .instance()
+            .get(&EscrowKey::StakePot)
+            .unwrap_or(0);
+        let winners = Self::winner_count(env);
+        let claimed = Self::read_players(env)
+            .keys()
+            .iter()
+            .filter(|player| {
+                env.storage()
+                    .instance()
+                    .has(&EscrowKey::PayoutClaimed(player.clone()))
+            })
+            .count() as u32;
+        let unclaimed = pot / winners as i128 * winners.saturating_sub(claimed) as i128;
+        if unclaimed == 0 {
+            return;
+        }
+        let Some(rake) = env
+            .storage()
+            .instance()
+            .get::<_, RakeConfig>(&EscrowKey::Rake)
+        else {
+            fail(env, ModuleError::StakeUnclaimed);
+        };
+        Self::escrow_release(env, &rake.treasury, &config.stake_token, unclaimed);
+        env.events()
+            .publish((symbol_short!("unclaimed"), rake.treasury), unclaimed);
+    }
+
+    pub(crate) fn collect_stake(env: &Env, player: &Address) {
+        let held = Self::hold_stake(env, player);
+        Self::add_to_pot(env, held);
+    }
+
+    // Queued players deposit up front; the stake joins the pot only on admission.
+    pub(crate) fn hold_stake(env: &Env, player: &Address) -> i128 {
+        let Some(config) = Self::read_economy(env) else {
+            return 0;
+        };
+        if config.stake_amount == 0 {
+            return 0;
+        }
+        Self::escrow_deposit(env, player, &config.stake_token, config.stake_amount);
+        config.stake_amount
+    }
+
+    pub(crate) fn add_to_pot(env: &Env, amount: i128) {
+        if amount == 0 {
+            return;
+        }
+        let pot: i128 = env
+            .storage()
+            .instance()
+            .get(&EscrowKey::StakePot)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&EscrowKey::StakePot, &(pot + amount));
+    }
+
+    pub(crate) fn release_held_stake(env: &Env, player: &Address, amount: i128) {
+        if amount == 0 {
+            return;
+        }
+        let config =
+            Self::read_economy(env).unwrap_or_else(|| fail(env, GameError::EconomyNotConfigured));
+        Self::escrow_release(env, player, &config.stake_token, amount);
+    }
+
+    // The economy is fixed once anyone has joined, so the current stake is what they paid.
+    pub(crate) fn refund_stake(env: &Env, player: &Address) {
+        let Some(config) = Self::read_economy(env) else {
+            return;
+        };
+        if config.stake_amount == 0 {
+            return;
+        }
+        Self::add_to_pot(env, -config.stake_amount);
+        Self::escrow_release(env, player, &config.stake_token, config.stake_amount);
+    }
+
+    fn winner_count(env: &Env) -> u32 {
+        let state = Self::read_state(env);
+        let players = Self::read_players(env).len();
+        let winners = if state.winner == WINNER_IMPOSTOR {
+            state.impostor_count
+        } else {
+            players.saturating_sub(state.impostor_count)
+        };
+        winners.max(1)
+    }
+
+    fn pay_reward(env: &Env, config: &EconomyConfig, to: &Address) {
+        if config.reward_per_winner == 0 {
+            return;
+        }
+        if config.mint_rewards {
+            token::StellarAssetClient::new(env, &config.reward_minter)
+                .mint(to, &config.reward_per_winner);
+        } else {
+            Self::escrow_release(env, to, &config.reward_token, config.reward_per_winner);
+        }
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    pub fn configure_economy(env: Env, caller: Address, config: EconomyConfig) {
+        Self::require_admin(&env, &caller);
+        Self::ensure_lobby(&env, true);
+        if config.stake_amount < 0 || config.reward_per_winner < 0 {
+            fail(&env, GameError::InvalidConfig);
+        }
+        env.storage().instance().set(&EscrowKey::Economy, &config);
+    }
+
+    pub fn configure_rake(
+        env: Env,
+        caller: Address,
+        treasury: Address,
+        rake_bps: u32,
+        burn_bps: u32,
+    ) {
+        Self::require_admin(&env, &caller);
+        if rake_bps > 10_000 || burn_bps > 10_000 {
+            fail(&env, GameError::InvalidConfig);
+        }
+        let storage = env.storage().instance();
+        if storage.has(&EscrowKey::RakeTaken) || storage.has(&EscrowKey::RakeInsured) {
+            fail(&env, GameError::InvalidConfig);
+        }
+        env.storage().instance().set(
+            &EscrowKey::Rake,
+            &RakeConfig {
+                treasury,
+                rake_bps,
+                burn_bps,
+            },
+        );
+    }
+
+    pub fn fund_rewards(env: Env, funder: Address, amount: i128) {
+        funder.require_auth();
+        let config =
+            Self::read_economy(&env).unwrap_or_else(|| fail(&env, GameError::EconomyNotConfigured));
+        if config.mint_rewards {
+            fail(&env, GameError::InvalidConfig);
+        }
+        if amount <= 0 {
+            fail(&env, GameError::AmountNotPositive);
+        }
+        Self::escrow_deposit(&env, &funder, &config.reward_token, amount);
+        env.events()
+            .publish((symbol_short!("funded"), funder), amount);
+    }
+
+    pub fn claim_payout(env: Env, player: Address, proof: ProofInput) -> Result<i128, GameError> {
+        player.require_auth();
+        Self::ensure_not_casual(&env)?;
+        let state = Self::read_state(&env);
+        // A voided match never settles: `void_match` already handed the stakes back.
+        if state.phase != PHASE_ENDED || state.winner == WINNER_VOID {
+            return Err(GameError::GameNotEnded);
+        }
+        let config = Self::read_economy(&env).ok_or(GameError::EconomyNotConfigured)?;
+        let entry = Self::read_players(&env)
+            .get(player.clone())
+            .ok_or(GameError::PlayerNotFound)?;
+        if env
+            .storage()
+            .instance()
+            .has(&EscrowKey::PayoutClaimed(player.clone()))
+        {
+            return Err(GameError::PayoutClaimed);
+        }
+
+        if env
+            .storage()
+            .instance()
+            .has(&DataKey::UsedNullifier(proof.nullifier.clone()))
+        {
+            return Err(GameError::NullifierUsed);
+        }
+        if !proof.public_inputs.contains(&entry.role_hash)
+            || !proof
+                .public_inputs
+                .contains(Self::result_hash(&env, &state.winner))
+        {
+            return Err(GameError::InvalidProof);
+        }
+        let mut public_inputs = proof.public_inputs.clone();
+        public_inputs.push_back(proof.nullifier.clone());
+        Self::consume_nullifier(&env, proof.nullifier);
+        env.storage()
+            .instance()
+            .set(&EscrowKey::PayoutClaimed(player.clone()), &true);
+        if !Self::verify_circuit(&env, CIRCUIT_ROLE, proof.proof, public_inputs) {
+            return Err(GameError::InvalidProof);
+        }
+
+        Self::take_rake(&env, &config);
+        let pot: i128 = env
+            .storage()
+            .instance()
+            .get(&EscrowKey::StakePot)
+            .unwrap_or(0);
+        let share = pot / Self::winner_count(&env) as i128;
+        if share > 0 {
+            Self::escrow_release(&env, &player, &config.stake_token, share);
+        }
+        Self::pay_reward(&env, &config, &player);
+
+        env.events().publish(
+            (symbol_short!("payout"), player),
+            (share, config.reward_per_winner),
+        );
+        Ok(share)
+    }
+
+    pub fn get_economy(env: Env) -> Option<EconomyConfig> {
+        Self::read_economy(&env)
+    }
+
+    pub fn get_rake(env: Env) -> Option<RakeConfig> {
+        env.storage().instance().get(&EscrowKey::Rake)
+    }
+}
@@ -0,0 +1,60 @@
+use soroban_sdk::{contractimpl, contracttype, Address, Env, Vec};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::fail;
+use crate::{AmongUsContract, AmongUsContractClient, GameError, ModuleError};
+
+const MAX_KILL_FLAVORS: u32 = 32;
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum FlavorKey {
+    KillFlavors,
+    MatchFlavors,
+}
+
+impl AmongUsContract {
+    // Flavor 0 is the plain kill and is always accepted.
+    pub(crate) fn note_kill_flavor(env: &Env, flavor: u32) {
+        if flavor != 0 && !Self::kill_flavors(env).contains(flavor) {
+            fail(env, ModuleError::FlavorNotAllowed);
+        }
+        let mut played = Self::match_flavors(env);
+        played.push_back(flavor);
+        env.storage()
+            .instance()
+            .set(&FlavorKey::MatchFlavors, &played);
+    }
+
+    pub(crate) fn match_flavors(env: &Env) -> Vec<u32> {
+        env.storage()
+            .instance()
+            .get(&FlavorKey::MatchFlavors)
+            .unwrap_or(Vec::new(env))
+    }
+
+    fn kill_flavors(env: &Env) -> Vec<u32> {
+        env.storage()
+            .instance()
+            .get(&FlavorKey::KillFlavors)
+            .unwrap_or(Vec::new(env))
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    pub fn configure_kill_flavors(env: Env, caller: Address, flavors: Vec<u32>) {
+        Self::require_operator(&env, &caller);
+        if flavors.len() > MAX_KILL_FLAVORS {
+            fail(&env, GameError::InvalidConfig);
+        }
+        env.storage()
+            .instance()
+            .set(&FlavorKey::KillFlavors, &flavors);
+    }
+
+    pub fn get_kill_flavors(env: Env) -> Vec<u32> {
+        Self::kill_flavors(&env)
+    }
+}
@@ -0,0 +1,223 @@
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, BytesN, Env, Vec};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::{fail, CIRCUIT_DECOY, CIRCUIT_MOVE, PHASE_PLAYING};
+use crate::{AmongUsContract, AmongUsContractClient, DataKey, GameError, ModuleError, ProofInput};
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum FogKey {
+    FogSpeed,
+    PosCommit(Address),
+    DecoyRadius,
+}
+
+impl AmongUsContract {
+    pub(crate) fn fog_enabled(env: &Env) -> bool {
+        env.storage().instance().has(&FogKey::FogSpeed)
+    }
+
+    pub(crate) fn position_commitment(env: &Env, player: &Address) -> BytesN<32> {
+        env.storage()
+            .instance()
+            .get(&FogKey::PosCommit(player.clone()))
+            .unwrap_or_else(|| fail(env, ModuleError::PositionNotCommitted))
+    }
+
+    pub(crate) fn ensure_spawns_committed(env: &Env) {
+        if !Self::fog_enabled(env) {
+            return;
+        }
+        for (addr, _) in Self::read_players(env).iter() {
+            Self::position_commitment(env, &addr);
+        }
+    }
+
+    pub(crate) fn ensure_public_positions(env: &Env) {
+        if Self::fog_enabled(env) {
+            fail(env, ModuleError::PositionsCommitted);
+        }
+    }
+
+    // In fog mode kills must prove adjacency over both current commitments.
+    pub(crate) fn ensure_fog_kill_inputs(
+        env: &Env,
+        killer: &Address,
+        victim: &Address,
+        proof: &ProofInput,
+    ) {
+        if !Self::fog_enabled(env) {
+            return;
+        }
+        if !proof
+            .public_inputs
+            .contains(Self::position_commitment(env, killer))
+            || !proof
+                .public_inputs
+                .contains(Self::position_commitment(env, victim))
+        {
+            fail(env, ModuleError::PositionsNotBound);
+        }
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    pub fn configure_fog(env: Env, caller: Address, enabled: bool, max_speed: u32) {
+        Self::require_operator(&env, &caller);
+        Self::ensure_lobby(&env, true);
+        if enabled {
+            if max_speed == 0 {
+                fail(&env, GameError::InvalidConfig);
+            }
+            env.storage().instance().set(&FogKey::FogSpeed, &max_speed);
+        } else {
+            env.storage().instance().remove(&FogKey::FogSpeed);
+        }
+    }
+
+    pub fn commit_spawn(
+        env: Env,
+        player: Address,
+        commitment: BytesN<32>,
+    ) -> Result<(), GameError> {
+        player.require_auth();
+        if !Self::fog_enabled(&env) {
+            return Err(GameError::FogDisabled);
+        }
+        Self::ensure_lobby(&env, false);
+        if !Self::read_players(&env).contains_key(player.clone()) {
+            return Err(GameError::PlayerNotFound);
+        }
+        env.storage()
+            .instance()
+            .set(&FogKey::PosCommit(player.clone()), &commitment);
+        env.events()
+            .publish((symbol_short!("pos_cmt"), player), commitment);
+        Ok(())
+    }
+
+    pub fn submit_fog_move(
+        env: Env,
+        player: Address,
+        new_commitment: BytesN<32>,
+        proof: ProofInput,
+    ) -> Result<(), GameError> {
+        player.require_auth();
+        Self::ensure_not_ended(&env);
+        Self::ensure_not_paused(&env);
+        let max_speed: u32 = env
+            .storage()
+            .instance()
+            .get(&FogKey::FogSpeed)
+            .ok_or(GameError::FogDisabled)?;
+        if Self::read_state(&env).phase != PHASE_PLAYING {
+            return Err(GameError::WrongPhase);
+        }
+        let entry = Self::read_players(&env)
+            .get(player.clone())
+            .ok_or(GameError::PlayerNotFound)?;
+        if !entry.alive {
+            return Err(GameError::PlayerDead);
+        }
+        if env
+            .storage()
+            .instance()
+            .has(&DataKey::UsedNullifier(proof.nullifier.clone()))
+        {
+            return Err(GameError::NullifierUsed);
+        }
+
+        let old_commitment = Self::position_commitment(&env, &player);
+        let mut public_inputs = Vec::new(&env);
+        public_inputs.push_back(old_commitment);
+        public_inputs.push_back(new_commitment.clone());
+        public_inputs.push_back(Self::field_input(&env, max_speed));
+        public_inputs.push_back(proof.nullifier.clone());
+        Self::consume_nullifier(&env, proof.nullifier);
+        env.storage()
+            .instance()
+            .set(&FogKey::PosCommit(player.clone()), &new_commitment);
+        Self::meter(&env, &player, Some(&proof.proof));
+        if !Self::verify_circuit(&env, CIRCUIT_MOVE, proof.proof, public_inputs) {
+            return Err(GameError::InvalidProof);
+        }
+
+        env.events()
+            .publish((symbol_short!("fog_move"), player), new_commitment);
+        Ok(())
+    }
+
+    pub fn configure_decoy_radius(env: Env, caller: Address, radius: u32) {
+        Self::require_operator(&env, &caller);
+        env.storage().instance().set(&FogKey::DecoyRadius, &radius);
+    }
+
+    // Every player may broadcast, so a missing public position does not single out impostors.
+    pub fn publish_decoy(
+        env: Env,
+        player: Address,
+        x: u32,
+        y: u32,
+        proof: ProofInput,
+    ) -> Result<(), GameError> {
+        player.require_auth();
+        Self::ensure_not_ended(&env);
+        Self::ensure_not_paused(&env);
+        if !Self::fog_enabled(&env) {
+            return Err(GameError::FogDisabled);
+        }
+        let radius: u32 = env
+            .storage()
+            .instance()
+            .get(&FogKey::DecoyRadius)
+            .unwrap_or_else(|| fail(&env, ModuleError::FeatureDisabled));
+        if Self::read_state(&env).phase != PHASE_PLAYING {
+            return Err(GameError::WrongPhase);
+        }
+        let mut players = Self::read_players(&env);
+        let mut entry = players
+            .get(player.clone())
+            .ok_or(GameError::PlayerNotFound)?;
+        if !entry.alive {
+            return Err(GameError::PlayerDead);
+        }
+        Self::ensure_on_map(&env, x, y);
+        if env
+            .storage()
+            .instance()
+            .has(&DataKey::UsedNullifier(proof.nullifier.clone()))
+        {
+            return Err(GameError::NullifierUsed);
+        }
+
+        let mut public_inputs = Vec::new(&env);
+        public_inputs.push_back(Self::position_commitment(&env, &player));
+        public_inputs.push_back(Self::field_input(&env, x));
+        public_inputs.push_back(Self::field_input(&env, y));
+        public_inputs.push_back(Self::field_input(&env, radius));
+        public_inputs.push_back(proof.nullifier.clone());
+        Self::consume_nullifier(&env, proof.nullifier);
+        entry.x = x;
+        entry.y = y;
+        players.set(player.clone(), entry);
+        Self::write_players(&env, &players);
+        Self::meter(&env, &player, Some(&proof.proof));
+        if !Self::verify_circuit(&env, CIRCUIT_DECOY, proof.proof, public_inputs) {
+            return Err(GameError::InvalidProof);
+        }
+
+        env.events()
+            .publish((symbol_short!("decoy"), player), (x, y));
+        Ok(())
+    }
+
+    pub fn get_fog_speed(env: Env) -> Option<u32> {
+        env.storage().instance().get(&FogKey::FogSpeed)
+    }
+
+    pub fn get_position_commitment(env: Env, player: Address) -> Option<BytesN<32>> {
+        env.storage().instance().get(&FogKey::PosCommit(player))
+    }
+}
@@ -0,0 +1,40 @@
+use soroban_sdk::{contractclient, contractimpl, xdr::ToXdr, Address, BytesN, Env, Symbol, Vec};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::PHASE_ENDED;
+use crate::{AmongUsContract, AmongUsContractClient, DataKey, MatchReport, RetentionKey};
+
+// The narrow read surface tournament, stats and betting contracts call, so they never
+// depend on the game's storage layout or snapshot types.
+#[contractclient(name = "GameResultClient")]
+pub trait IGameResult {
+    // `None` until the match ends.
+    fn winner(env: Env) -> Option<Symbol>;
+    // Seated players, dead or alive; empty once `gc` has collected the match.
+    fn participants(env: Env) -> Vec<Address>;
+    // SHA-256 of the match report's XDR, the same value `gc` keeps.
+    fn final_report_hash(env: Env) -> Option<BytesN<32>>;
+}
+
+#[contractimpl]
+impl IGameResult for AmongUsContract {
+    fn winner(env: Env) -> Option<Symbol> {
+        let state = Self::read_state(&env);
+        (state.phase == PHASE_ENDED).then_some(state.winner)
+    }
+
+    fn participants(env: Env) -> Vec<Address> {
+        Self::read_players(&env).keys()
+    }
+
+    fn final_report_hash(env: Env) -> Option<BytesN<32>> {
+        let storage = env.storage().instance();
+        if let Some(hash) = storage.get(&RetentionKey::ReportHash) {
+            return Some(hash);
+        }
+        storage
+            .get::<_, MatchReport>(&DataKey::MatchReport)
+            .map(|report| env.crypto().sha256(&report.to_xdr(&env)))
+    }
+}
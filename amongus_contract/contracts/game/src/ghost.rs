@@ -0,0 +1,95 @@
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, BytesN, Env, Vec};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::{fail, PHASE_ENDED, PHASE_LOBBY};
+use crate::{clock, AmongUsContract, AmongUsContractClient, GameError, ModuleError};
+
+const GHOST_COOLDOWN: u64 = 5;
+const MAX_GHOST_LOG: u32 = 64;
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum GhostKey {
+    GhostLog,
+    GhostLastSent(Address),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GhostMessage {
+    pub player: Address,
+    pub message_id: BytesN<32>,
+    pub round: u32,
+    pub timestamp: u64,
+}
+
+impl AmongUsContract {
+    fn ghost_log(env: &Env) -> Vec<GhostMessage> {
+        env.storage()
+            .instance()
+            .get(&GhostKey::GhostLog)
+            .unwrap_or(Vec::new(env))
+    }
+
+    pub(crate) fn clear_ghost(env: &Env, player: &Address) {
+        env.storage()
+            .instance()
+            .remove(&GhostKey::GhostLastSent(player.clone()));
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    // The event only signals that the dead are talking; who said what stays in the
+    // log, which contract reads expose once the match is over.
+    pub fn ghost_message(
+        env: Env,
+        player: Address,
+        message_id: BytesN<32>,
+    ) -> Result<(), GameError> {
+        player.require_auth();
+        Self::ensure_not_ended(&env);
+        Self::ensure_not_paused(&env);
+        let state = Self::read_state(&env);
+        if state.phase == PHASE_LOBBY {
+            return Err(GameError::WrongPhase);
+        }
+        let entry = Self::read_players(&env)
+            .get(player.clone())
+            .ok_or(GameError::PlayerNotFound)?;
+        if entry.alive {
+            fail(&env, ModuleError::PlayerAlive);
+        }
+
+        let now = Self::ledger_now(&env);
+        let last_key = GhostKey::GhostLastSent(player.clone());
+        if let Some(last) = env.storage().instance().get::<_, u64>(&last_key) {
+            if !clock::has_elapsed(last, GHOST_COOLDOWN, now) {
+                fail(&env, ModuleError::RateLimited);
+            }
+        }
+        env.storage().instance().set(&last_key, &now);
+
+        let mut log = Self::ghost_log(&env);
+        if log.len() >= MAX_GHOST_LOG {
+            log.pop_front();
+        }
+        log.push_back(GhostMessage {
+            player,
+            message_id,
+            round: state.round,
+            timestamp: now,
+        });
+        env.storage().instance().set(&GhostKey::GhostLog, &log);
+        env.events().publish((symbol_short!("ghost"),), state.round);
+        Ok(())
+    }
+
+    pub fn get_ghost_messages(env: Env) -> Vec<GhostMessage> {
+        if Self::read_state(&env).phase != PHASE_ENDED {
+            fail(&env, GameError::GameNotEnded);
+        }
+        Self::ghost_log(&env)
+    }
+}
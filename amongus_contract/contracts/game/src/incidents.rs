@@ -0,0 +1,106 @@
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Env, Symbol, Vec};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::{AmongUsContract, AmongUsContractClient};
+
+const INCIDENT_PAGE: u32 = 20;
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum IncidentKey {
+    IncidentCount,
+    IncidentEntry(u32),
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum IncidentReason {
+    Unspecified = 0,
+    Maintenance = 1,
+    VerifierFault = 2,
+    Exploit = 3,
+    Dispute = 4,
+    Infrastructure = 5,
+}
+
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+pub struct Incident {
+    pub seq: u32,
+    pub kind: Symbol,
+    pub reason: IncidentReason,
+    pub round: u32,
+    pub timestamp: u64,
+}
+
+impl AmongUsContract {
+    pub(crate) fn incident_count(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&IncidentKey::IncidentCount)
+            .unwrap_or(0)
+    }
+
+    pub(crate) fn read_incident(env: &Env, seq: u32) -> Option<Incident> {
+        env.storage()
+            .persistent()
+            .get(&IncidentKey::IncidentEntry(seq))
+    }
+
+    // Entries are persistent and append-only so the log outlives the match instance.
+    pub(crate) fn append_incident(
+        env: &Env,
+        kind: Symbol,
+        reason: IncidentReason,
+        round: u32,
+        timestamp: u64,
+    ) {
+        let seq = Self::incident_count(env);
+        env.storage().persistent().set(
+            &IncidentKey::IncidentEntry(seq),
+            &Incident {
+                seq,
+                kind: kind.clone(),
+                reason,
+                round,
+                timestamp,
+            },
+        );
+        env.storage()
+            .instance()
+            .set(&IncidentKey::IncidentCount, &(seq + 1));
+        env.events()
+            .publish((symbol_short!("incident"), seq), (kind, reason));
+    }
+
+    pub(crate) fn record_incident(env: &Env, kind: Symbol, reason: IncidentReason) {
+        Self::append_incident(
+            env,
+            kind,
+            reason,
+            Self::read_state(env).round,
+            Self::ledger_now(env),
+        );
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    // Pages forward from `cursor` (an incident seq); pass the last seq + 1 for the next page.
+    pub fn get_incidents(env: Env, cursor: u32) -> Vec<Incident> {
+        let end = Self::incident_count(&env).min(cursor.saturating_add(INCIDENT_PAGE));
+        let mut page = Vec::new(&env);
+        for seq in cursor..end {
+            if let Some(incident) = Self::read_incident(&env, seq) {
+                page.push_back(incident);
+            }
+        }
+        page
+    }
+
+    pub fn get_incident_count(env: Env) -> u32 {
+        Self::incident_count(&env)
+    }
+}
@@ -0,0 +1,128 @@
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, Env, Symbol};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::{fail, WINNER_VOID};
+use crate::{
+    AmongUsContract, AmongUsContractClient, EscrowKey, GameError, IncidentReason, ModuleError,
+};
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum InsuranceKey {
+    InsuranceBps,
+    InsurancePool,
+}
+
+impl AmongUsContract {
+    pub(crate) fn insurance_bps(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&InsuranceKey::InsuranceBps)
+            .unwrap_or(0)
+    }
+
+    fn insurance_pool(env: &Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&InsuranceKey::InsurancePool)
+            .unwrap_or(0)
+    }
+
+    pub(crate) fn add_to_insurance_pool(env: &Env, amount: i128) {
+        let pool = Self::insurance_pool(env) + amount;
+        env.storage()
+            .instance()
+            .set(&InsuranceKey::InsurancePool, &pool);
+    }
+
+    // A void is only covered when the log shows a pause or verifier shutdown still in force.
+    // The newest pause/verifier entry decides, so the scan walks back from the log head.
+    fn covered_incident(env: &Env) -> Option<Symbol> {
+        if !Self::is_paused(env) && !Self::is_verifier_disabled(env) {
+            return None;
+        }
+        for seq in (0..Self::incident_count(env)).rev() {
+            let Some(incident) = Self::read_incident(env, seq) else {
+                continue;
+            };
+            if incident.kind == symbol_short!("pause") || incident.kind == symbol_short!("vrf_off")
+            {
+                return Some(incident.kind);
+            }
+            if incident.kind == symbol_short!("unpause") || incident.kind == symbol_short!("vrf_on")
+            {
+                return None;
+            }
+        }
+        None
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    pub fn configure_insurance(env: Env, caller: Address, insurance_bps: u32) {
+        Self::require_admin(&env, &caller);
+        if insurance_bps > 10_000 {
+            fail(&env, GameError::InvalidConfig);
+        }
+        env.storage()
+            .instance()
+            .set(&InsuranceKey::InsuranceBps, &insurance_bps);
+    }
+
+    pub fn fund_insurance(env: Env, funder: Address, amount: i128) {
+        funder.require_auth();
+        if amount <= 0 {
+            fail(&env, GameError::AmountNotPositive);
+        }
+        let config =
+            Self::read_economy(&env).unwrap_or_else(|| fail(&env, GameError::EconomyNotConfigured));
+        Self::escrow_deposit(&env, &funder, &config.stake_token, amount);
+        Self::add_to_insurance_pool(&env, amount);
+        env.events()
+            .publish((symbol_short!("ins_fund"), funder), amount);
+    }
+
+    pub fn void_match(env: Env, caller: Address, reason: IncidentReason) -> Result<(), GameError> {
+        Self::require_admin(&env, &caller);
+        Self::ensure_not_ended(&env);
+        let Some(cause) = Self::covered_incident(&env) else {
+            fail(&env, ModuleError::NoCoveringIncident);
+        };
+
+        let mut refunded = 0i128;
+        if let Some(config) = Self::read_economy(&env) {
+            let mut pot: i128 = env
+                .storage()
+                .instance()
+                .get(&EscrowKey::StakePot)
+                .unwrap_or(0);
+            let mut pool = Self::insurance_pool(&env);
+            for (addr, _) in Self::read_players(&env).iter() {
+                let from_pot = pot.min(config.stake_amount);
+                let from_pool = (config.stake_amount - from_pot).min(pool);
+                pot -= from_pot;
+                pool -= from_pool;
+                if from_pot + from_pool > 0 {
+                    Self::escrow_release(&env, &addr, &config.stake_token, from_pot + from_pool);
+                    refunded += from_pot + from_pool;
+                }
+            }
+            env.storage().instance().set(&EscrowKey::StakePot, &pot);
+            env.storage()
+                .instance()
+                .set(&InsuranceKey::InsurancePool, &pool);
+        }
+
+        Self::record_incident(&env, symbol_short!("void"), reason);
+        Self::set_winner(&env, WINNER_VOID);
+        env.events()
+            .publish((symbol_short!("voided"), caller), (cause, refunded));
+        Ok(())
+    }
+
+    pub fn get_insurance_pool(env: Env) -> i128 {
+        Self::insurance_pool(&env)
+    }
+}
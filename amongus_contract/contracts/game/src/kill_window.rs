@@ -0,0 +1,91 @@
+use soroban_sdk::{contractimpl, contracttype, Address, Env};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::clock;
+use crate::{AmongUsContract, AmongUsContractClient, GameConfig, Player};
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum KillWindowKey {
+    VictimIdleSecs,
+    LastMove(Address),
+}
+
+// Where a player stood before their latest move, and when they left it.
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub struct MoveStamp {
+    pub at: u64,
+    pub from_x: u32,
+    pub from_y: u32,
+}
+
+impl AmongUsContract {
+    fn victim_idle_secs(env: &Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&KillWindowKey::VictimIdleSecs)
+            .unwrap_or(0)
+    }
+
+    // Stamps are only kept while the window is on; a move made before it was turned on
+    // cannot count against a kill.
+    pub(crate) fn note_move(env: &Env, player: &Address, from: &Player) {
+        if Self::victim_idle_secs(env) == 0 {
+            return;
+        }
+        env.storage().instance().set(
+            &KillWindowKey::LastMove(player.clone()),
+            &MoveStamp {
+                at: Self::ledger_now(env),
+                from_x: from.x,
+                from_y: from.y,
+            },
+        );
+    }
+
+    // A victim who moved inside the window must have been in reach at both ends of that
+    // move. Actions in one ledger apply in `action_seq` order: a move ordered before the
+    // kill counts against it, and one ordered after finds the victim already dead.
+    pub(crate) fn victim_moved_away(
+        env: &Env,
+        config: &GameConfig,
+        killer: &Player,
+        victim: &Address,
+    ) -> bool {
+        let window = Self::victim_idle_secs(env);
+        let stamp: Option<MoveStamp> = env
+            .storage()
+            .instance()
+            .get(&KillWindowKey::LastMove(victim.clone()));
+        match stamp {
+            Some(stamp) if !clock::has_elapsed(stamp.at, window, Self::ledger_now(env)) => {
+                !Self::within_kill_range(config, killer, stamp.from_x, stamp.from_y)
+            }
+            _ => false,
+        }
+    }
+
+    pub(crate) fn clear_move_stamp(env: &Env, player: &Address) {
+        env.storage()
+            .instance()
+            .remove(&KillWindowKey::LastMove(player.clone()));
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    // 0 turns the window off. Only positions outside `kill_range` conflict, so the window
+    // has no effect while kills are unbounded.
+    pub fn configure_victim_idle_window(env: Env, caller: Address, secs: u64) {
+        Self::require_operator(&env, &caller);
+        env.storage()
+            .instance()
+            .set(&KillWindowKey::VictimIdleSecs, &secs);
+    }
+
+    pub fn get_victim_idle_window(env: Env) -> u64 {
+        Self::victim_idle_secs(&env)
+    }
+}
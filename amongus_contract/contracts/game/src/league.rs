@@ -0,0 +1,90 @@
+use soroban_sdk::{contractimpl, contracttype, symbol_short, vec, Address, BytesN, Env, IntoVal};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::fail;
+use crate::{AmongUsContract, AmongUsContractClient, ModuleError};
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum LeagueKey {
+    LeagueRegistry,
+    ClaimedIdentity(Address),
+}
+
+impl AmongUsContract {
+    pub(crate) fn league_registry(env: &Env) -> Option<Address> {
+        env.storage().instance().get(&LeagueKey::LeagueRegistry)
+    }
+
+    // The registry is shared by every ranked game and refuses a player_hash that another
+    // live game already holds, so one secret identity cannot sit in two league matches.
+    pub(crate) fn claim_identity(env: &Env, player: &Address, player_hash: &BytesN<32>) {
+        let Some(registry) = Self::league_registry(env) else {
+            return;
+        };
+        let claimed: bool = env.invoke_contract(
+            &registry,
+            &symbol_short!("claim"),
+            vec![
+                env,
+                env.current_contract_address().into_val(env),
+                player_hash.into_val(env),
+            ],
+        );
+        if !claimed {
+            fail(env, ModuleError::IdentityInUse);
+        }
+        env.storage()
+            .instance()
+            .set(&LeagueKey::ClaimedIdentity(player.clone()), player_hash);
+    }
+
+    pub(crate) fn release_identity(env: &Env, player: &Address) {
+        let key = LeagueKey::ClaimedIdentity(player.clone());
+        let Some(player_hash) = env.storage().instance().get::<_, BytesN<32>>(&key) else {
+            return;
+        };
+        env.storage().instance().remove(&key);
+        let Some(registry) = Self::league_registry(env) else {
+            return;
+        };
+        env.invoke_contract::<()>(
+            &registry,
+            &symbol_short!("release"),
+            vec![
+                env,
+                env.current_contract_address().into_val(env),
+                player_hash.into_val(env),
+            ],
+        );
+    }
+
+    pub(crate) fn release_all_identities(env: &Env) {
+        for player in Self::read_players(env).keys().iter() {
+            Self::release_identity(env, &player);
+        }
+        for entry in Self::read_queue(env).iter() {
+            Self::release_identity(env, &entry.player);
+        }
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    pub fn configure_league_registry(env: Env, caller: Address, registry: Option<Address>) {
+        Self::require_operator(&env, &caller);
+        Self::ensure_lobby(&env, true);
+        match registry {
+            Some(registry) => env
+                .storage()
+                .instance()
+                .set(&LeagueKey::LeagueRegistry, &registry),
+            None => env.storage().instance().remove(&LeagueKey::LeagueRegistry),
+        }
+    }
+
+    pub fn get_league_registry(env: Env) -> Option<Address> {
+        Self::league_registry(&env)
+    }
+}
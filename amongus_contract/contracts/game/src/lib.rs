@@ -1,14 +1,142 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, vec, Address, BytesN, Env, IntoVal, Map,
-    Symbol, Val, Vec,
+    contract, contractimpl, contracttype, symbol_short, vec, xdr::ToXdr, Address, Bytes, BytesN,
+    Env, IntoVal, Map, Symbol, Val, Vec,
+};
+
+mod access;
+mod admin;
+mod afk;
+mod allowlist;
+mod analytics;
+#[cfg(feature = "anonymous-voting")]
+mod anon;
+mod batch;
+mod bodies;
+mod circuits;
+#[cfg(feature = "economy")]
+mod bounty;
+mod clock;
+mod constants;
+#[cfg(feature = "cosmetics")]
+mod cosmetics;
+mod delegation;
+mod derivation;
+mod emergency;
+mod errors;
+#[cfg(feature = "economy")]
+mod escrow;
+mod flavors;
+mod game_result;
+#[cfg(feature = "fog-of-war")]
+mod fog;
+mod ghost;
+mod incidents;
+#[cfg(feature = "economy")]
+mod insurance;
+mod kill_window;
+mod league;
+mod lobby;
+mod maps;
+mod match_pause;
+mod metering;
+mod migrations;
+mod multicall;
+mod names;
+mod ops;
+mod personhood;
+mod preimage;
+mod profile;
+mod reconcile;
+mod referrals;
+mod retention;
+#[cfg(feature = "economy")]
+mod rewards;
+mod sabotage;
+mod scenarios;
+mod settlement;
+mod shields;
+mod speakers;
+mod status;
+mod sync;
+mod tally;
+mod tasks;
+mod timeline;
+mod timers;
+mod vault;
+mod verifier_stats;
+mod win_conditions;
+
+pub use access::{AccessKey, ReadGrant};
+pub use admin::AdminKey;
+pub use afk::AfkKey;
+pub use allowlist::AllowlistKey;
+pub use analytics::{AnalyticsKey, MatchAnalytics};
+#[cfg(feature = "anonymous-voting")]
+pub use anon::AnonKey;
+pub use batch::AdminOp;
+pub use bodies::BodyKey;
+pub use clock::LedgerClock;
+pub use circuits::{CircuitKey, ShadowVk, VkInfo, VkRegistration};
+#[cfg(feature = "economy")]
+pub use bounty::{Bounty, BountyKey};
+pub use delegation::DelegationKey;
+pub use derivation::DerivationKey;
+pub use emergency::EmergencyKey;
+pub use errors::{GameError, ModuleError};
+#[cfg(feature = "economy")]
+pub use escrow::{EconomyConfig, EscrowKey, RakeConfig};
+pub use flavors::FlavorKey;
+pub use game_result::{GameResultClient, IGameResult};
+#[cfg(feature = "fog-of-war")]
+pub use fog::FogKey;
+pub use ghost::{GhostKey, GhostMessage};
+pub use incidents::{Incident, IncidentKey, IncidentReason};
+#[cfg(feature = "economy")]
+pub use insurance::InsuranceKey;
+pub use kill_window::{KillWindowKey, MoveStamp};
+pub use league::LeagueKey;
+pub use lobby::{LobbyKey, QueuedJoin};
+pub use maps::{Console, MapBondConfig, MapConfig, MapKey, MapRef, MapSubmission, Point, Room};
+pub use match_pause::MatchPauseKey;
+pub use metering::{MeterKey, PlayerCost};
+pub use migrations::{GameConfigV1, GameConfigV2, GameConfigV3, GameStateV1, IncidentV1, PlayerV1};
+pub use multicall::PlayerAction;
+pub use names::NameKey;
+pub use ops::OperatorMetrics;
+pub use personhood::{PersonhoodGate, PersonhoodKey};
+pub use preimage::PreimageKey;
+pub use profile::ProfileKey;
+pub use referrals::{ReferralKey, ReferralRewardConfig};
+pub use retention::RetentionKey;
+#[cfg(feature = "economy")]
+pub use rewards::{RewardKey, TaskRewardConfig};
+pub use sabotage::{Repair, SabotageKey};
+pub use scenarios::{Scenario, ScenarioKey};
+pub use settlement::{PendingResult, SettlementConfig, SettlementKey};
+pub use shields::ShieldKey;
+pub use speakers::SpeakerKey;
+pub use status::{MatchPhase, MatchStatus, MatchWinner};
+pub use sync::{StateDelta, SyncKey};
+pub use tally::TallyKey;
+pub use tasks::{TaskKey, TaskStart};
+pub use timeline::{Receipt, TimelineEntry, TimelineKey};
+pub use timers::PlayerTimers;
+pub use vault::VaultKey;
+pub use verifier_stats::{VerifierStatsKey, VerifierTally};
+pub use win_conditions::{GameMode, WinCondition, WinKey};
+
+use constants::{
+    fail, CIRCUIT_DIRECT, CIRCUIT_IDS, CIRCUIT_KILL, CIRCUIT_ROLE, CIRCUIT_TASK, CIRCUIT_VOTE,
+    COSMETIC_NONE, DEFAULT_MAX_PROOF_LEN, PHASE_ENDED, PHASE_LOBBY, PHASE_MEETING, PHASE_PLAYING,
+    SKIP_VOTE_TARGET, WINNER_CREW, WINNER_IMPOSTOR, WINNER_NONE,
 };
 
 #[contract]
 pub struct AmongUsContract;
 
-#[contracttype]
+#[contracttype(export = false)]
 #[derive(Clone, Eq, PartialEq)]
 pub enum DataKey {
     Admin,
@@ -17,6 +145,24 @@ pub enum DataKey {
     GameState,
     Players,
     UsedNullifier(BytesN<32>),
+    SpentRole(BytesN<32>),
+    Quota(Address),
+    CheckpointSeq,
+    Checkpoint(u32),
+    Slashed(Address),
+    Paused,
+    VerifierDisabled,
+    // Pre-v4 incident log; `migrate` moves it into `IncidentKey` entries.
+    Incidents,
+    MatchReport,
+    StorageVersion,
+    VerifierLock,
+    ImpostorsAlive,
+    CrewAlive,
+    LastKillAt(Address),
+    MaxProofLen,
+    // Set when a casual match starts without a verifier; its proofs are not checked.
+    Casual,
 }
 
 #[contracttype]
@@ -31,6 +177,10 @@ pub struct Player {
     pub voted_for_hash: BytesN<32>,
     pub color: Symbol,
     pub name: Symbol,
+    pub hat: Symbol,
+    pub skin: Symbol,
+    pub joined_at: u64,
+    pub last_action_at: u64,
 }
 
 #[contracttype]
@@ -38,6 +188,49 @@ pub struct Player {
 pub struct GameConfig {
     pub max_players: u32,
     pub tasks_to_win: u32,
+    pub max_tasks_per_round: u32,
+    pub max_kills_per_round: u32,
+    // Largest killer-to-victim distance in map units; 0 leaves kills unbounded.
+    pub kill_range: u32,
+    // Seconds of match time a killer must wait between kills; 0 turns the cooldown off.
+    pub kill_cooldown_secs: u64,
+    // Seconds of match time after a meeting opens before any player may finalize it; 0
+    // keeps it to operators.
+    pub voting_deadline_secs: u64,
+    // Dead players may keep proving tasks toward `tasks_to_win`.
+    pub ghost_tasks_enabled: bool,
+}
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub struct QuotaUsage {
+    pub round: u32,
+    pub tasks: u32,
+    pub kills: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+pub struct NearbyPlayer {
+    pub player_hash: BytesN<32>,
+    pub x: u32,
+    pub y: u32,
+    pub alive: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+pub struct PositionUpdate {
+    pub player: Address,
+    pub x: u32,
+    pub y: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+pub struct PositionCheckpoint {
+    pub seq: u32,
+    pub updates: Vec<PositionUpdate>,
 }
 
 #[contracttype]
@@ -49,20 +242,35 @@ pub struct GameState {
     pub impostor_count: u32,
     pub sabotage_active: bool,
     pub winner: Symbol,
+    pub map_hash: BytesN<32>,
+    pub button_used_round: u32,
+    pub meeting_started_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+pub struct MatchReport {
+    pub winner: Symbol,
+    pub rounds: u32,
+    pub players: u32,
+    pub tasks_done: u32,
+    pub map_hash: BytesN<32>,
+    pub kill_flavors: Vec<u32>,
+    pub repairs: Vec<Repair>,
 }
 
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 pub struct VoteInput {
     pub target_hash: BytesN<32>,
-    pub proof_hash: BytesN<32>,
+    pub proof: Bytes,
     pub nullifier: BytesN<32>,
 }
 
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 pub struct ProofInput {
-    pub proof_hash: BytesN<32>,
+    pub proof: Bytes,
     pub nullifier: BytesN<32>,
     pub public_inputs: Vec<BytesN<32>>,
 }
@@ -70,415 +278,1023 @@ pub struct ProofInput {
 impl AmongUsContract {
     fn ensure_not_ended(env: &Env) {
         let state = Self::read_state(env);
-        if state.phase == symbol_short!("ended") {
-            panic!("game already ended");
+        if state.phase == PHASE_ENDED {
+            fail(env, GameError::GameEnded);
         }
     }
 
-    fn require_admin(env: &Env, caller: &Address) {
-        caller.require_auth();
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .unwrap_or_else(|| panic!("admin not set"));
-        if admin != *caller {
-            panic!("not admin");
+    // Match settings freeze at start; those with `empty` set also freeze once anyone joins.
+    fn ensure_lobby(env: &Env, empty: bool) {
+        if Self::read_state(env).phase != PHASE_LOBBY {
+            fail(env, GameError::WrongPhase);
+        }
+        if empty && !Self::read_players(env).is_empty() {
+            fail(env, ModuleError::LobbyNotEmpty);
         }
     }
 
-    fn read_players(env: &Env) -> Map<Address, Player> {
+    fn ensure_not_paused(env: &Env) {
+        if Self::is_paused(env) {
+            fail(env, GameError::GamePaused);
+        }
+        if Self::is_match_paused(env) {
+            fail(env, GameError::MatchPaused);
+        }
+    }
+
+    fn is_paused(env: &Env) -> bool {
         env.storage()
             .instance()
-            .get(&DataKey::Players)
-            .unwrap_or(Map::new(env))
+            .get(&DataKey::Paused)
+            .unwrap_or(false)
     }
 
-    fn write_players(env: &Env, players: &Map<Address, Player>) {
-        env.storage().instance().set(&DataKey::Players, players);
+    fn is_verifier_disabled(env: &Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::VerifierDisabled)
+            .unwrap_or(false)
     }
 
-    fn read_config(env: &Env) -> GameConfig {
-        env.storage().instance().get(&DataKey::Config).unwrap_or(GameConfig {
-            max_players: 15,
-            tasks_to_win: 40,
-        })
+    fn require_admin(env: &Env, caller: &Address) {
+        caller.require_auth();
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| fail(env, GameError::NotAdmin));
+        if admin != *caller {
+            fail(env, GameError::NotAdmin);
+        }
     }
 
-    fn write_config(env: &Env, config: &GameConfig) {
-        env.storage().instance().set(&DataKey::Config, config);
+    // `own_role` is the submitting seat's commitment, which its own proofs may still open.
+    fn ensure_roles_not_spent(
+        env: &Env,
+        public_inputs: &Vec<BytesN<32>>,
+        own_role: Option<&BytesN<32>>,
+    ) {
+        for input in public_inputs.iter() {
+            if Some(&input) != own_role && env.storage().instance().has(&DataKey::SpentRole(input))
+            {
+                fail(env, GameError::RoleSpent);
+            }
+        }
     }
 
-    fn read_state(env: &Env) -> GameState {
-        env.storage().instance().get(&DataKey::GameState).unwrap_or(GameState {
-            phase: symbol_short!("lobby"),
-            round: 0,
-            meeting_active: false,
-            impostor_count: 1,
-            sabotage_active: false,
-            winner: symbol_short!("none"),
-        })
+    // One match per deployment: the contract address identifies the game, and the
+    // nullifier keys below already live in this contract's own storage.
+    pub(crate) fn game_scope(env: &Env) -> BytesN<32> {
+        env.crypto()
+            .sha256(&env.current_contract_address().to_xdr(env))
     }
 
-    fn write_state(env: &Env, state: &GameState) {
-        env.storage().instance().set(&DataKey::GameState, state);
+    // Nullifiers stay spent until `gc` collects the finished match, so finding one here
+    // means a check-then-verify window was re-entered.
+    fn consume_nullifier(env: &Env, nullifier: BytesN<32>) {
+        Self::note_nullifier(env, &nullifier);
+        let key = DataKey::UsedNullifier(nullifier);
+        debug_assert!(
+            !env.storage().instance().has(&key),
+            "nullifier consumed twice"
+        );
+        env.storage().instance().set(&key, &true);
     }
 
-    fn count_alive(players: &Map<Address, Player>) -> u32 {
-        let mut alive = 0u32;
-        for (_, p) in players.iter() {
-            if p.alive {
-                alive += 1;
-            }
+    // `late` asks for a seat dropped from the running match instead of a lobby slot.
+    fn ensure_can_join(
+        env: &Env,
+        player: &Address,
+        player_hash: &BytesN<32>,
+        late: bool,
+    ) -> Result<(), GameError> {
+        let open = if late {
+            Self::late_join_window(env) && env.storage().instance().has(&LobbyKey::OpenSeat)
+        } else {
+            Self::read_state(env).phase == PHASE_LOBBY
+        };
+        if !open {
+            return Err(GameError::WrongPhase);
+        }
+        let players = Self::read_players(env);
+        if players.get(player.clone()).is_some() {
+            return Err(GameError::AlreadyJoined);
+        }
+        Self::ensure_allowlisted(env, player);
+        Self::ensure_hash_opened(env, player, player_hash);
+        if player_hash.to_array() == SKIP_VOTE_TARGET {
+            // The skip sentinel counts as a hash already taken.
+            return Err(GameError::DuplicatePlayerHash);
         }
-        alive
-    }
-
-    fn total_tasks(players: &Map<Address, Player>) -> u32 {
-        let mut total = 0u32;
         for (_, p) in players.iter() {
-            total += p.tasks_done;
+            if p.player_hash == *player_hash {
+                return Err(GameError::DuplicatePlayerHash);
+            }
         }
-        total
+        Self::ensure_not_queued(env, player, player_hash)
     }
 
-    fn set_winner(env: &Env, winner: Symbol) {
-        let mut state = Self::read_state(env);
-        state.winner = winner;
-        state.phase = symbol_short!("ended");
-        state.meeting_active = false;
-        Self::write_state(env, &state);
-    }
-}
-
-#[contractimpl]
-impl AmongUsContract {
-    pub fn init(env: Env, admin: Address, impostor_count: u32) {
-        if env.storage().instance().has(&DataKey::GameState) {
-            panic!("already initialized");
-        }
-        admin.require_auth();
-
+    // Kept apart from `init` so a `__constructor` can call it once the SDK supports one.
+    fn initialize(env: &Env, admin: &Address, impostor_count: u32) {
         let state = GameState {
-            phase: symbol_short!("lobby"),
+            phase: PHASE_LOBBY,
             round: 0,
             meeting_active: false,
             impostor_count,
             sabotage_active: false,
-            winner: symbol_short!("none"),
+            winner: WINNER_NONE,
+            map_hash: BytesN::from_array(env, &[0; 32]),
+            button_used_round: 0,
+            meeting_started_at: 0,
         };
 
-        Self::write_state(&env, &state);
-        Self::write_config(
-            &env,
-            &GameConfig {
-                max_players: 15,
-                tasks_to_win: 40,
-            },
-        );
-        env.storage().instance().set(&DataKey::Admin, &admin);
-        Self::write_players(&env, &Map::new(&env));
+        Self::write_state(env, &state);
+        Self::write_config(env, &Self::default_config());
+        env.storage().instance().set(&DataKey::Admin, admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::StorageVersion, &migrations::STORAGE_VERSION);
+        Self::write_players(env, &Map::new(env));
     }
 
-    pub fn configure_game(env: Env, caller: Address, max_players: u32, tasks_to_win: u32) {
-        Self::require_admin(&env, &caller);
+    fn apply_game_config(env: &Env, max_players: u32, tasks_to_win: u32) {
         if max_players < 4 {
-            panic!("max_players must be >= 4");
+            fail(env, GameError::InvalidConfig);
         }
         if tasks_to_win == 0 {
-            panic!("tasks_to_win must be > 0");
+            fail(env, GameError::InvalidConfig);
         }
-        Self::write_config(
-            &env,
-            &GameConfig {
-                max_players,
-                tasks_to_win,
-            },
-        );
+        let mut config = Self::read_config(env);
+        config.max_players = max_players;
+        config.tasks_to_win = tasks_to_win;
+        Self::write_config(env, &config);
     }
 
-    pub fn set_verifier(env: Env, caller: Address, verifier: Address) {
-        Self::require_admin(&env, &caller);
-        env.storage().instance().set(&DataKey::Verifier, &verifier);
+    fn apply_quotas(env: &Env, max_tasks_per_round: u32, max_kills_per_round: u32) {
+        if max_tasks_per_round == 0 {
+            fail(env, GameError::InvalidConfig);
+        }
+        if max_kills_per_round == 0 {
+            fail(env, GameError::InvalidConfig);
+        }
+        let mut config = Self::read_config(env);
+        config.max_tasks_per_round = max_tasks_per_round;
+        config.max_kills_per_round = max_kills_per_round;
+        Self::write_config(env, &config);
     }
 
-    pub fn set_phase(env: Env, caller: Address, phase: Symbol) {
-        Self::require_admin(&env, &caller);
-        let mut state = Self::read_state(&env);
-        state.phase = phase;
-        Self::write_state(&env, &state);
+    fn apply_kill_range(env: &Env, kill_range: u32) {
+        let mut config = Self::read_config(env);
+        config.kill_range = kill_range;
+        Self::write_config(env, &config);
     }
 
-    pub fn start_game(env: Env, caller: Address) {
-        Self::require_admin(&env, &caller);
-        let mut state = Self::read_state(&env);
-        if state.phase != symbol_short!("lobby") {
-            panic!("game already started");
-        }
-        let players = Self::read_players(&env);
-        if players.len() < 4 {
-            panic!("need at least 4 players");
-        }
-        state.phase = symbol_short!("playing");
-        state.round = 1;
-        state.meeting_active = false;
-        state.winner = symbol_short!("none");
-        Self::write_state(&env, &state);
-        env.events().publish((symbol_short!("started"), caller), state.round);
+    fn apply_kill_cooldown(env: &Env, kill_cooldown_secs: u64) {
+        let mut config = Self::read_config(env);
+        config.kill_cooldown_secs = kill_cooldown_secs;
+        Self::write_config(env, &config);
     }
 
-    pub fn join_game(
-        env: Env,
-        player: Address,
-        color: Symbol,
-        name: Symbol,
-        player_hash: BytesN<32>,
-        role_hash: BytesN<32>,
-    ) {
-        player.require_auth();
-        Self::ensure_not_ended(&env);
+    fn apply_voting_deadline(env: &Env, voting_deadline_secs: u64) {
+        let mut config = Self::read_config(env);
+        config.voting_deadline_secs = voting_deadline_secs;
+        Self::write_config(env, &config);
+    }
 
-        let state = Self::read_state(&env);
-        if state.phase != symbol_short!("lobby") {
-            panic!("joining only allowed in lobby");
-        }
+    fn apply_ghost_tasks(env: &Env, enabled: bool) {
+        let mut config = Self::read_config(env);
+        config.ghost_tasks_enabled = enabled;
+        Self::write_config(env, &config);
+    }
 
-        let config = Self::read_config(&env);
-        let mut players = Self::read_players(&env);
-        if players.len() >= config.max_players {
-            panic!("lobby is full");
-        }
-        if players.get(player.clone()).is_some() {
-            panic!("player already joined");
+    // Match seconds left before `killer` may kill again; 0 once the cooldown has run out.
+    // A paused match holds the wait, as it does the sabotage cooldown.
+    pub(crate) fn kill_cooldown_left(env: &Env, config: &GameConfig, killer: &Address) -> u64 {
+        let last: Option<u64> = env
+            .storage()
+            .instance()
+            .get(&DataKey::LastKillAt(killer.clone()));
+        match last {
+            Some(last) => clock::deadline(last, config.kill_cooldown_secs)
+                .saturating_sub(Self::match_time(env)),
+            None => 0,
         }
+    }
 
-        for (_, p) in players.iter() {
-            if p.player_hash == player_hash {
-                panic!("duplicate player hash");
-            }
+    fn within_kill_range(config: &GameConfig, killer: &Player, x: u32, y: u32) -> bool {
+        if config.kill_range == 0 {
+            return true;
         }
-
-        let entry = Player {
-            x: 0,
-            y: 0,
-            alive: true,
-            tasks_done: 0,
-            player_hash,
-            role_hash,
-            voted_for_hash: BytesN::from_array(&env, &[0; 32]),
-            color,
-            name,
-        };
-
-        players.set(player.clone(), entry);
-        Self::write_players(&env, &players);
-        env.events().publish((symbol_short!("joined"), player), ());
+        let dx = killer.x.abs_diff(x) as u64;
+        let dy = killer.y.abs_diff(y) as u64;
+        dx * dx + dy * dy <= config.kill_range as u64 * config.kill_range as u64
     }
 
-    pub fn submit_move(env: Env, player: Address, x: u32, y: u32) {
-        player.require_auth();
-        Self::ensure_not_ended(&env);
-        let state = Self::read_state(&env);
-        if state.phase != symbol_short!("playing") {
-            panic!("movement not allowed in current phase");
+    fn apply_move(env: &Env, player: Address, x: u32, y: u32) -> Result<Receipt, GameError> {
+        Self::ensure_not_ended(env);
+        Self::ensure_not_paused(env);
+        let state = Self::read_state(env);
+        if state.phase != PHASE_PLAYING {
+            return Err(GameError::WrongPhase);
         }
 
-        let mut players = Self::read_players(&env);
-        let mut entry = players.get(player.clone()).unwrap_or_else(|| panic!("player not found"));
+        let mut players = Self::read_players(env);
+        let mut entry = players.get(player.clone()).ok_or(GameError::PlayerNotFound)?;
         if !entry.alive {
-            panic!("dead player cannot move");
+            return Err(GameError::PlayerDead);
         }
+        #[cfg(feature = "fog-of-war")]
+        Self::ensure_public_positions(env);
+        Self::ensure_on_map(env, x, y);
+        Self::note_move(env, &player, &entry);
 
         entry.x = x;
         entry.y = y;
+        entry.last_action_at = Self::ledger_now(env);
 
+        let player_hash = entry.player_hash.clone();
         players.set(player.clone(), entry);
-        Self::write_players(&env, &players);
+        Self::write_players(env, &players);
+        Self::meter(env, &player, None);
+        let receipt = Self::record_action(env, symbol_short!("move"), player_hash);
         env.events().publish((symbol_short!("moved"), player), (x, y));
+        Ok(receipt)
     }
 
-    pub fn start_meeting(env: Env, caller: Address) {
-        caller.require_auth();
-        Self::ensure_not_ended(&env);
+    fn apply_task_proof(
+        env: &Env,
+        player: Address,
+        proof: ProofInput,
+    ) -> Result<Receipt, GameError> {
+        Self::ensure_not_ended(env);
+        Self::ensure_not_paused(env);
+        let state = Self::read_state(env);
+        if state.phase != PHASE_PLAYING {
+            return Err(GameError::WrongPhase);
+        }
 
-        let players_for_caller = Self::read_players(&env);
-        let caller_entry = players_for_caller
-            .get(caller.clone())
-            .unwrap_or_else(|| panic!("caller not found"));
-        if !caller_entry.alive {
-            panic!("dead player cannot start meeting");
+        if env
+            .storage()
+            .instance()
+            .has(&DataKey::UsedNullifier(proof.nullifier.clone()))
+        {
+            return Err(GameError::NullifierUsed);
         }
 
-        let mut state = Self::read_state(&env);
-        if state.phase != symbol_short!("playing") {
-            panic!("meeting can only be started while playing");
+        let mut players = Self::read_players(env);
+        let mut entry = players.get(player.clone()).ok_or(GameError::PlayerNotFound)?;
+        let cfg = Self::read_config(env);
+        if !entry.alive && !cfg.ghost_tasks_enabled {
+            return Err(GameError::PlayerDead);
         }
-        state.phase = symbol_short!("meeting");
-        state.meeting_active = true;
-        state.round += 1;
 
-        let mut players = Self::read_players(&env);
-        for (addr, mut p) in players.clone().iter() {
-            if p.alive {
-                p.voted_for_hash = BytesN::from_array(&env, &[0; 32]);
-                players.set(addr, p);
-            }
+        let mut usage = Self::read_quota(env, &player, state.round);
+        if usage.tasks >= cfg.max_tasks_per_round {
+            return Err(GameError::QuotaReached);
         }
-        Self::write_players(&env, &players);
+        if state.sabotage_active && Self::active_sabotage(env) == Some(sabotage::SABOTAGE_COMMS) {
+            return Err(GameError::SabotageActive);
+        }
+        Self::finish_task(env, &player);
+        let role_hash = entry.role_hash.clone();
 
-        Self::write_state(&env, &state);
-        env.events().publish((symbol_short!("meeting"), caller), state.round);
+        // The seat's role commitment leads the inputs, so only a proof that opens it as
+        // crew verifies and an impostor cannot fill the task bar.
+        let mut public_inputs = vec![env, entry.role_hash.clone()];
+        public_inputs.append(&proof.public_inputs);
+        public_inputs.push_back(proof.nullifier.clone());
+        // An ejected crewmate's commitment is spent, yet ghost crew keep proving tasks.
+        Self::ensure_roles_not_spent(env, &public_inputs, Some(&role_hash));
+
+        entry.tasks_done += 1;
+        entry.last_action_at = Self::ledger_now(env);
+        let player_hash = entry.player_hash.clone();
+        players.set(player.clone(), entry);
+        Self::write_players(env, &players);
+        Self::ensure_derived_nullifier(
+            env,
+            CIRCUIT_TASK,
+            Some(role_hash),
+            usage.tasks,
+            &proof.nullifier,
+        );
+        usage.tasks += 1;
+        Self::write_quota(env, &player, &usage);
+        Self::consume_nullifier(env, proof.nullifier);
+        Self::meter(env, &player, Some(&proof.proof));
+        if !Self::verify_circuit(env, CIRCUIT_TASK, proof.proof, public_inputs) {
+            return Err(GameError::InvalidProof);
+        }
+        let receipt = Self::record_action(env, symbol_short!("task"), player_hash);
+        Self::settle_win(env, player.to_val());
+        Ok(receipt)
     }
 
-    pub fn end_meeting(env: Env, caller: Address) {
-        Self::require_admin(&env, &caller);
-        let mut state = Self::read_state(&env);
-        if state.phase != symbol_short!("meeting") {
-            panic!("meeting not active");
+    fn apply_start_meeting(env: &Env, caller: Address) -> Result<Receipt, GameError> {
+        Self::ensure_not_ended(env);
+        Self::ensure_not_paused(env);
+
+        let players_for_caller = Self::read_players(env);
+        let caller_entry = players_for_caller
+            .get(caller.clone())
+            .ok_or(GameError::PlayerNotFound)?;
+        if !caller_entry.alive {
+            return Err(GameError::PlayerDead);
         }
-        state.phase = symbol_short!("playing");
-        state.meeting_active = false;
-        Self::write_state(&env, &state);
-        env.events().publish((symbol_short!("resume"), caller), state.round);
+
+        let mut state = Self::read_state(env);
+        if state.phase != PHASE_PLAYING {
+            return Err(GameError::WrongPhase);
+        }
+        if state.button_used_round == state.round {
+            return Err(GameError::ButtonUsed);
+        }
+        Self::ensure_meeting_allowed(env, Some(&caller))?;
+        Self::ensure_at_emergency_button(env, caller_entry.x, caller_entry.y);
+        Self::open_meeting(env, &mut state, true);
+        Self::note_emergency_meeting(env, &caller);
+        Self::meter(env, &caller, None);
+        let receipt = Self::record_action(env, symbol_short!("meeting"), caller_entry.player_hash);
+        env.events().publish((symbol_short!("meeting"), caller), state.round);
+        Ok(receipt)
     }
 
-    pub fn finalize_meeting(env: Env, caller: Address, ejected_player_hash: BytesN<32>) {
-        Self::require_admin(&env, &caller);
-        let mut state = Self::read_state(&env);
-        if state.phase != symbol_short!("meeting") {
-            panic!("meeting not active");
+    // Tallies the meeting against `ejected_player_hash` and reopens play. `caller` tops the
+    // outcome events: the operator, or the last voter when the meeting closes itself.
+    fn resolve_meeting(
+        env: &Env,
+        caller: Address,
+        ejected_player_hash: BytesN<32>,
+        reveal: Option<ProofInput>,
+    ) -> Result<(), GameError> {
+        let mut state = Self::read_state(env);
+        if state.phase != PHASE_MEETING {
+            return Err(GameError::MeetingNotActive);
         }
 
-        let mut players = Self::read_players(&env);
+        let mut players = Self::read_players(env);
+        let skip = BytesN::from_array(env, &SKIP_VOTE_TARGET);
         let mut alive_voters = 0u32;
         let mut votes_for_target = 0u32;
+        let mut skip_votes = 0u32;
         for (_, p) in players.iter() {
             if p.alive {
                 alive_voters += 1;
                 if p.voted_for_hash == ejected_player_hash {
                     votes_for_target += 1;
+                } else if p.voted_for_hash == skip {
+                    skip_votes += 1;
                 }
             }
         }
+        #[cfg(feature = "anonymous-voting")]
+        {
+            votes_for_target += Self::anon_votes_for(env, state.round, &ejected_player_hash);
+            skip_votes += Self::anon_votes_for(env, state.round, &skip);
+        }
+        // Skip votes are counted like any target, and a tie with the target goes to skip.
+        let skip_won = skip_votes > 0 && skip_votes >= votes_for_target;
 
         if alive_voters == 0 {
-            panic!("no alive voters");
+            return Err(GameError::NoAliveVoters);
         }
 
-        let mut ejected = false;
-        if votes_for_target * 2 > alive_voters {
+        let mut ejected_role: Option<BytesN<32>> = None;
+        if votes_for_target * 2 > alive_voters && !skip_won {
             for (addr, mut p) in players.clone().iter() {
                 if p.alive && p.player_hash == ejected_player_hash {
                     p.alive = false;
+                    ejected_role = Some(p.role_hash.clone());
                     players.set(addr, p);
-                    ejected = true;
                     break;
                 }
             }
         }
 
-        if ejected {
+        if let Some(role_hash) = ejected_role.clone() {
+            // The ejection reveals the role, so the commitment must never back another proof.
+            env.storage()
+                .instance()
+                .set(&DataKey::SpentRole(role_hash.clone()), &true);
             env.events()
-                .publish((symbol_short!("ejected"), caller.clone()), ejected_player_hash);
+                .publish((symbol_short!("ejected"), caller.clone()), ejected_player_hash.clone());
+            env.events().publish((symbol_short!("role_spnt"), caller.clone()), role_hash);
+            Self::record_action(env, symbol_short!("eject"), ejected_player_hash.clone());
+            #[cfg(feature = "economy")]
+            Self::pay_bounties(env, &ejected_player_hash, state.round);
+        } else if skip_won {
+            env.events().publish(
+                (symbol_short!("skipped"), caller.clone()),
+                (ejected_player_hash, skip_votes),
+            );
         } else {
             env.events()
                 .publish((symbol_short!("skipped"), caller.clone()), ejected_player_hash);
         }
 
-        Self::write_players(&env, &players);
-        state.phase = symbol_short!("playing");
+        Self::write_players(env, &players);
+        state.phase = PHASE_PLAYING;
         state.meeting_active = false;
-        Self::write_state(&env, &state);
-    }
-
-    pub fn submit_vote(env: Env, voter: Address, vote: VoteInput) {
-        voter.require_auth();
-        Self::ensure_not_ended(&env);
-        let state = Self::read_state(&env);
-        if state.phase != symbol_short!("meeting") {
-            panic!("voting not allowed in current phase");
-        }
-
-        if env.storage().instance().has(&DataKey::UsedNullifier(vote.nullifier.clone())) {
-            panic!("nullifier already used");
-        }
-
-        let mut players = Self::read_players(&env);
-        let mut entry = players.get(voter.clone()).unwrap_or_else(|| panic!("player not found"));
-        if !entry.alive {
-            panic!("dead player cannot vote");
-        }
-        if entry.voted_for_hash != BytesN::from_array(&env, &[0; 32]) {
-            panic!("player already voted");
+        Self::write_state(env, &state);
+        if let Some(role_hash) = ejected_role {
+            match reveal {
+                Some(proof) => Self::reveal_ejected_impostor(env, role_hash, state.round, proof)?,
+                None => Self::hold_ejection(env, role_hash, state.round),
+            }
+            Self::settle_win(env, caller.to_val());
+        } else if reveal.is_some() {
+            fail(env, ModuleError::NothingToReveal);
         }
+        Self::note_meeting_closed(env);
+        Self::reconcile_after_meeting(env, state.round);
+        Ok(())
+    }
 
-        if !Self::verify_zk_proof(
-            env.clone(),
-            vote.proof_hash,
-            vec![&env, vote.nullifier.clone()],
-        ) {
-            panic!("invalid vote proof");
+    fn enter_lobby(
+        env: &Env,
+        player: &Address,
+        color: Symbol,
+        name: Symbol,
+        player_hash: BytesN<32>,
+        role_hash: Option<BytesN<32>>,
+    ) -> Result<(), GameError> {
+        Self::ensure_not_ended(env);
+        Self::ensure_not_paused(env);
+
+        Self::ensure_can_join(env, player, &player_hash, role_hash.is_none())?;
+        if Self::lobby_full(env, player) {
+            return Err(GameError::LobbyFull);
         }
+        Self::ensure_name_allowed(env, player, &name);
+        let color = Self::assign_color(env, player, color, Self::auto_color(env));
+        Self::seat_human(env, player);
+        Self::claim_identity(env, player, &player_hash);
+
+        #[cfg(feature = "economy")]
+        Self::collect_stake(env, player);
+        Self::fill_seat(env, player, color, name, player_hash, role_hash);
+        Self::meter(env, player, None);
+        Ok(())
+    }
 
-        entry.voted_for_hash = vote.target_hash.clone();
+    fn insert_player(
+        env: &Env,
+        player: &Address,
+        color: Symbol,
+        name: Symbol,
+        player_hash: BytesN<32>,
+        role_hash: BytesN<32>,
+    ) {
+        let mut players = Self::read_players(env);
+        let spawn = Self::spawn_point(env, players.len());
+        let entry = Player {
+            x: spawn.x,
+            y: spawn.y,
+            alive: true,
+            tasks_done: 0,
+            player_hash,
+            role_hash,
+            voted_for_hash: BytesN::from_array(env, &[0; 32]),
+            color,
+            name,
+            hat: COSMETIC_NONE,
+            skin: COSMETIC_NONE,
+            joined_at: Self::ledger_now(env),
+            last_action_at: Self::ledger_now(env),
+        };
+        players.set(player.clone(), entry);
+        Self::write_players(env, &players);
+        Self::note_call(env, &symbol_short!("join"));
+        env.events().publish((symbol_short!("joined"), player.clone()), ());
+    }
+
+    fn read_players(env: &Env) -> Map<Address, Player> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Players)
+            .unwrap_or(Map::new(env))
+    }
+
+    fn write_players(env: &Env, players: &Map<Address, Player>) {
+        env.storage().instance().set(&DataKey::Players, players);
+        Self::note_players_changed(env);
+    }
+
+    fn default_config() -> GameConfig {
+        GameConfig {
+            max_players: 15,
+            tasks_to_win: 40,
+            max_tasks_per_round: 10,
+            max_kills_per_round: 3,
+            kill_range: 0,
+            kill_cooldown_secs: 0,
+            voting_deadline_secs: 0,
+            ghost_tasks_enabled: false,
+        }
+    }
+
+    fn read_config(env: &Env) -> GameConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config)
+            .unwrap_or(Self::default_config())
+    }
+
+    fn write_config(env: &Env, config: &GameConfig) {
+        env.storage().instance().set(&DataKey::Config, config);
+    }
+
+    fn read_quota(env: &Env, player: &Address, round: u32) -> QuotaUsage {
+        let usage: Option<QuotaUsage> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Quota(player.clone()));
+        match usage {
+            Some(usage) if usage.round == round => usage,
+            _ => QuotaUsage {
+                round,
+                tasks: 0,
+                kills: 0,
+            },
+        }
+    }
+
+    fn write_quota(env: &Env, player: &Address, usage: &QuotaUsage) {
+        env.storage()
+            .instance()
+            .set(&DataKey::Quota(player.clone()), usage);
+    }
+
+    fn read_state(env: &Env) -> GameState {
+        env.storage().instance().get(&DataKey::GameState).unwrap_or(GameState {
+            phase: PHASE_LOBBY,
+            round: 0,
+            meeting_active: false,
+            impostor_count: 1,
+            sabotage_active: false,
+            winner: WINNER_NONE,
+            map_hash: BytesN::from_array(env, &[0; 32]),
+            button_used_round: 0,
+            meeting_started_at: 0,
+        })
+    }
+
+    fn write_state(env: &Env, state: &GameState) {
+        env.storage().instance().set(&DataKey::GameState, state);
+        Self::note_state_changed(env, state.round);
+    }
+
+    fn count_alive(players: &Map<Address, Player>) -> u32 {
+        let mut alive = 0u32;
+        for (_, p) in players.iter() {
+            if p.alive {
+                alive += 1;
+            }
+        }
+        alive
+    }
+
+    fn total_tasks(players: &Map<Address, Player>) -> u32 {
+        let mut total = 0u32;
+        for (_, p) in players.iter() {
+            total += p.tasks_done;
+        }
+        total
+    }
+
+    fn field_input(env: &Env, value: u32) -> BytesN<32> {
+        let mut bytes = [0u8; 32];
+        bytes[28..].copy_from_slice(&value.to_be_bytes());
+        BytesN::from_array(env, &bytes)
+    }
+
+    // Only the emergency button locks itself for the new round; a body report leaves it free.
+    fn open_meeting(env: &Env, state: &mut GameState, by_button: bool) {
+        state.phase = PHASE_MEETING;
+        state.meeting_active = true;
+        state.round += 1;
+        if by_button {
+            state.button_used_round = state.round;
+        }
+        state.meeting_started_at = Self::match_time(env);
+
+        let mut players = Self::read_players(env);
+        for (addr, mut p) in players.clone().iter() {
+            if p.alive {
+                p.voted_for_hash = BytesN::from_array(env, &[0; 32]);
+                players.set(addr, p);
+            }
+        }
+        Self::write_players(env, &players);
+        Self::write_state(env, state);
+        Self::clear_bodies(env);
+        Self::note_meeting_opened(env);
+        Self::note_speaker_order(env, state.round);
+    }
+
+    fn cast_vote(env: &Env, voter: &Address, vote: VoteInput) -> Result<Receipt, GameError> {
+        Self::ensure_not_ended(env);
+        Self::ensure_not_paused(env);
+        let state = Self::read_state(env);
+        if state.phase != PHASE_MEETING {
+            return Err(GameError::WrongPhase);
+        }
+
+        #[cfg(feature = "anonymous-voting")]
+        if Self::anonymous_voting(env) {
+            return Err(GameError::VotesAnonymous);
+        }
+
+        if env.storage().instance().has(&DataKey::UsedNullifier(vote.nullifier.clone())) {
+            return Err(GameError::NullifierUsed);
+        }
+
+        let mut players = Self::read_players(env);
+        let mut entry = players.get(voter.clone()).ok_or(GameError::PlayerNotFound)?;
+        if !entry.alive {
+            return Err(GameError::PlayerDead);
+        }
+        if entry.voted_for_hash != BytesN::from_array(env, &[0; 32]) {
+            return Err(GameError::AlreadyVoted);
+        }
+
+        let role_hash = entry.role_hash.clone();
+        entry.voted_for_hash = vote.target_hash.clone();
+        entry.last_action_at = Self::ledger_now(env);
+        let voter_hash = entry.player_hash.clone();
         players.set(voter.clone(), entry);
-        Self::write_players(&env, &players);
+        Self::write_players(env, &players);
+        Self::ensure_derived_nullifier(
+            env,
+            CIRCUIT_VOTE,
+            Some(role_hash),
+            0,
+            &vote.nullifier,
+        );
+        Self::consume_nullifier(env, vote.nullifier.clone());
+        Self::meter(env, voter, Some(&vote.proof));
+        if !Self::verify_circuit(env, CIRCUIT_VOTE, vote.proof, vec![env, vote.nullifier]) {
+            return Err(GameError::InvalidProof);
+        }
+
+        #[cfg(feature = "economy")]
+        Self::note_bounty_vote(env, &vote.target_hash, voter, state.round);
+        let receipt = Self::record_action(env, symbol_short!("vote"), voter_hash);
+        env.events().publish((symbol_short!("voted"), voter.clone()), vote.target_hash.clone());
+        Self::note_vote_cast(env, voter, state.round, &vote.target_hash, &players)?;
+        Ok(receipt)
+    }
+
+    fn set_winner(env: &Env, winner: Symbol) {
+        let mut state = Self::read_state(env);
+        state.winner = winner;
+        state.phase = PHASE_ENDED;
+        state.meeting_active = false;
+        Self::write_state(env, &state);
+        Self::void_pending_result(env);
+        Self::note_match_ended(env);
+        Self::release_all_identities(env);
+
+        let players = Self::read_players(env);
+        let report = MatchReport {
+            winner: state.winner,
+            rounds: state.round,
+            players: players.len(),
+            tasks_done: Self::total_tasks(&players),
+            map_hash: state.map_hash,
+            kill_flavors: Self::match_flavors(env),
+            repairs: Self::match_repairs(env),
+        };
+        env.storage().instance().set(&DataKey::MatchReport, &report);
+        Self::notify_factory(env, "record_game_end", players.keys().into_val(env));
+        Self::report_profile_results(env, &players, &report.winner);
+        Self::report_analytics(env, &report);
+        env.events().publish((symbol_short!("report"),), report);
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    // Safe to retry: repeating the original arguments is a no-op, anything else is rejected.
+    pub fn init(env: Env, admin: Address, impostor_count: u32) -> Result<(), GameError> {
+        admin.require_auth();
+        if env.storage().instance().has(&DataKey::GameState) {
+            let stored: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+            if stored == Some(admin) && Self::read_state(&env).impostor_count == impostor_count {
+                return Ok(());
+            }
+            return Err(GameError::AlreadyInitialized);
+        }
+        Self::initialize(&env, &admin, impostor_count);
+        Ok(())
+    }
+
+    pub fn configure_game(env: Env, caller: Address, max_players: u32, tasks_to_win: u32) {
+        Self::require_operator(&env, &caller);
+        Self::apply_game_config(&env, max_players, tasks_to_win);
+    }
+
+    pub fn configure_quotas(
+        env: Env,
+        caller: Address,
+        max_tasks_per_round: u32,
+        max_kills_per_round: u32,
+    ) {
+        Self::require_operator(&env, &caller);
+        Self::apply_quotas(&env, max_tasks_per_round, max_kills_per_round);
+    }
+
+    pub fn configure_kill_range(env: Env, caller: Address, kill_range: u32) {
+        Self::require_operator(&env, &caller);
+        Self::apply_kill_range(&env, kill_range);
+    }
+
+    pub fn configure_kill_cooldown(env: Env, caller: Address, kill_cooldown_secs: u64) {
+        Self::require_operator(&env, &caller);
+        Self::apply_kill_cooldown(&env, kill_cooldown_secs);
+    }
+
+    pub fn configure_voting_deadline(env: Env, caller: Address, voting_deadline_secs: u64) {
+        Self::require_operator(&env, &caller);
+        Self::apply_voting_deadline(&env, voting_deadline_secs);
+    }
+
+    pub fn configure_ghost_tasks(env: Env, caller: Address, enabled: bool) {
+        Self::require_operator(&env, &caller);
+        Self::apply_ghost_tasks(&env, enabled);
+    }
+
+    pub fn set_verifier(env: Env, caller: Address, verifier: Address) {
+        Self::require_admin(&env, &caller);
+        env.storage().instance().set(&DataKey::Verifier, &verifier);
+        // Proofs from here on are checked for real, even mid-match.
+        env.storage().instance().remove(&DataKey::Casual);
+    }
+
+    // Proofs are stored nowhere, but every byte is passed to the verifier, so the cap bounds
+    // what one call can make the game forward.
+    pub fn configure_max_proof_len(env: Env, caller: Address, max_len: u32) {
+        Self::require_admin(&env, &caller);
+        if max_len == 0 {
+            fail(&env, GameError::InvalidConfig);
+        }
+        env.storage().instance().set(&DataKey::MaxProofLen, &max_len);
+    }
+
+    pub fn is_casual(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Casual)
+            .unwrap_or(false)
+    }
+
+    pub fn get_max_proof_len(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxProofLen)
+            .unwrap_or(DEFAULT_MAX_PROOF_LEN)
+    }
+
+    pub fn set_paused(env: Env, caller: Address, paused: bool, reason: IncidentReason) {
+        Self::require_operator(&env, &caller);
+        if Self::is_paused(&env) == paused {
+            return;
+        }
+        env.storage().instance().set(&DataKey::Paused, &paused);
+        if paused {
+            Self::record_incident(&env, symbol_short!("pause"), reason);
+        } else {
+            Self::record_incident(&env, symbol_short!("unpause"), reason);
+        }
+    }
+
+    pub fn set_verifier_enabled(
+        env: Env,
+        caller: Address,
+        enabled: bool,
+        reason: IncidentReason,
+    ) {
+        Self::require_admin(&env, &caller);
+        if Self::is_verifier_disabled(&env) != enabled {
+            return;
+        }
         env.storage()
             .instance()
-            .set(&DataKey::UsedNullifier(vote.nullifier), &true);
-        env.events().publish((symbol_short!("voted"), voter), vote.target_hash);
+            .set(&DataKey::VerifierDisabled, &!enabled);
+        if enabled {
+            Self::record_incident(&env, symbol_short!("vrf_on"), reason);
+        } else {
+            Self::record_incident(&env, symbol_short!("vrf_off"), reason);
+        }
     }
 
-    pub fn submit_task_proof(env: Env, player: Address, proof: ProofInput) {
+    pub fn set_phase(env: Env, caller: Address, phase: Symbol, reason: IncidentReason) {
+        Self::require_operator(&env, &caller);
+        let mut state = Self::read_state(&env);
+        state.phase = phase;
+        Self::write_state(&env, &state);
+        Self::record_incident(&env, symbol_short!("force"), reason);
+    }
+
+    pub fn start_game(env: Env, caller: Address) -> Result<(), GameError> {
+        Self::require_operator(&env, &caller);
+        let mut state = Self::read_state(&env);
+        if state.phase != PHASE_LOBBY {
+            return Err(GameError::WrongPhase);
+        }
+        let players = Self::read_players(&env);
+        if players.len() < 4 {
+            return Err(GameError::NotEnoughPlayers);
+        }
+        #[cfg(feature = "fog-of-war")]
+        Self::ensure_spawns_committed(&env);
+        Self::ensure_verifier_or_casual(&env)?;
+        state.phase = PHASE_PLAYING;
+        state.round = 1;
+        state.meeting_active = false;
+        state.winner = WINNER_NONE;
+        state.map_hash = Self::selected_map_hash(&env);
+        Self::write_state(&env, &state);
+        #[cfg(feature = "economy")]
+        Self::reserve_insurance(&env);
+        Self::seed_win_state(&env, &state, players.len());
+        env.events().publish((symbol_short!("started"), caller), state.round);
+        Ok(())
+    }
+
+    pub fn join_game(
+        env: Env,
+        player: Address,
+        color: Symbol,
+        name: Symbol,
+        player_hash: BytesN<32>,
+        role_hash: BytesN<32>,
+    ) -> Result<(), GameError> {
+        player.require_auth();
+        Self::enter_lobby(&env, &player, color, name, player_hash, Some(role_hash))
+    }
+
+    pub fn submit_move(env: Env, player: Address, x: u32, y: u32) -> Result<Receipt, GameError> {
         player.require_auth();
+        Self::apply_move(&env, player, x, y)
+    }
+
+    pub fn checkpoint_positions(
+        env: Env,
+        submitter: Address,
+        checkpoint: PositionCheckpoint,
+    ) -> Result<(), GameError> {
+        submitter.require_auth();
         Self::ensure_not_ended(&env);
+        Self::ensure_not_paused(&env);
         let state = Self::read_state(&env);
-        if state.phase != symbol_short!("playing") {
-            panic!("task submission not allowed in current phase");
+        if state.phase != PHASE_PLAYING {
+            return Err(GameError::WrongPhase);
         }
-
+        #[cfg(feature = "fog-of-war")]
+        Self::ensure_public_positions(&env);
         if env
             .storage()
             .instance()
-            .has(&DataKey::UsedNullifier(proof.nullifier.clone()))
+            .has(&DataKey::Slashed(submitter.clone()))
         {
-            panic!("task nullifier already used");
+            return Err(GameError::SubmitterSlashed);
         }
 
         let mut players = Self::read_players(&env);
-        let mut entry = players.get(player.clone()).unwrap_or_else(|| panic!("player not found"));
-        if !entry.alive {
-            panic!("dead player cannot submit tasks");
+        if !Self::is_operator(&env, &submitter) && players.get(submitter.clone()).is_none() {
+            return Err(GameError::PlayerNotFound);
         }
 
-        let mut public_inputs = proof.public_inputs.clone();
-        public_inputs.push_back(proof.nullifier.clone());
-        if !Self::verify_zk_proof(env.clone(), proof.proof_hash, public_inputs) {
-            panic!("invalid task proof");
+        let digest = env.crypto().sha256(&checkpoint.clone().to_xdr(&env));
+        let applied: Option<BytesN<32>> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Checkpoint(checkpoint.seq));
+        if let Some(applied) = applied {
+            if applied == digest {
+                return Err(GameError::InvalidCheckpoint);
+            }
+            env.storage()
+                .instance()
+                .set(&DataKey::Slashed(submitter.clone()), &true);
+            env.events()
+                .publish((symbol_short!("slashed"), submitter), checkpoint.seq);
+            return Ok(());
+        }
+
+        let last_seq: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::CheckpointSeq)
+            .unwrap_or(0);
+        if checkpoint.seq <= last_seq {
+            return Err(GameError::InvalidCheckpoint);
+        }
+        if checkpoint.updates.is_empty() {
+            return Err(GameError::InvalidCheckpoint);
+        }
+
+        let mut seen: Map<Address, bool> = Map::new(&env);
+        for update in checkpoint.updates.iter() {
+            if seen.contains_key(update.player.clone()) {
+                return Err(GameError::InvalidCheckpoint);
+            }
+            seen.set(update.player.clone(), true);
+
+            if update.player != submitter {
+                update.player.require_auth_for_args(
+                    (checkpoint.seq, update.x, update.y).into_val(&env),
+                );
+            }
+            let mut entry = players
+                .get(update.player.clone())
+                .ok_or(GameError::PlayerNotFound)?;
+            if !entry.alive {
+                return Err(GameError::PlayerDead);
+            }
+            Self::ensure_on_map(&env, update.x, update.y);
+            entry.x = update.x;
+            entry.y = update.y;
+            players.set(update.player.clone(), entry);
         }
 
-        entry.tasks_done += 1;
-        players.set(player.clone(), entry);
         Self::write_players(&env, &players);
         env.storage()
             .instance()
-            .set(&DataKey::UsedNullifier(proof.nullifier), &true);
+            .set(&DataKey::Checkpoint(checkpoint.seq), &digest);
+        env.storage()
+            .instance()
+            .set(&DataKey::CheckpointSeq, &checkpoint.seq);
+        env.events().publish(
+            (symbol_short!("chkpt"), submitter),
+            (checkpoint.seq, checkpoint.updates.len()),
+        );
+        Ok(())
+    }
 
-        let cfg = Self::read_config(&env);
-        let total_tasks = Self::total_tasks(&players);
-        if total_tasks >= cfg.tasks_to_win {
-            Self::set_winner(&env, symbol_short!("crew"));
-            env.events().publish((symbol_short!("winner"), player), symbol_short!("crew"));
+    pub fn start_meeting(env: Env, caller: Address) -> Result<Receipt, GameError> {
+        caller.require_auth();
+        Self::apply_start_meeting(&env, caller)
+    }
+
+    pub fn end_meeting(env: Env, caller: Address) -> Result<(), GameError> {
+        Self::require_operator(&env, &caller);
+        let mut state = Self::read_state(&env);
+        if state.phase != PHASE_MEETING {
+            return Err(GameError::MeetingNotActive);
+        }
+        state.phase = PHASE_PLAYING;
+        state.meeting_active = false;
+        Self::write_state(&env, &state);
+        env.events().publish((symbol_short!("resume"), caller), state.round);
+        Self::note_meeting_closed(&env);
+        Self::reconcile_after_meeting(&env, state.round);
+        Ok(())
+    }
+
+    pub fn finalize_meeting(
+        env: Env,
+        caller: Address,
+        ejected_player_hash: BytesN<32>,
+        reveal: Option<ProofInput>,
+    ) -> Result<(), GameError> {
+        caller.require_auth();
+        let mut ejected_player_hash = ejected_player_hash;
+        if !Self::is_operator(&env, &caller) {
+            ejected_player_hash = Self::overdue_tally_leader(&env, &caller);
         }
+        Self::resolve_meeting(&env, caller, ejected_player_hash, reveal)
+    }
+
+    pub fn submit_vote(env: Env, voter: Address, vote: VoteInput) -> Result<Receipt, GameError> {
+        voter.require_auth();
+        Self::cast_vote(&env, &voter, vote)
+    }
+
+    pub fn submit_task_proof(
+        env: Env,
+        player: Address,
+        proof: ProofInput,
+    ) -> Result<Receipt, GameError> {
+        player.require_auth();
+        Self::apply_task_proof(&env, player, proof)
     }
 
-    pub fn submit_kill_proof(env: Env, killer: Address, victim: Address, proof: ProofInput) {
+    pub fn submit_kill_proof(
+        env: Env,
+        killer: Address,
+        victim: Address,
+        proof: ProofInput,
+        flavor: u32,
+    ) -> Result<Receipt, GameError> {
         killer.require_auth();
         Self::ensure_not_ended(&env);
+        Self::ensure_not_paused(&env);
 
         let state = Self::read_state(&env);
-        if state.phase != symbol_short!("playing") {
-            panic!("kills not allowed in current phase");
+        if state.phase != PHASE_PLAYING {
+            return Err(GameError::WrongPhase);
         }
 
         if env
@@ -486,84 +1302,175 @@ impl AmongUsContract {
             .instance()
             .has(&DataKey::UsedNullifier(proof.nullifier.clone()))
         {
-            panic!("kill nullifier already used");
+            return Err(GameError::NullifierUsed);
         }
 
         let mut players = Self::read_players(&env);
-        let killer_entry = players
+        let mut killer_entry = players
             .get(killer.clone())
-            .unwrap_or_else(|| panic!("killer not found"));
+            .ok_or(GameError::PlayerNotFound)?;
         if !killer_entry.alive {
-            panic!("dead player cannot kill");
+            return Err(GameError::PlayerDead);
+        }
+
+        let cfg = Self::read_config(&env);
+        let mut usage = Self::read_quota(&env, &killer, state.round);
+        if usage.kills >= cfg.max_kills_per_round {
+            return Err(GameError::QuotaReached);
+        }
+        // A failed call's events are discarded with it; `get_kill_cooldown` reports the wait.
+        if Self::kill_cooldown_left(&env, &cfg, &killer) > 0 {
+            return Err(GameError::KillCooldown);
+        }
+        // The shield covers every seat at once, so it holds the kill back like a cooldown.
+        if Self::shield_expires_in(&env).unwrap_or(0) > 0 {
+            return Err(GameError::KillCooldown);
         }
 
         let mut victim_entry = players
             .get(victim.clone())
-            .unwrap_or_else(|| panic!("victim not found"));
+            .ok_or(GameError::PlayerNotFound)?;
         if !victim_entry.alive {
-            panic!("victim already dead");
+            return Err(GameError::VictimDead);
+        }
+        // Under fog the stored positions go stale; the kill proof binds the committed ones.
+        #[cfg(feature = "fog-of-war")]
+        let positions_public = !Self::fog_enabled(&env);
+        #[cfg(not(feature = "fog-of-war"))]
+        let positions_public = true;
+        if positions_public {
+            if !Self::within_kill_range(&cfg, &killer_entry, victim_entry.x, victim_entry.y) {
+                return Err(GameError::OutOfKillRange);
+            }
+            if Self::victim_moved_away(&env, &cfg, &killer_entry, &victim) {
+                return Err(GameError::VictimMoved);
+            }
         }
 
+        #[cfg(feature = "fog-of-war")]
+        Self::ensure_fog_kill_inputs(&env, &killer, &victim, &proof);
         let mut public_inputs = proof.public_inputs.clone();
         public_inputs.push_back(proof.nullifier.clone());
-        if !Self::verify_zk_proof(env.clone(), proof.proof_hash, public_inputs) {
-            panic!("invalid kill proof");
-        }
+        Self::ensure_roles_not_spent(&env, &public_inputs, None);
+        let killer_role = killer_entry.role_hash.clone();
 
         victim_entry.alive = false;
+        Self::note_body(&env, &victim);
+        let victim_hash = victim_entry.player_hash.clone();
         players.set(victim.clone(), victim_entry);
+        killer_entry.last_action_at = Self::ledger_now(&env);
+        players.set(killer.clone(), killer_entry);
         Self::write_players(&env, &players);
-        env.storage()
-            .instance()
-            .set(&DataKey::UsedNullifier(proof.nullifier), &true);
-
-        let alive = Self::count_alive(&players);
-        if alive <= state.impostor_count {
-            Self::set_winner(&env, symbol_short!("impost"));
-            env.events().publish((symbol_short!("winner"), killer.clone()), symbol_short!("impost"));
+        Self::ensure_derived_nullifier(
+            &env,
+            CIRCUIT_KILL,
+            Some(killer_role),
+            usage.kills,
+            &proof.nullifier,
+        );
+        usage.kills += 1;
+        Self::write_quota(&env, &killer, &usage);
+        env.storage().instance().set(
+            &DataKey::LastKillAt(killer.clone()),
+            &Self::match_time(&env),
+        );
+        Self::note_kill_flavor(&env, flavor);
+        Self::consume_nullifier(&env, proof.nullifier);
+        Self::meter(&env, &killer, Some(&proof.proof));
+        if !Self::verify_circuit(&env, CIRCUIT_KILL, proof.proof, public_inputs) {
+            return Err(GameError::InvalidProof);
         }
+        let receipt = Self::record_action(&env, symbol_short!("kill"), victim_hash);
+        Self::note_kill(&env, state.round);
+
+        Self::note_crew_lost(&env);
+        Self::settle_win(&env, killer.to_val());
 
-        env.events().publish((symbol_short!("killed"), killer), victim);
+        env.events()
+            .publish((symbol_short!("killed"), killer), (victim, flavor));
+        Ok(receipt)
     }
 
-    pub fn submit_impostor_win_proof(env: Env, caller: Address, proof: ProofInput) {
-        caller.require_auth();
+    pub fn submit_impostor_win_proof(env: Env, proof: ProofInput) -> Result<(), GameError> {
         Self::ensure_not_ended(&env);
+        Self::ensure_not_paused(&env);
+        Self::ensure_not_casual(&env)?;
 
         if env
             .storage()
             .instance()
             .has(&DataKey::UsedNullifier(proof.nullifier.clone()))
         {
-            panic!("impostor nullifier already used");
+            return Err(GameError::NullifierUsed);
         }
 
-        let mut public_inputs = proof.public_inputs.clone();
-        public_inputs.push_back(proof.nullifier.clone());
-        if !Self::verify_zk_proof(env.clone(), proof.proof_hash, public_inputs) {
-            panic!("invalid impostor win proof");
+        // Anonymous like `trigger_sabotage`: the proof opens one of the seated role commitments
+        // over `[role_root, round, nullifier]`, so the win names no impostor.
+        let public_inputs = vec![
+            &env,
+            Self::role_root(&env, false),
+            Self::field_input(&env, Self::read_state(&env).round),
+            proof.nullifier.clone(),
+        ];
+        Self::consume_nullifier(&env, proof.nullifier);
+        if !Self::verify_circuit(&env, CIRCUIT_ROLE, proof.proof, public_inputs) {
+            return Err(GameError::InvalidProof);
         }
 
-        env.storage()
-            .instance()
-            .set(&DataKey::UsedNullifier(proof.nullifier), &true);
-        Self::set_winner(&env, symbol_short!("impost"));
+        Self::set_winner(&env, WINNER_IMPOSTOR);
         env.events()
-            .publish((symbol_short!("winner"), caller), symbol_short!("impost"));
+            .publish((symbol_short!("winner"),), WINNER_IMPOSTOR);
+        Ok(())
     }
 
-    pub fn end_game_admin(env: Env, caller: Address, winner: Symbol) {
-        Self::require_admin(&env, &caller);
-        if winner != symbol_short!("crew") && winner != symbol_short!("impost") {
-            panic!("invalid winner symbol");
+    pub fn end_game_admin(env: Env, caller: Address, winner: Symbol) -> Result<(), GameError> {
+        Self::require_operator(&env, &caller);
+        if winner != WINNER_CREW && winner != WINNER_IMPOSTOR {
+            return Err(GameError::InvalidConfig);
         }
         Self::set_winner(&env, winner);
+        Ok(())
     }
 
     pub fn get_players(env: Env) -> Map<Address, Player> {
         Self::read_players(&env)
     }
 
+    pub fn get_nearby_players(
+        env: Env,
+        center_x: u32,
+        center_y: u32,
+        radius: u32,
+    ) -> Vec<NearbyPlayer> {
+        let radius_sq = radius as u64 * radius as u64;
+        let mut nearby = Vec::new(&env);
+        for (_, p) in Self::read_players(&env).iter() {
+            let dx = p.x.abs_diff(center_x) as u64;
+            let dy = p.y.abs_diff(center_y) as u64;
+            if dx * dx + dy * dy <= radius_sq {
+                nearby.push_back(NearbyPlayer {
+                    player_hash: p.player_hash,
+                    x: p.x,
+                    y: p.y,
+                    alive: p.alive,
+                });
+            }
+        }
+        nearby
+    }
+
+    // Nine bytes per player in `get_players` order: big-endian `x` and `y`, then 1 if alive.
+    // Polling clients keep the roster from `get_players` and refetch only this blob.
+    pub fn get_positions_packed(env: Env) -> Bytes {
+        let mut packed = Bytes::new(&env);
+        for (_, p) in Self::read_players(&env).iter() {
+            packed.extend_from_array(&p.x.to_be_bytes());
+            packed.extend_from_array(&p.y.to_be_bytes());
+            packed.push_back(u8::from(p.alive));
+        }
+        packed
+    }
+
     pub fn get_config(env: Env) -> GameConfig {
         Self::read_config(&env)
     }
@@ -572,21 +1479,165 @@ impl AmongUsContract {
         Self::read_state(&env)
     }
 
-    pub fn verify_zk_proof(env: Env, proof_hash: BytesN<32>, public_inputs: Vec<BytesN<32>>) -> bool {
+    pub fn get_capabilities(env: Env) -> Vec<Symbol> {
+        let mut capabilities = Vec::new(&env);
+        if cfg!(feature = "economy") {
+            capabilities.push_back(symbol_short!("economy"));
+        }
+        if cfg!(feature = "cosmetics") {
+            capabilities.push_back(symbol_short!("cosmetics"));
+        }
+        if cfg!(feature = "anonymous-voting") {
+            capabilities.push_back(symbol_short!("anon_vote"));
+        }
+        if cfg!(feature = "fog-of-war") {
+            capabilities.push_back(symbol_short!("fog"));
+        }
+        capabilities
+    }
+
+    pub fn get_match_report(env: Env) -> Option<MatchReport> {
+        env.storage().instance().get(&DataKey::MatchReport)
+    }
+
+    pub fn get_game_scope(env: Env) -> BytesN<32> {
+        Self::game_scope(&env)
+    }
+
+    pub fn get_checkpoint_seq(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::CheckpointSeq)
+            .unwrap_or(0)
+    }
+
+    pub fn verify_zk_proof(env: Env, proof: Bytes, public_inputs: Vec<BytesN<32>>) -> bool {
+        Self::ensure_verifier_enabled(&env);
         let verifier: Address = env
             .storage()
             .instance()
             .get(&DataKey::Verifier)
-            .unwrap_or_else(|| panic!("verifier not configured"));
+            .unwrap_or_else(|| fail(&env, GameError::VerifierNotConfigured));
+        let args = Self::verifier_args(&env, &CIRCUIT_DIRECT, proof, public_inputs);
+        Self::call_verifier(&env, &CIRCUIT_DIRECT, &verifier, args)
+    }
+}
 
-        let args: Vec<Val> = vec![
-            &env,
-            proof_hash.into_val(&env),
-            public_inputs.into_val(&env),
-        ];
+impl AmongUsContract {
+    pub(crate) fn verify_circuit(
+        env: &Env,
+        circuit_id: Symbol,
+        proof: Bytes,
+        public_inputs: Vec<BytesN<32>>,
+    ) -> bool {
+        Self::ensure_verifier_enabled(env);
+        // Only actions a seated player signs for reach here in a casual match; nullifiers and
+        // every game rule still apply, and only the proof itself goes unchecked.
+        if Self::is_casual(env.clone()) {
+            return true;
+        }
+        let verifier = Self::circuit_verifier(env, &circuit_id);
+        let args = Self::verifier_args(env, &circuit_id, proof, public_inputs);
+        let verified = Self::call_verifier(env, &circuit_id, &verifier, args.clone());
+        Self::shadow_verify(env, &circuit_id, args, verified);
+        verified
+    }
 
-        env.invoke_contract::<bool>(&verifier, &symbol_short!("verify"), args)
+    // With no verifier at all, a ranked (league) match refuses to start and a casual one
+    // starts without proofs, so a misconfigured deployment fails before anyone has played.
+    fn ensure_verifier_or_casual(env: &Env) -> Result<(), GameError> {
+        let configured = env.storage().instance().has(&DataKey::Verifier)
+            || CIRCUIT_IDS
+                .iter()
+                .any(|circuit_id| Self::active_vk(env, circuit_id).is_some());
+        if configured {
+            return Ok(());
+        }
+        if Self::league_registry(env).is_some() || Self::stakes_configured(env) {
+            return Err(GameError::VerifierNotConfigured);
+        }
+        env.storage().instance().set(&DataKey::Casual, &true);
+        env.events().publish((symbol_short!("casual"),), ());
+        Ok(())
+    }
+
+    // Nothing of value may ride on a match whose proofs go unchecked.
+    fn stakes_configured(env: &Env) -> bool {
+        let storage = env.storage().instance();
+        #[cfg(feature = "economy")]
+        if storage.has(&EscrowKey::Economy)
+            || storage.has(&RewardKey::TaskRewards)
+            || Self::read_players(env)
+                .iter()
+                .any(|(_, p)| storage.has(&BountyKey::Bounties(p.player_hash)))
+        {
+            return true;
+        }
+        storage.has(&SettlementKey::BondConfig)
+    }
+
+    // Calls whose only credential is a proof have nothing to stand on in a casual match.
+    pub(crate) fn ensure_not_casual(env: &Env) -> Result<(), GameError> {
+        if Self::is_casual(env.clone()) {
+            return Err(GameError::CasualMatch);
+        }
+        Ok(())
+    }
+
+    fn ensure_verifier_enabled(env: &Env) {
+        if Self::is_verifier_disabled(env) {
+            fail(env, GameError::VerifierDisabled);
+        }
+    }
+
+    // Every proof is bound to this deployment, so one minted for another game fails here.
+    // Only the proof's hash reaches the event log; the bytes themselves go to the verifier.
+    fn verifier_args(
+        env: &Env,
+        circuit_id: &Symbol,
+        proof: Bytes,
+        public_inputs: Vec<BytesN<32>>,
+    ) -> Vec<Val> {
+        if proof.len() > Self::get_max_proof_len(env.clone()) {
+            fail(env, ModuleError::ProofTooLarge);
+        }
+        env.events().publish(
+            (symbol_short!("proof"), circuit_id.clone()),
+            env.crypto().sha256(&proof),
+        );
+        let mut scoped = vec![env, Self::game_scope(env)];
+        scoped.append(&public_inputs);
+        vec![env, proof.into_val(env), scoped.into_val(env)]
+    }
+
+    fn call_verifier(env: &Env, circuit_id: &Symbol, verifier: &Address, args: Vec<Val>) -> bool {
+        let outcome = Self::invoke_verifier(env, verifier, args);
+        Self::note_verification(env, circuit_id, outcome);
+        outcome.unwrap_or_else(|| fail(env, GameError::VerifierFailed))
+    }
+
+    // A trapping verifier or one returning a non-bool is a failed call, not a verdict.
+    pub(crate) fn invoke_verifier(env: &Env, verifier: &Address, args: Vec<Val>) -> Option<bool> {
+        // Callers spend nullifiers and write state first; the lock rejects any
+        // game entrypoint the verifier reaches before returning.
+        if env.storage().instance().has(&DataKey::VerifierLock) {
+            fail(env, GameError::VerifierFailed);
+        }
+        env.storage().instance().set(&DataKey::VerifierLock, &true);
+        let verified = match env.try_invoke_contract::<bool, soroban_sdk::Error>(
+            verifier,
+            &symbol_short!("verify"),
+            args,
+        ) {
+            Ok(Ok(verified)) => Some(verified),
+            _ => None,
+        };
+        env.storage().instance().remove(&DataKey::VerifierLock);
+        verified
     }
 }
 
 mod test;
+mod test_nullifiers;
+mod test_verifiers;
+mod test_voting_modes;
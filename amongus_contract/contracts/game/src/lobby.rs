@@ -0,0 +1,479 @@
+use soroban_sdk::{
+    contractimpl, contracttype, symbol_short, vec, Address, BytesN, Env, IntoVal, Symbol, Val, Vec,
+};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::clock;
+use crate::constants::{fail, COLOR_PALETTE, PHASE_LOBBY, PHASE_PLAYING};
+use crate::{
+    AmongUsContract, AmongUsContractClient, DataKey, DelegationKey, GameError, ModuleError,
+    ProofInput, WinKey,
+};
+
+const MAX_JOIN_QUEUE: u32 = 16;
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum LobbyKey {
+    JoinQueue,
+    AutoColor,
+    LateJoinGrace,
+    ReserveRole,
+    // Set while a dropped seat waits; true when the leaver revealed an impostor role.
+    OpenSeat,
+    ReservedSlots,
+    ReservedUntil,
+    // The factory that deployed this game, told of every seat change.
+    Factory,
+}
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub struct QueuedJoin {
+    pub player: Address,
+    pub color: Symbol,
+    pub name: Symbol,
+    pub player_hash: BytesN<32>,
+    pub role_hash: BytesN<32>,
+    pub stake: i128,
+}
+
+impl AmongUsContract {
+    pub(crate) fn read_queue(env: &Env) -> Vec<QueuedJoin> {
+        env.storage()
+            .instance()
+            .get(&LobbyKey::JoinQueue)
+            .unwrap_or(Vec::new(env))
+    }
+
+    fn write_queue(env: &Env, queue: &Vec<QueuedJoin>) {
+        env.storage().instance().set(&LobbyKey::JoinQueue, queue);
+    }
+
+    fn queue_index(queue: &Vec<QueuedJoin>, player: &Address) -> Option<u32> {
+        queue
+            .iter()
+            .position(|q| q.player == *player)
+            .map(|i| i as u32)
+    }
+
+    pub(crate) fn ensure_not_queued(
+        env: &Env,
+        player: &Address,
+        player_hash: &BytesN<32>,
+    ) -> Result<(), GameError> {
+        for queued in Self::read_queue(env).iter() {
+            if queued.player == *player {
+                return Err(GameError::AlreadyQueued);
+            }
+            if queued.player_hash == *player_hash {
+                return Err(GameError::DuplicatePlayerHash);
+            }
+        }
+        Ok(())
+    }
+
+    // Reserved holders who have not joined yet. Every hold lapses at the deadline, and none
+    // outlives the lobby.
+    fn open_reservations(env: &Env) -> Vec<Address> {
+        let storage = env.storage().instance();
+        let until: u64 = storage.get(&LobbyKey::ReservedUntil).unwrap_or(0);
+        let mut open = Vec::new(env);
+        if Self::read_state(env).phase != PHASE_LOBBY || Self::ledger_now(env) >= until {
+            return open;
+        }
+        let players = Self::read_players(env);
+        let reserved: Vec<Address> = storage
+            .get(&LobbyKey::ReservedSlots)
+            .unwrap_or(Vec::new(env));
+        for holder in reserved.iter() {
+            if !players.contains_key(holder.clone()) {
+                open.push_back(holder);
+            }
+        }
+        open
+    }
+
+    // A holder takes their own reserved seat; everyone else sees the reserved seats as taken.
+    pub(crate) fn lobby_full(env: &Env, player: &Address) -> bool {
+        let open = Self::open_reservations(env);
+        let held_for_others = open.len() - open.contains(player) as u32;
+        Self::read_players(env).len() + held_for_others >= Self::read_config(env).max_players
+    }
+
+    pub(crate) fn auto_color(env: &Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&LobbyKey::AutoColor)
+            .unwrap_or(false)
+    }
+
+    // A taken color is rejected unless `auto` is set, in which case the joiner gets the
+    // next free palette color and a `color` event tells clients what they were given.
+    pub(crate) fn assign_color(
+        env: &Env,
+        player: &Address,
+        requested: Symbol,
+        auto: bool,
+    ) -> Symbol {
+        let players = Self::read_players(env);
+        let taken = |color: &Symbol| players.values().iter().any(|p| p.color == *color);
+        if !taken(&requested) {
+            return requested;
+        }
+        if !auto {
+            fail(env, GameError::ColorTaken);
+        }
+        let start = COLOR_PALETTE
+            .iter()
+            .position(|c| *c == requested)
+            .map_or(0, |i| i + 1);
+        let color = (0..COLOR_PALETTE.len())
+            .map(|offset| COLOR_PALETTE[(start + offset) % COLOR_PALETTE.len()].clone())
+            .find(|color| !taken(color))
+            .unwrap_or_else(|| fail(env, GameError::NoFreeColors));
+        env.events()
+            .publish((symbol_short!("color"), player.clone()), color.clone());
+        color
+    }
+
+    // Seats change hands mid-match only while playing, inside the grace window, and until
+    // the reserve role is dealt.
+    pub(crate) fn late_join_window(env: &Env) -> bool {
+        let storage = env.storage().instance();
+        let grace: u64 = storage.get(&LobbyKey::LateJoinGrace).unwrap_or(0);
+        let start: u64 = storage.get(&WinKey::MatchStart).unwrap_or(0);
+        Self::read_state(env).phase == PHASE_PLAYING
+            && storage.has(&LobbyKey::ReserveRole)
+            && !clock::has_elapsed(start, grace, Self::match_time(env))
+    }
+
+    // Lets the deploying factory index games by player; `hook` is its `record_seat`,
+    // `record_unseat` or `record_game_end`, and `arg` the player or players it concerns.
+    pub(crate) fn notify_factory(env: &Env, hook: &str, arg: Val) {
+        let Some(factory) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&LobbyKey::Factory)
+        else {
+            return;
+        };
+        env.invoke_contract::<()>(
+            &factory,
+            &Symbol::new(env, hook),
+            vec![env, env.current_contract_address().into_val(env), arg],
+        );
+    }
+
+    // Seats a player. Mid-match (`role_hash` of None) the seat takes the reserve role, and
+    // the side the dropped seat cost gets its player back.
+    pub(crate) fn fill_seat(
+        env: &Env,
+        player: &Address,
+        color: Symbol,
+        name: Symbol,
+        player_hash: BytesN<32>,
+        role_hash: Option<BytesN<32>>,
+    ) {
+        let storage = env.storage().instance();
+        let late = role_hash.is_none();
+        let impostor_seat: bool = storage.get(&LobbyKey::OpenSeat).unwrap_or(false);
+        let role_hash = role_hash.unwrap_or_else(|| {
+            storage.remove(&LobbyKey::OpenSeat);
+            storage
+                .get(&LobbyKey::ReserveRole)
+                .unwrap_or_else(|| fail(env, GameError::WrongPhase))
+        });
+        Self::insert_player(env, player, color, name, player_hash, role_hash);
+        Self::notify_factory(env, "record_seat", player.into_val(env));
+        if late {
+            storage.remove(&LobbyKey::ReserveRole);
+            if impostor_seat {
+                storage.set(&DataKey::ImpostorsAlive, &(Self::impostors_alive(env) + 1));
+            } else {
+                storage.set(&DataKey::CrewAlive, &(Self::crew_alive(env) + 1));
+            }
+        }
+    }
+
+    // Inside the late-join window a player may also leave the running match. Their stake
+    // stays in the pot and the seat goes to the head of the queue, or waits for `late_join`.
+    // Roles stay hidden, so the seat counts as crew unless `reveal` opens it as an impostor,
+    // as for an ejection.
+    fn remove_from_lobby(
+        env: &Env,
+        player: &Address,
+        reveal: Option<ProofInput>,
+    ) -> Result<(), GameError> {
+        let in_match = Self::read_state(env).phase != PHASE_LOBBY;
+        let mut players = Self::read_players(env);
+        let entry = players
+            .get(player.clone())
+            .ok_or(GameError::PlayerNotFound)?;
+        // A dead seat has nothing left to hand over.
+        if in_match && !(entry.alive && Self::late_join_window(env)) {
+            return Err(GameError::WrongPhase);
+        }
+        if !in_match && reveal.is_some() {
+            fail(env, ModuleError::NothingToReveal);
+        }
+        players.remove(player.clone());
+        Self::write_players(env, &players);
+        env.storage()
+            .instance()
+            .remove(&DelegationKey::VoteDelegate(player.clone()));
+        #[cfg(feature = "fog-of-war")]
+        env.storage()
+            .instance()
+            .remove(&crate::FogKey::PosCommit(player.clone()));
+        Self::clear_profile(env, player);
+        Self::release_human(env, player);
+        Self::release_identity(env, player);
+        #[cfg(feature = "economy")]
+        if !in_match {
+            Self::refund_stake(env, player);
+        }
+        Self::notify_factory(env, "record_unseat", player.into_val(env));
+        env.events()
+            .publish((symbol_short!("left"), player.clone()), ());
+        if in_match {
+            let impostor_seat = reveal.is_some();
+            match reveal {
+                Some(proof) => {
+                    let round = Self::read_state(env).round;
+                    Self::reveal_ejected_impostor(env, entry.role_hash.clone(), round, proof)?;
+                    env.storage()
+                        .instance()
+                        .set(&DataKey::SpentRole(entry.role_hash), &true);
+                }
+                None => Self::note_crew_lost(env),
+            }
+            env.storage()
+                .instance()
+                .set(&LobbyKey::OpenSeat, &impostor_seat);
+        }
+        Self::admit_from_queue(env);
+        if in_match {
+            Self::settle_win(env, player.to_val());
+        }
+        Ok(())
+    }
+
+    // The first queued player takes the freed slot with the stake they deposited when queueing.
+    fn admit_from_queue(env: &Env) {
+        let mut queue = Self::read_queue(env);
+        let Some(next) = queue.pop_front() else {
+            return;
+        };
+        Self::write_queue(env, &queue);
+        #[cfg(feature = "economy")]
+        Self::add_to_pot(env, next.stake);
+        // The requested color may have been taken while waiting, so admission never rejects it.
+        let color = Self::assign_color(env, &next.player, next.color.clone(), true);
+        let role_hash = (Self::read_state(env).phase == PHASE_LOBBY).then_some(next.role_hash);
+        Self::fill_seat(
+            env,
+            &next.player,
+            color,
+            next.name,
+            next.player_hash,
+            role_hash,
+        );
+        env.events()
+            .publish((symbol_short!("admitted"), next.player), ());
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    pub fn queue_for_slot(
+        env: Env,
+        player: Address,
+        color: Symbol,
+        name: Symbol,
+        player_hash: BytesN<32>,
+        role_hash: BytesN<32>,
+    ) -> Result<u32, GameError> {
+        player.require_auth();
+        Self::ensure_not_ended(&env);
+        Self::ensure_not_paused(&env);
+        Self::ensure_can_join(&env, &player, &player_hash, false)?;
+        if !Self::lobby_full(&env, &player) {
+            return Err(GameError::LobbyHasOpenSlots);
+        }
+        let mut queue = Self::read_queue(&env);
+        if queue.len() >= MAX_JOIN_QUEUE {
+            return Err(GameError::JoinQueueFull);
+        }
+        Self::ensure_name_allowed(&env, &player, &name);
+        Self::seat_human(&env, &player);
+        Self::claim_identity(&env, &player, &player_hash);
+
+        #[cfg(feature = "economy")]
+        let stake = Self::hold_stake(&env, &player);
+        #[cfg(not(feature = "economy"))]
+        let stake = 0;
+        queue.push_back(QueuedJoin {
+            player: player.clone(),
+            color,
+            name,
+            player_hash,
+            role_hash,
+            stake,
+        });
+        Self::write_queue(&env, &queue);
+        let position = queue.len();
+        env.events()
+            .publish((symbol_short!("queued"), player), position);
+        Ok(position)
+    }
+
+    // Commits the dealer's reserve leaf before the match, so it cannot be picked after
+    // seeing who left; the dealer hands its opening, dealt with the dropped seat's role, to
+    // the replacement. One reserve refills one seat. A `grace_secs` of 0 turns this off.
+    pub fn configure_late_join(
+        env: Env,
+        caller: Address,
+        grace_secs: u64,
+        reserve_role_hash: BytesN<32>,
+    ) {
+        Self::require_operator(&env, &caller);
+        Self::ensure_lobby(&env, false);
+        let storage = env.storage().instance();
+        storage.set(&LobbyKey::LateJoinGrace, &grace_secs);
+        storage.set(&LobbyKey::ReserveRole, &reserve_role_hash);
+    }
+
+    // The factory binds itself right after `init`, while the lobby is still empty, so it
+    // hears about every seat.
+    pub fn bind_factory(env: Env, caller: Address, factory: Address) {
+        Self::require_admin(&env, &caller);
+        factory.require_auth();
+        Self::ensure_lobby(&env, true);
+        if env.storage().instance().has(&LobbyKey::Factory) {
+            fail(&env, GameError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&LobbyKey::Factory, &factory);
+    }
+
+    pub fn get_factory(env: Env) -> Option<Address> {
+        env.storage().instance().get(&LobbyKey::Factory)
+    }
+
+    // Fills a seat dropped inside the late-join window when nobody was queued for it. The
+    // joiner passes the lobby's join checks and takes the reserve role.
+    pub fn late_join(
+        env: Env,
+        player: Address,
+        color: Symbol,
+        name: Symbol,
+        player_hash: BytesN<32>,
+    ) -> Result<(), GameError> {
+        player.require_auth();
+        Self::enter_lobby(&env, &player, color, name, player_hash, None)
+    }
+
+    // Holds seats for listed addresses (streamers, organizers) until `until`. Holders join
+    // through `join_game` and pay the stake as usual but skip the queue; replacing the list
+    // replaces every hold, and an empty list clears them.
+    pub fn reserve_slots(env: Env, caller: Address, holders: Vec<Address>, until: u64) {
+        Self::require_operator(&env, &caller);
+        Self::ensure_lobby(&env, false);
+        let seated = Self::read_players(&env).len();
+        if seated + holders.len() > Self::read_config(&env).max_players {
+            fail(&env, GameError::InvalidConfig);
+        }
+        let storage = env.storage().instance();
+        storage.set(&LobbyKey::ReservedSlots, &holders);
+        storage.set(&LobbyKey::ReservedUntil, &until);
+    }
+
+    // Past the deadline, hands the lapsed seats to the head of the queue. Anyone may call it.
+    pub fn release_reservations(env: Env) -> u32 {
+        let storage = env.storage().instance();
+        let until: u64 = storage.get(&LobbyKey::ReservedUntil).unwrap_or(0);
+        if Self::ledger_now(&env) < until {
+            fail(&env, ModuleError::ReservationsHeld);
+        }
+        storage.remove(&LobbyKey::ReservedSlots);
+        storage.remove(&LobbyKey::ReservedUntil);
+        let mut admitted = 0;
+        while let Some(next) = Self::read_queue(&env).first() {
+            if Self::lobby_full(&env, &next.player) {
+                break;
+            }
+            Self::admit_from_queue(&env);
+            admitted += 1;
+        }
+        admitted
+    }
+
+    pub fn get_reserved_slots(env: Env) -> Vec<Address> {
+        Self::open_reservations(&env)
+    }
+
+    pub fn configure_color_assignment(env: Env, caller: Address, auto: bool) {
+        Self::require_operator(&env, &caller);
+        env.storage().instance().set(&LobbyKey::AutoColor, &auto);
+    }
+
+    pub fn get_color_assignment(env: Env) -> bool {
+        Self::auto_color(&env)
+    }
+
+    pub fn leave_queue(env: Env, player: Address) -> Result<(), GameError> {
+        player.require_auth();
+        let mut queue = Self::read_queue(&env);
+        let index = Self::queue_index(&queue, &player).ok_or(GameError::NotQueued)?;
+        let entry = queue.get(index).ok_or(GameError::NotQueued)?;
+        queue.remove(index);
+        Self::write_queue(&env, &queue);
+        Self::release_human(&env, &player);
+        Self::release_identity(&env, &player);
+        #[cfg(feature = "economy")]
+        Self::release_held_stake(&env, &player, entry.stake);
+        #[cfg(not(feature = "economy"))]
+        let _ = entry;
+        env.events()
+            .publish((symbol_short!("unqueued"), player), ());
+        Ok(())
+    }
+
+    pub fn leave_game(
+        env: Env,
+        player: Address,
+        reveal: Option<ProofInput>,
+    ) -> Result<(), GameError> {
+        player.require_auth();
+        Self::remove_from_lobby(&env, &player, reveal)
+    }
+
+    pub fn kick_player(
+        env: Env,
+        caller: Address,
+        player: Address,
+        reveal: Option<ProofInput>,
+    ) -> Result<(), GameError> {
+        // A seat past its AFK deadline may be kicked by anyone; the removal rules still apply.
+        if Self::afk_deadline_in(&env, &player) == Some(0) {
+            caller.require_auth();
+        } else {
+            Self::require_operator(&env, &caller);
+        }
+        Self::remove_from_lobby(&env, &player, reveal)
+    }
+
+    // 1-based position in the join queue, or None when not queued.
+    pub fn get_queue_position(env: Env, player: Address) -> Option<u32> {
+        Self::queue_index(&Self::read_queue(&env), &player).map(|i| i + 1)
+    }
+
+    pub fn get_join_queue(env: Env) -> Vec<Address> {
+        let mut players = Vec::new(&env);
+        for queued in Self::read_queue(&env).iter() {
+            players.push_back(queued.player);
+        }
+        players
+    }
+}
@@ -0,0 +1,324 @@
+use soroban_sdk::{
+    contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, BytesN, Env, Symbol, Vec,
+};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::fail;
+use crate::{AmongUsContract, AmongUsContractClient, GameError, ModuleError};
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum MapKey {
+    MapEntry(u32, u32),
+    MapVersion(u32),
+    SelectedMap,
+    MapBond,
+    NextSubmission,
+    Submission(u32),
+}
+
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+pub struct MapRef {
+    pub map_id: u32,
+    pub version: u32,
+}
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub struct MapBondConfig {
+    pub token: Address,
+    pub bond: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+pub struct MapSubmission {
+    pub submitter: Address,
+    pub map_id: u32,
+    pub map: MapConfig,
+    pub token: Address,
+    pub bond: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+pub struct Point {
+    pub x: u32,
+    pub y: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+pub struct Room {
+    pub room_id: u32,
+    pub min: Point,
+    pub max: Point,
+}
+
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+pub struct Console {
+    pub task_id: u32,
+    pub kind: Symbol,
+    pub position: Point,
+}
+
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+pub struct MapConfig {
+    pub width: u32,
+    pub height: u32,
+    pub rooms: Vec<Room>,
+    pub vents: Vec<Point>,
+    pub consoles: Vec<Console>,
+    pub spawns: Vec<Point>,
+    pub emergency_button: Point,
+}
+
+impl AmongUsContract {
+    fn latest_map_version(env: &Env, map_id: u32) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&MapKey::MapVersion(map_id))
+            .unwrap_or(0)
+    }
+
+    fn read_map(env: &Env, map_id: u32, version: u32) -> Option<MapConfig> {
+        env.storage()
+            .persistent()
+            .get(&MapKey::MapEntry(map_id, version))
+    }
+
+    // Publishing never overwrites: games in flight keep reading the version they selected.
+    fn publish_map(env: &Env, map_id: u32, map: &MapConfig) -> u32 {
+        let version = Self::latest_map_version(env, map_id) + 1;
+        env.storage()
+            .persistent()
+            .set(&MapKey::MapEntry(map_id, version), map);
+        env.storage()
+            .persistent()
+            .set(&MapKey::MapVersion(map_id), &version);
+        version
+    }
+
+    pub(crate) fn selected_map(env: &Env) -> Option<MapConfig> {
+        let selected: MapRef = env.storage().instance().get(&MapKey::SelectedMap)?;
+        Self::read_map(env, selected.map_id, selected.version)
+    }
+
+    pub(crate) fn selected_map_hash(env: &Env) -> BytesN<32> {
+        match Self::selected_map(env) {
+            Some(map) => env.crypto().sha256(&map.to_xdr(env)),
+            None => BytesN::from_array(env, &[0; 32]),
+        }
+    }
+
+    fn read_submission(env: &Env, submission_id: u32) -> MapSubmission {
+        env.storage()
+            .persistent()
+            .get(&MapKey::Submission(submission_id))
+            .unwrap_or_else(|| fail(env, ModuleError::MapNotFound))
+    }
+
+    pub(crate) fn ensure_on_map(env: &Env, x: u32, y: u32) {
+        if let Some(map) = Self::selected_map(env) {
+            if x >= map.width || y >= map.height {
+                fail(env, ModuleError::OffMap);
+            }
+        }
+    }
+
+    pub(crate) fn ensure_at_emergency_button(env: &Env, x: u32, y: u32) {
+        if let Some(map) = Self::selected_map(env) {
+            if map.emergency_button.x != x || map.emergency_button.y != y {
+                fail(env, ModuleError::OutOfReach);
+            }
+        }
+    }
+
+    pub(crate) fn spawn_point(env: &Env, slot: u32) -> Point {
+        match Self::selected_map(env) {
+            Some(map) => map.spawns.get(slot % map.spawns.len()).unwrap(),
+            None => Point { x: 0, y: 0 },
+        }
+    }
+
+    pub(crate) fn apply_register_map(
+        env: &Env,
+        caller: &Address,
+        map_id: u32,
+        map: &MapConfig,
+    ) -> u32 {
+        Self::validate_map(env, map);
+        let version = Self::publish_map(env, map_id, map);
+        env.events().publish(
+            (symbol_short!("map_reg"), caller.clone()),
+            (map_id, version),
+        );
+        version
+    }
+
+    pub(crate) fn apply_select_map(env: &Env, caller: &Address, map_id: u32) {
+        Self::ensure_lobby(env, true);
+        let version = Self::latest_map_version(env, map_id);
+        if version == 0 {
+            fail(env, ModuleError::MapNotFound);
+        }
+        env.storage()
+            .instance()
+            .set(&MapKey::SelectedMap, &MapRef { map_id, version });
+        env.events().publish(
+            (symbol_short!("map_sel"), caller.clone()),
+            (map_id, version),
+        );
+    }
+
+    fn validate_map(env: &Env, map: &MapConfig) {
+        if map.width == 0 || map.height == 0 {
+            fail(env, ModuleError::InvalidMap);
+        }
+        if map.spawns.is_empty() {
+            fail(env, ModuleError::InvalidMap);
+        }
+        let inside = |p: &Point| p.x < map.width && p.y < map.height;
+        for room in map.rooms.iter() {
+            if !inside(&room.min) || !inside(&room.max) {
+                fail(env, ModuleError::InvalidMap);
+            }
+            if room.min.x > room.max.x || room.min.y > room.max.y {
+                fail(env, ModuleError::InvalidMap);
+            }
+        }
+        for vent in map.vents.iter() {
+            if !inside(&vent) {
+                fail(env, ModuleError::InvalidMap);
+            }
+        }
+        for console in map.consoles.iter() {
+            if !inside(&console.position) {
+                fail(env, ModuleError::InvalidMap);
+            }
+        }
+        for spawn in map.spawns.iter() {
+            if !inside(&spawn) {
+                fail(env, ModuleError::InvalidMap);
+            }
+        }
+        if !inside(&map.emergency_button) {
+            fail(env, ModuleError::InvalidMap);
+        }
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    pub fn register_map(env: Env, caller: Address, map_id: u32, map: MapConfig) -> u32 {
+        Self::require_operator(&env, &caller);
+        Self::apply_register_map(&env, &caller, map_id, &map)
+    }
+
+    pub fn configure_map_bond(env: Env, caller: Address, token: Address, bond: i128) {
+        Self::require_admin(&env, &caller);
+        if bond <= 0 {
+            fail(&env, GameError::InvalidConfig);
+        }
+        env.storage()
+            .instance()
+            .set(&MapKey::MapBond, &MapBondConfig { token, bond });
+    }
+
+    pub fn submit_map(env: Env, submitter: Address, map_id: u32, map: MapConfig) -> u32 {
+        submitter.require_auth();
+        Self::validate_map(&env, &map);
+        let bond: MapBondConfig = env
+            .storage()
+            .instance()
+            .get(&MapKey::MapBond)
+            .unwrap_or_else(|| fail(&env, ModuleError::FeatureDisabled));
+        Self::escrow_deposit(&env, &submitter, &bond.token, bond.bond);
+
+        let submission_id: u32 = env
+            .storage()
+            .instance()
+            .get(&MapKey::NextSubmission)
+            .unwrap_or(1);
+        env.storage()
+            .instance()
+            .set(&MapKey::NextSubmission, &(submission_id + 1));
+        env.storage().persistent().set(
+            &MapKey::Submission(submission_id),
+            &MapSubmission {
+                submitter: submitter.clone(),
+                map_id,
+                map,
+                token: bond.token,
+                bond: bond.bond,
+            },
+        );
+        env.events().publish(
+            (symbol_short!("map_sub"), submitter),
+            (map_id, submission_id),
+        );
+        submission_id
+    }
+
+    pub fn approve_map(env: Env, caller: Address, submission_id: u32) -> u32 {
+        Self::require_admin(&env, &caller);
+        let submission = Self::read_submission(&env, submission_id);
+        env.storage()
+            .persistent()
+            .remove(&MapKey::Submission(submission_id));
+
+        let version = Self::publish_map(&env, submission.map_id, &submission.map);
+        Self::escrow_release(
+            &env,
+            &submission.submitter,
+            &submission.token,
+            submission.bond,
+        );
+        env.events().publish(
+            (symbol_short!("map_ok"), submission.submitter),
+            (submission.map_id, version),
+        );
+        version
+    }
+
+    pub fn reject_map(env: Env, caller: Address, submission_id: u32) {
+        Self::require_admin(&env, &caller);
+        let submission = Self::read_submission(&env, submission_id);
+        env.storage()
+            .persistent()
+            .remove(&MapKey::Submission(submission_id));
+
+        Self::escrow_release(&env, &caller, &submission.token, submission.bond);
+        env.events().publish(
+            (symbol_short!("map_rej"), submission.submitter),
+            (submission.map_id, submission.bond),
+        );
+    }
+
+    pub fn select_map(env: Env, caller: Address, map_id: u32) {
+        Self::require_operator(&env, &caller);
+        Self::apply_select_map(&env, &caller, map_id);
+    }
+
+    pub fn get_map(env: Env, map_id: u32) -> Option<MapConfig> {
+        Self::read_map(&env, map_id, Self::latest_map_version(&env, map_id))
+    }
+
+    pub fn get_map_version(env: Env, map_id: u32, version: u32) -> Option<MapConfig> {
+        Self::read_map(&env, map_id, version)
+    }
+
+    pub fn get_selected_map(env: Env) -> Option<MapRef> {
+        env.storage().instance().get(&MapKey::SelectedMap)
+    }
+
+    pub fn get_map_submission(env: Env, submission_id: u32) -> Option<MapSubmission> {
+        env.storage()
+            .persistent()
+            .get(&MapKey::Submission(submission_id))
+    }
+}
@@ -0,0 +1,66 @@
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, Env};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::fail;
+use crate::{clock, AmongUsContract, AmongUsContractClient, GameError};
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum MatchPauseKey {
+    MatchPausedAt,
+    PausedTotal,
+}
+
+impl AmongUsContract {
+    pub(crate) fn is_match_paused(env: &Env) -> bool {
+        env.storage().instance().has(&MatchPauseKey::MatchPausedAt)
+    }
+
+    pub(crate) fn paused_total(env: &Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&MatchPauseKey::PausedTotal)
+            .unwrap_or(0)
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    pub fn pause_match(env: Env, caller: Address) {
+        Self::require_operator(&env, &caller);
+        Self::ensure_not_ended(&env);
+        if Self::is_match_paused(&env) {
+            fail(&env, GameError::MatchPaused);
+        }
+        let paused_at = Self::ledger_now(&env);
+        env.storage()
+            .instance()
+            .set(&MatchPauseKey::MatchPausedAt, &paused_at);
+        env.events()
+            .publish((symbol_short!("m_pause"), caller), paused_at);
+    }
+
+    pub fn resume_match(env: Env, caller: Address) {
+        Self::require_operator(&env, &caller);
+        let paused_at: u64 = env
+            .storage()
+            .instance()
+            .get(&MatchPauseKey::MatchPausedAt)
+            .unwrap_or_else(|| fail(&env, GameError::WrongPhase));
+        let paused_for = clock::elapsed(paused_at, Self::ledger_now(&env));
+        env.storage().instance().set(
+            &MatchPauseKey::PausedTotal,
+            &(Self::paused_total(&env) + paused_for),
+        );
+        env.storage()
+            .instance()
+            .remove(&MatchPauseKey::MatchPausedAt);
+        env.events()
+            .publish((symbol_short!("m_resume"), caller), paused_for);
+    }
+
+    pub fn get_match_time(env: Env) -> u64 {
+        Self::match_time(&env)
+    }
+}
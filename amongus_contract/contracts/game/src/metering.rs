@@ -0,0 +1,55 @@
+use soroban_sdk::{contractimpl, contracttype, Address, Bytes, Env, Map};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::{AmongUsContract, AmongUsContractClient};
+
+// Every metered action rewrites the instance entry and appends a timeline entry.
+const ACTION_WRITES: u32 = 2;
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum MeterKey {
+    Costs,
+}
+
+// An estimate, not a fee: counts what each player's calls made the contract do, so an
+// operator can see who drives storage and verifier spend before pricing actions.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PlayerCost {
+    pub actions: u32,
+    pub writes: u32,
+    pub proofs: u32,
+    pub proof_bytes: u32,
+}
+
+impl AmongUsContract {
+    fn cost_report(env: &Env) -> Map<Address, PlayerCost> {
+        env.storage()
+            .instance()
+            .get(&MeterKey::Costs)
+            .unwrap_or(Map::new(env))
+    }
+
+    // A rejected action reverts with its charge, so only accepted actions are counted.
+    pub(crate) fn meter(env: &Env, player: &Address, proof: Option<&Bytes>) {
+        let mut report = Self::cost_report(env);
+        let mut cost = report.get(player.clone()).unwrap_or_default();
+        cost.actions += 1;
+        cost.writes += ACTION_WRITES;
+        if let Some(proof) = proof {
+            cost.proofs += 1;
+            cost.proof_bytes += proof.len();
+        }
+        report.set(player.clone(), cost);
+        env.storage().instance().set(&MeterKey::Costs, &report);
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    pub fn get_cost_report(env: Env) -> Map<Address, PlayerCost> {
+        Self::cost_report(&env)
+    }
+}
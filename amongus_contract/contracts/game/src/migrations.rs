@@ -0,0 +1,321 @@
+use soroban_sdk::{
+    contractimpl, contracttype, symbol_short, Address, BytesN, Env, IntoVal, Map, Symbol, Val, Vec,
+};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::{fail, COSMETIC_NONE};
+use crate::{
+    AmongUsContract, AmongUsContractClient, DataKey, EmergencyKey, GameError, IncidentReason,
+    Repair, SyncKey, TimelineEntry, TimelineKey,
+};
+
+pub(crate) const STORAGE_VERSION: u32 = 13;
+// Stored ledger counts become match seconds at the network's usual close time.
+const SECS_PER_LEDGER: u64 = 5;
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub struct PlayerV1 {
+    pub x: u32,
+    pub y: u32,
+    pub alive: bool,
+    pub tasks_done: u32,
+    pub player_hash: BytesN<32>,
+    pub role_hash: BytesN<32>,
+    pub voted_for_hash: BytesN<32>,
+    pub color: Symbol,
+    pub name: Symbol,
+}
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub struct GameConfigV1 {
+    pub max_players: u32,
+    pub tasks_to_win: u32,
+}
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub struct GameConfigV2 {
+    pub max_players: u32,
+    pub tasks_to_win: u32,
+    pub max_tasks_per_round: u32,
+    pub max_kills_per_round: u32,
+}
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub struct GameConfigV3 {
+    pub max_players: u32,
+    pub tasks_to_win: u32,
+    pub max_tasks_per_round: u32,
+    pub max_kills_per_round: u32,
+    pub kill_range: u32,
+}
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub struct GameStateV1 {
+    pub phase: Symbol,
+    pub round: u32,
+    pub meeting_active: bool,
+    pub impostor_count: u32,
+    pub sabotage_active: bool,
+    pub winner: Symbol,
+}
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub struct IncidentV1 {
+    pub kind: Symbol,
+    pub round: u32,
+    pub timestamp: u64,
+}
+
+impl AmongUsContract {
+    // Layouts that predate the version key are told apart by the config shape:
+    // v2 added the per-round quota fields.
+    pub(crate) fn storage_version(env: &Env) -> u32 {
+        if let Some(version) = env.storage().instance().get(&DataKey::StorageVersion) {
+            return version;
+        }
+        let config: Option<Map<Symbol, Val>> = env.storage().instance().get(&DataKey::Config);
+        match config {
+            Some(fields) if fields.contains_key(Symbol::new(env, "max_kills_per_round")) => 2,
+            Some(_) => 1,
+            None => STORAGE_VERSION,
+        }
+    }
+
+    // v2 added the per-round quotas to the config, and the map hash and button round to the
+    // game state.
+    fn migrate_v1_to_v2(env: &Env) {
+        let defaults = Self::default_config();
+        let config = DataKey::Config;
+        Self::backfill_field(
+            env,
+            &config,
+            "max_tasks_per_round",
+            defaults.max_tasks_per_round,
+        );
+        Self::backfill_field(
+            env,
+            &config,
+            "max_kills_per_round",
+            defaults.max_kills_per_round,
+        );
+        let state = DataKey::GameState;
+        Self::backfill_field(env, &state, "map_hash", BytesN::from_array(env, &[0; 32]));
+        Self::backfill_field(env, &state, "button_used_round", 0u32);
+    }
+
+    // v3 added cosmetics and the join and last-action timestamps to every player.
+    fn migrate_v2_to_v3(env: &Env) {
+        let old: Map<Address, Map<Symbol, Val>> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Players)
+            .unwrap_or(Map::new(env));
+        let added = [
+            ("hat", COSMETIC_NONE.to_val()),
+            ("skin", COSMETIC_NONE.to_val()),
+            ("joined_at", 0u64.into_val(env)),
+            ("last_action_at", 0u64.into_val(env)),
+        ];
+        let mut players = Map::new(env);
+        for (addr, mut fields) in old.iter() {
+            for (field, default) in added {
+                fields.set(Symbol::new(env, field), default);
+            }
+            players.set(addr, fields);
+        }
+        env.storage().instance().set(&DataKey::Players, &players);
+        Self::bump_sync(env, SyncKey::PlayersVersion, Self::stored_round(env));
+    }
+
+    // v4 moved the incident log out of one instance vector into persistent entries.
+    fn migrate_v3_to_v4(env: &Env) {
+        let old: Vec<IncidentV1> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Incidents)
+            .unwrap_or(Vec::new(env));
+        for incident in old.iter() {
+            Self::append_incident(
+                env,
+                incident.kind,
+                IncidentReason::Unspecified,
+                incident.round,
+                incident.timestamp,
+            );
+        }
+        env.storage().instance().remove(&DataKey::Incidents);
+    }
+
+    // v7 added the voting deadline to the config and the meeting start ledger to the state.
+    fn migrate_v6_to_v7(env: &Env) {
+        Self::backfill_field(env, &DataKey::Config, "voting_deadline_ledgers", 0u32);
+        Self::backfill_field(env, &DataKey::GameState, "meeting_started_ledger", 0u32);
+    }
+
+    // v8 moved round timelines from persistent into temporary storage.
+    fn migrate_v7_to_v8(env: &Env) {
+        for round in 0..=Self::stored_round(env) {
+            let key = TimelineKey::Timeline(round);
+            let timeline: Option<Vec<TimelineEntry>> = env.storage().persistent().get(&key);
+            if let Some(timeline) = timeline {
+                Self::write_timeline(env, round, &timeline);
+                env.storage().persistent().remove(&key);
+            }
+        }
+    }
+
+    // v11 moved the kill cooldown onto match time. Pending cooldowns were stamped with a
+    // ledger sequence under the old key and are dropped with it.
+    fn migrate_v10_to_v11(env: &Env) {
+        Self::convert_ledger_field(
+            env,
+            &DataKey::Config,
+            "kill_cooldown_ledgers",
+            "kill_cooldown_secs",
+        );
+    }
+
+    // v12 moved the voting deadline onto match time. An open meeting's window restarts at
+    // the migration, since its start was stamped with a ledger sequence.
+    fn migrate_v11_to_v12(env: &Env) {
+        Self::convert_ledger_field(
+            env,
+            &DataKey::Config,
+            "voting_deadline_ledgers",
+            "voting_deadline_secs",
+        );
+        let fields: Option<Map<Symbol, Val>> = env.storage().instance().get(&DataKey::GameState);
+        if let Some(mut fields) = fields {
+            fields.remove(Symbol::new(env, "meeting_started_ledger"));
+            fields.set(
+                Symbol::new(env, "meeting_started_at"),
+                Self::match_time(env).into_val(env),
+            );
+            env.storage().instance().set(&DataKey::GameState, &fields);
+        }
+    }
+
+    // v13 moved the emergency meeting cooldown onto match time. A cooldown still running
+    // was stamped with a ledger sequence and is dropped.
+    fn migrate_v12_to_v13(env: &Env) {
+        let storage = env.storage().instance();
+        if let Some(ledgers) = storage.get::<_, u32>(&EmergencyKey::MeetingCooldown) {
+            storage.set(
+                &EmergencyKey::MeetingCooldown,
+                &(u64::from(ledgers) * SECS_PER_LEDGER),
+            );
+        }
+        storage.remove(&EmergencyKey::MeetingClosedAt);
+    }
+
+    // Earlier steps run before later state fields are backfilled, so they read the round
+    // from the raw field map instead of decoding a `GameState`.
+    fn stored_round(env: &Env) -> u32 {
+        let fields: Option<Map<Symbol, Val>> = env.storage().instance().get(&DataKey::GameState);
+        fields
+            .and_then(|fields| fields.get(Symbol::new(env, "round")))
+            .map(|round| round.into_val(env))
+            .unwrap_or(0)
+    }
+
+    // Replaces a ledger-count field with its match-seconds successor in place.
+    fn convert_ledger_field(env: &Env, key: &DataKey, from: &str, to: &str) {
+        let fields: Option<Map<Symbol, Val>> = env.storage().instance().get(key);
+        let Some(mut fields) = fields else {
+            return;
+        };
+        let old = Symbol::new(env, from);
+        let Some(ledgers) = fields.get(old.clone()) else {
+            return;
+        };
+        let ledgers: u32 = ledgers.into_val(env);
+        fields.remove(old);
+        fields.set(
+            Symbol::new(env, to),
+            (u64::from(ledgers) * SECS_PER_LEDGER).into_val(env),
+        );
+        env.storage().instance().set(key, &fields);
+    }
+
+    // Structs are stored as field maps, so a field added with a fixed default is backfilled
+    // in place; a record that already has the field is left alone.
+    fn backfill_field(env: &Env, key: &DataKey, field: &str, default: impl IntoVal<Env, Val>) {
+        let fields: Option<Map<Symbol, Val>> = env.storage().instance().get(key);
+        let Some(mut fields) = fields else {
+            return;
+        };
+        let name = Symbol::new(env, field);
+        if !fields.contains_key(name.clone()) {
+            fields.set(name, default.into_val(env));
+            env.storage().instance().set(key, &fields);
+        }
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    pub fn migrate(env: Env, caller: Address) -> u32 {
+        Self::require_admin(&env, &caller);
+        let from = Self::storage_version(&env);
+        let mut version = from;
+        let defaults = Self::default_config();
+        let config = DataKey::Config;
+        while version < STORAGE_VERSION {
+            match version {
+                1 => Self::migrate_v1_to_v2(&env),
+                2 => Self::migrate_v2_to_v3(&env),
+                3 => Self::migrate_v3_to_v4(&env),
+                // v5 added `kill_range`, v6 `kill_cooldown_ledgers`.
+                4 => Self::backfill_field(&env, &config, "kill_range", defaults.kill_range),
+                5 => Self::backfill_field(&env, &config, "kill_cooldown_ledgers", 0u32),
+                6 => Self::migrate_v6_to_v7(&env),
+                7 => Self::migrate_v7_to_v8(&env),
+                // v9 added sabotage repairs to the match report, v10 `ghost_tasks_enabled`.
+                8 => Self::backfill_field(
+                    &env,
+                    &DataKey::MatchReport,
+                    "repairs",
+                    Vec::<Repair>::new(&env),
+                ),
+                9 => Self::backfill_field(
+                    &env,
+                    &config,
+                    "ghost_tasks_enabled",
+                    defaults.ghost_tasks_enabled,
+                ),
+                10 => Self::migrate_v10_to_v11(&env),
+                11 => Self::migrate_v11_to_v12(&env),
+                12 => Self::migrate_v12_to_v13(&env),
+                _ => fail(&env, GameError::InvalidConfig),
+            }
+            version += 1;
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::StorageVersion, &version);
+        env.events()
+            .publish((symbol_short!("migrated"), caller), (from, version));
+        version
+    }
+
+    // Swaps the code under this address; storage is left as is. Call `migrate` next so the
+    // new code reads its own layout.
+    pub fn upgrade(env: Env, caller: Address, new_wasm_hash: BytesN<32>) {
+        Self::require_admin(&env, &caller);
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+        env.events()
+            .publish((symbol_short!("upgraded"), caller), new_wasm_hash);
+    }
+
+    pub fn get_storage_version(env: Env) -> u32 {
+        Self::storage_version(&env)
+    }
+}
@@ -0,0 +1,54 @@
+use soroban_sdk::{contractimpl, contracttype, Address, Env, Vec};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::fail;
+use crate::{AmongUsContract, AmongUsContractClient, GameError, ModuleError, ProofInput, Receipt};
+
+const MAX_MULTI_CALL_ACTIONS: u32 = 4;
+
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+pub enum PlayerAction {
+    Move(u32, u32),
+    StartTask(u32),
+    SubmitTask(ProofInput),
+    StartMeeting,
+    ReportBody(Address),
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    // Runs the actions in order under one authorization; the first failure reverts the
+    // whole call, so no other transaction can land between them. Returns one receipt per
+    // action that reaches the round timeline (`StartTask` does not).
+    pub fn multi_call(
+        env: Env,
+        player: Address,
+        actions: Vec<PlayerAction>,
+    ) -> Result<Vec<Receipt>, GameError> {
+        player.require_auth();
+        if actions.is_empty() || actions.len() > MAX_MULTI_CALL_ACTIONS {
+            fail(&env, ModuleError::InvalidBatch);
+        }
+        let mut receipts = Vec::new(&env);
+        for action in actions.iter() {
+            let receipt = match action {
+                PlayerAction::Move(x, y) => Self::apply_move(&env, player.clone(), x, y)?,
+                PlayerAction::StartTask(task_id) => {
+                    Self::apply_start_task(&env, player.clone(), task_id);
+                    continue;
+                }
+                PlayerAction::SubmitTask(proof) => {
+                    Self::apply_task_proof(&env, player.clone(), proof)?
+                }
+                PlayerAction::StartMeeting => Self::apply_start_meeting(&env, player.clone())?,
+                PlayerAction::ReportBody(victim) => {
+                    Self::apply_report_body(&env, player.clone(), victim)?
+                }
+            };
+            receipts.push_back(receipt);
+        }
+        Ok(receipts)
+    }
+}
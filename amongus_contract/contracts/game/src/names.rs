@@ -0,0 +1,49 @@
+use soroban_sdk::{contractimpl, contracttype, vec, Address, Env, IntoVal, Symbol};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::fail;
+use crate::{AmongUsContract, AmongUsContractClient, ModuleError};
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum NameKey {
+    NamePolicy,
+}
+
+impl AmongUsContract {
+    // The policy contract answers `allow_name(player, name) -> bool`, so moderation rules
+    // live with the host rather than in a blocklist compiled into the game.
+    pub(crate) fn ensure_name_allowed(env: &Env, player: &Address, name: &Symbol) {
+        let Some(policy) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&NameKey::NamePolicy)
+        else {
+            return;
+        };
+        let allowed: bool = env.invoke_contract(
+            &policy,
+            &Symbol::new(env, "allow_name"),
+            vec![env, player.into_val(env), name.into_val(env)],
+        );
+        if !allowed {
+            fail(env, ModuleError::NameRejected);
+        }
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    pub fn configure_name_policy(env: Env, caller: Address, policy: Option<Address>) {
+        Self::require_operator(&env, &caller);
+        match policy {
+            Some(policy) => env.storage().instance().set(&NameKey::NamePolicy, &policy),
+            None => env.storage().instance().remove(&NameKey::NamePolicy),
+        }
+    }
+
+    pub fn get_name_policy(env: Env) -> Option<Address> {
+        env.storage().instance().get(&NameKey::NamePolicy)
+    }
+}
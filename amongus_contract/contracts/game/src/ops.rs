@@ -0,0 +1,48 @@
+use soroban_sdk::{contractimpl, contracttype, Env, Symbol};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::{AmongUsContract, AmongUsContractClient};
+
+const DAY_SECS: u64 = 86_400;
+
+// One read for dashboards that poll instead of following events. The factory sums these
+// across its games.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperatorMetrics {
+    pub phase: Symbol,
+    pub players: u32,
+    pub players_today: u32,
+    pub proofs_verified: u32,
+    pub proof_failures: u32,
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    // "Today" is the current UTC day of the ledger clock. Failures count rejected and
+    // failed verdicts, which only stick through calls that return them.
+    pub fn get_operator_metrics(env: Env) -> OperatorMetrics {
+        let day_start = Self::ledger_now(&env) / DAY_SECS * DAY_SECS;
+        let players = Self::read_players(&env);
+        let mut players_today = 0u32;
+        for (_, p) in players.iter() {
+            if p.joined_at >= day_start {
+                players_today += 1;
+            }
+        }
+        let mut proofs_verified = 0u32;
+        let mut proof_failures = 0u32;
+        for (_, tally) in Self::circuit_stats(&env).iter() {
+            proofs_verified += tally.accepted;
+            proof_failures += tally.rejected + tally.failed;
+        }
+        OperatorMetrics {
+            phase: Self::read_state(&env).phase,
+            players: players.len(),
+            players_today,
+            proofs_verified,
+            proof_failures,
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use soroban_sdk::{contractimpl, contracttype, symbol_short, vec, Address, BytesN, Env, IntoVal};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::{fail, CIRCUIT_HUMAN};
+use crate::{AmongUsContract, AmongUsContractClient, DataKey, GameError, ModuleError, ProofInput};
+
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+pub enum PersonhoodGate {
+    Registry(Address),
+    Credential,
+}
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum PersonhoodKey {
+    PersonhoodGate,
+    CredentialHuman(Address),
+    HumanSeat(BytesN<32>),
+    SeatedHuman(Address),
+}
+
+impl AmongUsContract {
+    fn personhood_gate(env: &Env) -> Option<PersonhoodGate> {
+        env.storage().instance().get(&PersonhoodKey::PersonhoodGate)
+    }
+
+    fn human_id(env: &Env, gate: &PersonhoodGate, player: &Address) -> BytesN<32> {
+        let id: Option<BytesN<32>> = match gate {
+            PersonhoodGate::Registry(registry) => env.invoke_contract(
+                registry,
+                &symbol_short!("human_id"),
+                vec![env, player.into_val(env)],
+            ),
+            PersonhoodGate::Credential => env
+                .storage()
+                .instance()
+                .get(&PersonhoodKey::CredentialHuman(player.clone())),
+        };
+        id.unwrap_or_else(|| fail(env, ModuleError::NotAttested))
+    }
+
+    // One human, one slot: the attested human id is held for as long as its wallet is
+    // seated or queued.
+    pub(crate) fn seat_human(env: &Env, player: &Address) {
+        let Some(gate) = Self::personhood_gate(env) else {
+            return;
+        };
+        let id = Self::human_id(env, &gate, player);
+        if env
+            .storage()
+            .instance()
+            .has(&PersonhoodKey::HumanSeat(id.clone()))
+        {
+            fail(env, ModuleError::IdentityInUse);
+        }
+        env.storage()
+            .instance()
+            .set(&PersonhoodKey::HumanSeat(id.clone()), player);
+        env.storage()
+            .instance()
+            .set(&PersonhoodKey::SeatedHuman(player.clone()), &id);
+    }
+
+    pub(crate) fn release_human(env: &Env, player: &Address) {
+        let key = PersonhoodKey::SeatedHuman(player.clone());
+        if let Some(id) = env.storage().instance().get::<_, BytesN<32>>(&key) {
+            env.storage().instance().remove(&key);
+            env.storage()
+                .instance()
+                .remove(&PersonhoodKey::HumanSeat(id));
+        }
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    pub fn configure_personhood(env: Env, caller: Address, gate: Option<PersonhoodGate>) {
+        Self::require_operator(&env, &caller);
+        Self::ensure_lobby(&env, true);
+        match gate {
+            Some(gate) => env
+                .storage()
+                .instance()
+                .set(&PersonhoodKey::PersonhoodGate, &gate),
+            None => env
+                .storage()
+                .instance()
+                .remove(&PersonhoodKey::PersonhoodGate),
+        }
+    }
+
+    // The nullifier is derived from the credential and this game, so a second wallet held
+    // by the same human cannot attest again.
+    pub fn attest_personhood(
+        env: Env,
+        player: Address,
+        proof: ProofInput,
+    ) -> Result<(), GameError> {
+        player.require_auth();
+        Self::ensure_not_casual(&env)?;
+        if Self::personhood_gate(&env) != Some(PersonhoodGate::Credential) {
+            fail(&env, ModuleError::FeatureDisabled);
+        }
+        if env
+            .storage()
+            .instance()
+            .has(&DataKey::UsedNullifier(proof.nullifier.clone()))
+        {
+            fail(&env, ModuleError::AlreadyAttested);
+        }
+
+        let public_inputs = vec![&env, proof.nullifier.clone()];
+        Self::consume_nullifier(&env, proof.nullifier.clone());
+        env.storage().instance().set(
+            &PersonhoodKey::CredentialHuman(player.clone()),
+            &proof.nullifier,
+        );
+        if !Self::verify_circuit(&env, CIRCUIT_HUMAN, proof.proof, public_inputs) {
+            return Err(GameError::InvalidProof);
+        }
+        env.events().publish((symbol_short!("human"), player), ());
+        Ok(())
+    }
+
+    pub fn get_personhood_gate(env: Env) -> Option<PersonhoodGate> {
+        Self::personhood_gate(&env)
+    }
+}
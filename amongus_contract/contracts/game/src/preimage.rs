@@ -0,0 +1,75 @@
+use soroban_sdk::{contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, BytesN, Env};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::fail;
+use crate::{AmongUsContract, AmongUsContractClient, ModuleError};
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum PreimageKey {
+    PreimageRequired,
+    HashOpening(Address),
+}
+
+impl AmongUsContract {
+    fn preimage_required(env: &Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&PreimageKey::PreimageRequired)
+            .unwrap_or(false)
+    }
+
+    // The opening folds in the joiner's address, so a hash published by one player
+    // cannot be opened, and therefore not squatted, by another.
+    fn opened_hash(env: &Env, player: &Address, salt: BytesN<32>) -> BytesN<32> {
+        let mut preimage = player.clone().to_xdr(env);
+        preimage.append(&salt.into());
+        env.crypto().sha256(&preimage)
+    }
+
+    pub(crate) fn ensure_hash_opened(env: &Env, player: &Address, player_hash: &BytesN<32>) {
+        if !Self::preimage_required(env) {
+            return;
+        }
+        let opened: Option<BytesN<32>> = env
+            .storage()
+            .instance()
+            .get(&PreimageKey::HashOpening(player.clone()));
+        if opened.as_ref() != Some(player_hash) {
+            fail(env, ModuleError::HashNotOpened);
+        }
+    }
+
+    pub(crate) fn clear_hash_opening(env: &Env, player: &Address) {
+        env.storage()
+            .instance()
+            .remove(&PreimageKey::HashOpening(player.clone()));
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    pub fn configure_preimage_check(env: Env, caller: Address, required: bool) {
+        Self::require_operator(&env, &caller);
+        env.storage()
+            .instance()
+            .set(&PreimageKey::PreimageRequired, &required);
+    }
+
+    // player_hash = sha256(player address XDR || salt)
+    pub fn open_player_hash(env: Env, player: Address, salt: BytesN<32>) -> BytesN<32> {
+        player.require_auth();
+        let player_hash = Self::opened_hash(&env, &player, salt);
+        env.storage()
+            .instance()
+            .set(&PreimageKey::HashOpening(player.clone()), &player_hash);
+        env.events()
+            .publish((symbol_short!("opened"), player), player_hash.clone());
+        player_hash
+    }
+
+    pub fn get_preimage_check(env: Env) -> bool {
+        Self::preimage_required(&env)
+    }
+}
@@ -0,0 +1,117 @@
+use soroban_sdk::{
+    contractimpl, contracttype, symbol_short, vec, Address, BytesN, Env, IntoVal, Map, Symbol,
+};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::fail;
+use crate::{AmongUsContract, AmongUsContractClient, GameError, ModuleError, Player};
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum ProfileKey {
+    ProfileCollection,
+    PlayerProfile(Address),
+    ProfileSeat(u32),
+}
+
+impl AmongUsContract {
+    fn profile_owner(env: &Env, collection: &Address, profile_id: u32) -> Address {
+        env.invoke_contract(
+            collection,
+            &symbol_short!("owner_of"),
+            vec![env, profile_id.into_val(env)],
+        )
+    }
+
+    pub(crate) fn clear_profile(env: &Env, player: &Address) {
+        let key = ProfileKey::PlayerProfile(player.clone());
+        if let Some(profile_id) = env.storage().instance().get::<_, u32>(&key) {
+            env.storage().instance().remove(&key);
+            env.storage()
+                .instance()
+                .remove(&ProfileKey::ProfileSeat(profile_id));
+        }
+    }
+
+    // Results are keyed by profile so stats follow the token across wallet rotations.
+    pub(crate) fn report_profile_results(
+        env: &Env,
+        players: &Map<Address, Player>,
+        winner: &Symbol,
+    ) {
+        for (addr, entry) in players.iter() {
+            let profile: Option<u32> = env
+                .storage()
+                .instance()
+                .get(&ProfileKey::PlayerProfile(addr.clone()));
+            if let Some(profile_id) = profile {
+                env.events().publish(
+                    (symbol_short!("prof_res"), profile_id),
+                    (addr, winner.clone(), entry.alive, entry.tasks_done),
+                );
+            }
+        }
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    pub fn configure_profiles(env: Env, caller: Address, collection: Address) {
+        Self::require_operator(&env, &caller);
+        Self::ensure_lobby(&env, true);
+        env.storage()
+            .instance()
+            .set(&ProfileKey::ProfileCollection, &collection);
+    }
+
+    pub fn join_with_profile(
+        env: Env,
+        player: Address,
+        profile_id: u32,
+        color: Symbol,
+        name: Symbol,
+        player_hash: BytesN<32>,
+        role_hash: BytesN<32>,
+    ) -> Result<(), GameError> {
+        player.require_auth();
+        let collection: Address = env
+            .storage()
+            .instance()
+            .get(&ProfileKey::ProfileCollection)
+            .unwrap_or_else(|| fail(&env, ModuleError::FeatureDisabled));
+        if Self::profile_owner(&env, &collection, profile_id) != player {
+            fail(&env, ModuleError::NotProfileOwner);
+        }
+        if env
+            .storage()
+            .instance()
+            .has(&ProfileKey::ProfileSeat(profile_id))
+        {
+            return Err(GameError::AlreadyJoined);
+        }
+
+        Self::enter_lobby(&env, &player, color, name, player_hash, Some(role_hash))?;
+        env.storage()
+            .instance()
+            .set(&ProfileKey::PlayerProfile(player.clone()), &profile_id);
+        env.storage()
+            .instance()
+            .set(&ProfileKey::ProfileSeat(profile_id), &player);
+        env.events()
+            .publish((symbol_short!("profile"), player), profile_id);
+        Ok(())
+    }
+
+    pub fn get_player_profile(env: Env, player: Address) -> Option<u32> {
+        env.storage()
+            .instance()
+            .get(&ProfileKey::PlayerProfile(player))
+    }
+
+    pub fn get_profile_player(env: Env, profile_id: u32) -> Option<Address> {
+        env.storage()
+            .instance()
+            .get(&ProfileKey::ProfileSeat(profile_id))
+    }
+}
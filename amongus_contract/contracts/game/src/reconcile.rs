@@ -0,0 +1,23 @@
+use soroban_sdk::{symbol_short, Env, IntoVal};
+
+use crate::{AmongUsContract, DataKey};
+
+impl AmongUsContract {
+    // Runs as a meeting closes. The side counts must add up to the seats alive in the player
+    // map, plus an ejection still waiting on its reveal. Only reveals move the impostor count,
+    // so a mismatch is put on the crew count, which is re-derived and flagged with a `drift`
+    // event; the refreshed totals then go through the win conditions.
+    pub(crate) fn reconcile_after_meeting(env: &Env, round: u32) {
+        let seated =
+            Self::count_alive(&Self::read_players(env)) + u32::from(Self::ejection_pending(env));
+        let impostors = Self::impostors_alive(env);
+        let crew = Self::crew_alive(env);
+        if impostors + crew != seated {
+            let derived = seated.saturating_sub(impostors);
+            env.events()
+                .publish((symbol_short!("drift"), round), (crew, derived));
+            env.storage().instance().set(&DataKey::CrewAlive, &derived);
+        }
+        Self::settle_win(env, symbol_short!("reconcile").into_val(env));
+    }
+}
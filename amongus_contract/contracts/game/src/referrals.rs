@@ -0,0 +1,179 @@
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, BytesN, Env, Symbol};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::fail;
+use crate::{AmongUsContract, AmongUsContractClient, GameError, ModuleError};
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum ReferralKey {
+    ReferralCode(Symbol),
+    ReferrerCode(Address),
+    ReferredBy(Address),
+    ReferralPoints(Address),
+    ReferralRedeemed(Address),
+    ReferralRewards,
+    ReferralPool,
+}
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub struct ReferralRewardConfig {
+    pub token: Address,
+    pub per_point: i128,
+}
+
+impl AmongUsContract {
+    fn referral_points(env: &Env, referrer: &Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&ReferralKey::ReferralPoints(referrer.clone()))
+            .unwrap_or(0)
+    }
+
+    fn referral_redeemed(env: &Env, referrer: &Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&ReferralKey::ReferralRedeemed(referrer.clone()))
+            .unwrap_or(0)
+    }
+
+    fn referral_pool(env: &Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&ReferralKey::ReferralPool)
+            .unwrap_or(0)
+    }
+
+    fn read_referral_rewards(env: &Env) -> ReferralRewardConfig {
+        env.storage()
+            .instance()
+            .get(&ReferralKey::ReferralRewards)
+            .unwrap_or_else(|| fail(env, ModuleError::FeatureDisabled))
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    // Codes and points are persistent, so a referrer keeps both from one match to the next.
+    pub fn register_referral_code(env: Env, referrer: Address, code: Symbol) {
+        referrer.require_auth();
+        let storage = env.storage().persistent();
+        if storage.has(&ReferralKey::ReferralCode(code.clone())) {
+            fail(&env, ModuleError::ReferralCodeTaken);
+        }
+        if storage.has(&ReferralKey::ReferrerCode(referrer.clone())) {
+            fail(&env, ModuleError::ReferralCodeTaken);
+        }
+        storage.set(&ReferralKey::ReferralCode(code.clone()), &referrer);
+        storage.set(&ReferralKey::ReferrerCode(referrer.clone()), &code);
+        env.events()
+            .publish((symbol_short!("ref_code"), referrer), code);
+    }
+
+    // A player is credited to the first code they join with; later joins through any code
+    // seat them without crediting anyone again.
+    pub fn join_with_referral(
+        env: Env,
+        player: Address,
+        code: Symbol,
+        color: Symbol,
+        name: Symbol,
+        player_hash: BytesN<32>,
+        role_hash: BytesN<32>,
+    ) -> Result<(), GameError> {
+        player.require_auth();
+        let referrer: Address = env
+            .storage()
+            .persistent()
+            .get(&ReferralKey::ReferralCode(code))
+            .unwrap_or_else(|| fail(&env, ModuleError::UnknownReferralCode));
+        if referrer == player {
+            fail(&env, ModuleError::SelfTarget);
+        }
+
+        Self::enter_lobby(&env, &player, color, name, player_hash, Some(role_hash))?;
+        let referred = ReferralKey::ReferredBy(player.clone());
+        if env.storage().persistent().has(&referred) {
+            return Ok(());
+        }
+        env.storage().persistent().set(&referred, &referrer);
+        let points = Self::referral_points(&env, &referrer) + 1;
+        env.storage()
+            .persistent()
+            .set(&ReferralKey::ReferralPoints(referrer.clone()), &points);
+        env.events()
+            .publish((symbol_short!("referral"), referrer), (player, points));
+        Ok(())
+    }
+
+    // Points earned before rewards were configured are redeemable at the configured rate.
+    pub fn configure_referral_rewards(env: Env, caller: Address, token: Address, per_point: i128) {
+        Self::require_operator(&env, &caller);
+        if per_point <= 0 {
+            fail(&env, GameError::AmountNotPositive);
+        }
+        if Self::referral_pool(&env) > 0 {
+            fail(&env, ModuleError::PoolFunded);
+        }
+        env.storage().instance().set(
+            &ReferralKey::ReferralRewards,
+            &ReferralRewardConfig { token, per_point },
+        );
+    }
+
+    pub fn fund_referral_rewards(env: Env, funder: Address, amount: i128) {
+        funder.require_auth();
+        if amount <= 0 {
+            fail(&env, GameError::AmountNotPositive);
+        }
+        let config = Self::read_referral_rewards(&env);
+        Self::escrow_deposit(&env, &funder, &config.token, amount);
+        let pool = Self::referral_pool(&env) + amount;
+        env.storage()
+            .instance()
+            .set(&ReferralKey::ReferralPool, &pool);
+        env.events()
+            .publish((symbol_short!("ref_fund"), funder), pool);
+    }
+
+    // Pays out every point not yet redeemed; the pool must cover the whole claim.
+    pub fn claim_referral_rewards(env: Env, referrer: Address) -> i128 {
+        referrer.require_auth();
+        let config = Self::read_referral_rewards(&env);
+        let points = Self::referral_points(&env, &referrer);
+        let unredeemed = points - Self::referral_redeemed(&env, &referrer);
+        let amount = config.per_point * unredeemed as i128;
+        let pool = Self::referral_pool(&env);
+        if amount > pool {
+            fail(&env, GameError::InsufficientEscrow);
+        }
+        env.storage()
+            .persistent()
+            .set(&ReferralKey::ReferralRedeemed(referrer.clone()), &points);
+        if amount > 0 {
+            env.storage()
+                .instance()
+                .set(&ReferralKey::ReferralPool, &(pool - amount));
+            Self::escrow_release(&env, &referrer, &config.token, amount);
+        }
+        env.events()
+            .publish((symbol_short!("ref_claim"), referrer), amount);
+        amount
+    }
+
+    pub fn get_referral_points(env: Env, referrer: Address) -> u32 {
+        Self::referral_points(&env, &referrer)
+    }
+
+    pub fn get_referral_code_owner(env: Env, code: Symbol) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&ReferralKey::ReferralCode(code))
+    }
+
+    pub fn get_referral_pool(env: Env) -> i128 {
+        Self::referral_pool(&env)
+    }
+}
@@ -0,0 +1,183 @@
+use soroban_sdk::{
+    contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, BytesN, Env, Vec,
+};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::{fail, PHASE_ENDED};
+use crate::{
+    clock, AmongUsContract, AmongUsContractClient, AnalyticsKey, DataKey, DelegationKey,
+    EmergencyKey, FlavorKey, GameError, GhostKey, MatchReport, MeterKey, ModuleError, SabotageKey,
+    TaskKey, TimelineKey, WinKey,
+};
+
+const DEFAULT_RETENTION: u64 = 30 * 24 * 60 * 60;
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum RetentionKey {
+    RetentionPeriod,
+    EndedAt,
+    ReportHash,
+    // Every nullifier the match spent, so `gc` can find the keys to drop.
+    SpentNullifiers,
+}
+
+impl AmongUsContract {
+    fn retention_period(env: &Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&RetentionKey::RetentionPeriod)
+            .unwrap_or(DEFAULT_RETENTION)
+    }
+
+    pub(crate) fn note_match_ended(env: &Env) {
+        env.storage()
+            .instance()
+            .set(&RetentionKey::EndedAt, &Self::ledger_now(env));
+    }
+
+    pub(crate) fn note_nullifier(env: &Env, nullifier: &BytesN<32>) {
+        let mut spent: Vec<BytesN<32>> = env
+            .storage()
+            .instance()
+            .get(&RetentionKey::SpentNullifiers)
+            .unwrap_or(Vec::new(env));
+        spent.push_back(nullifier.clone());
+        env.storage()
+            .instance()
+            .set(&RetentionKey::SpentNullifiers, &spent);
+    }
+
+    fn collect_nullifiers(env: &Env) {
+        let spent: Vec<BytesN<32>> = env
+            .storage()
+            .instance()
+            .get(&RetentionKey::SpentNullifiers)
+            .unwrap_or(Vec::new(env));
+        for nullifier in spent.iter() {
+            env.storage()
+                .instance()
+                .remove(&DataKey::UsedNullifier(nullifier));
+        }
+        env.storage()
+            .instance()
+            .remove(&RetentionKey::SpentNullifiers);
+    }
+
+    fn collect_player(env: &Env, player: &Address) {
+        let instance = env.storage().instance();
+        instance.remove(&DataKey::Quota(player.clone()));
+        instance.remove(&DataKey::LastKillAt(player.clone()));
+        instance.remove(&EmergencyKey::EmergencyMeetingsUsed(player.clone()));
+        Self::clear_move_stamp(env, player);
+        instance.remove(&DelegationKey::VoteDelegate(player.clone()));
+        instance.remove(&TaskKey::TaskStart(player.clone()));
+        #[cfg(feature = "fog-of-war")]
+        instance.remove(&crate::FogKey::PosCommit(player.clone()));
+        #[cfg(feature = "economy")]
+        instance.remove(&crate::EscrowKey::PayoutClaimed(player.clone()));
+        #[cfg(feature = "economy")]
+        instance.remove(&crate::RewardKey::RewardClaimed(player.clone()));
+        Self::clear_profile(env, player);
+        Self::release_human(env, player);
+        Self::clear_ghost(env, player);
+        Self::clear_hash_opening(env, player);
+    }
+
+    fn collect_round(env: &Env, round: u32) {
+        env.storage()
+            .temporary()
+            .remove(&TimelineKey::Timeline(round));
+        env.storage()
+            .instance()
+            .remove(&DelegationKey::DelegatedVotes(round));
+        Self::clear_round_stats(env, round);
+        Self::clear_round_grants(env, round);
+        Self::clear_speaker_order(env, round);
+        Self::clear_vote_tally(env, round);
+        #[cfg(feature = "anonymous-voting")]
+        env.storage()
+            .instance()
+            .remove(&crate::AnonKey::VoteTally(round));
+        #[cfg(feature = "anonymous-voting")]
+        env.storage()
+            .instance()
+            .remove(&crate::AnonKey::SuspicionHeat(round));
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    pub fn configure_retention(env: Env, caller: Address, period_secs: u64) {
+        Self::require_admin(&env, &caller);
+        env.storage()
+            .instance()
+            .set(&RetentionKey::RetentionPeriod, &period_secs);
+    }
+
+    // Permissionless once the retention period has run: drops per-player and per-round
+    // entries so only the match report and its hash keep paying rent. Unclaimed payouts
+    // and rewards can no longer be claimed afterwards, so unclaimed stake goes to the rake
+    // treasury first.
+    pub fn gc(env: Env) -> BytesN<32> {
+        if env.storage().instance().has(&RetentionKey::ReportHash) {
+            fail(&env, ModuleError::AlreadyCollected);
+        }
+        let state = Self::read_state(&env);
+        let ended_at: u64 = env
+            .storage()
+            .instance()
+            .get(&RetentionKey::EndedAt)
+            .filter(|_| state.phase == PHASE_ENDED)
+            .unwrap_or_else(|| fail(&env, GameError::GameNotEnded));
+        if !clock::has_elapsed(
+            ended_at,
+            Self::retention_period(&env),
+            Self::ledger_now(&env),
+        ) {
+            fail(&env, ModuleError::RetentionNotOver);
+        }
+        #[cfg(feature = "economy")]
+        Self::sweep_unclaimed_stake(&env);
+
+        let players = Self::read_players(&env);
+        for player in players.keys().iter() {
+            Self::collect_player(&env, &player);
+        }
+        env.storage().instance().remove(&DataKey::Players);
+        // Round 0 holds lobby-time entries such as personhood proofs.
+        for round in 0..=state.round {
+            Self::collect_round(&env, round);
+        }
+        env.storage().instance().remove(&AnalyticsKey::RoundKills);
+        env.storage().instance().remove(&FlavorKey::MatchFlavors);
+        env.storage().instance().remove(&GhostKey::GhostLog);
+        Self::clear_bodies(&env);
+        env.storage().instance().remove(&SabotageKey::Repairs);
+        env.storage().instance().remove(&WinKey::PendingEjection);
+        env.storage().instance().remove(&MeterKey::Costs);
+        Self::collect_nullifiers(&env);
+
+        let report: MatchReport = env
+            .storage()
+            .instance()
+            .get(&DataKey::MatchReport)
+            .unwrap_or_else(|| fail(&env, GameError::GameNotEnded));
+        let report_hash = env.crypto().sha256(&report.to_xdr(&env));
+        env.storage()
+            .instance()
+            .set(&RetentionKey::ReportHash, &report_hash);
+        env.events()
+            .publish((symbol_short!("gc"),), (players.len(), state.round));
+        report_hash
+    }
+
+    pub fn get_retention_period(env: Env) -> u64 {
+        Self::retention_period(&env)
+    }
+
+    pub fn get_report_hash(env: Env) -> Option<BytesN<32>> {
+        env.storage().instance().get(&RetentionKey::ReportHash)
+    }
+}
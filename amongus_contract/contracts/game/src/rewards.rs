@@ -0,0 +1,126 @@
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, Env};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::{fail, PHASE_ENDED};
+use crate::{AmongUsContract, AmongUsContractClient, GameError, ModuleError};
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum RewardKey {
+    TaskRewards,
+    RewardPool,
+    RewardRate,
+    RewardClaimed(Address),
+}
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub struct TaskRewardConfig {
+    pub token: Address,
+    pub per_task: i128,
+}
+
+impl AmongUsContract {
+    fn read_task_reward_config(env: &Env) -> TaskRewardConfig {
+        env.storage()
+            .instance()
+            .get(&RewardKey::TaskRewards)
+            .unwrap_or_else(|| fail(env, ModuleError::FeatureDisabled))
+    }
+
+    fn task_reward_pool(env: &Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&RewardKey::RewardPool)
+            .unwrap_or(0)
+    }
+
+    // The rate is fixed on the first claim so payouts do not depend on claim order.
+    fn task_reward_rate(env: &Env, config: &TaskRewardConfig) -> i128 {
+        if let Some(rate) = env.storage().instance().get(&RewardKey::RewardRate) {
+            return rate;
+        }
+        let total_tasks = Self::total_tasks(&Self::read_players(env)) as i128;
+        let rate = if total_tasks == 0 {
+            0
+        } else {
+            config
+                .per_task
+                .min(Self::task_reward_pool(env) / total_tasks)
+        };
+        env.storage().instance().set(&RewardKey::RewardRate, &rate);
+        rate
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    pub fn configure_task_rewards(env: Env, caller: Address, token: Address, per_task: i128) {
+        Self::require_admin(&env, &caller);
+        if per_task <= 0 {
+            fail(&env, GameError::InvalidConfig);
+        }
+        if Self::task_reward_pool(&env) > 0 {
+            fail(&env, ModuleError::PoolFunded);
+        }
+        env.storage().instance().set(
+            &RewardKey::TaskRewards,
+            &TaskRewardConfig { token, per_task },
+        );
+    }
+
+    pub fn fund_task_rewards(env: Env, funder: Address, amount: i128) {
+        funder.require_auth();
+        if amount <= 0 {
+            fail(&env, GameError::AmountNotPositive);
+        }
+        if env.storage().instance().has(&RewardKey::RewardRate) {
+            fail(&env, ModuleError::RewardsClaimed);
+        }
+        let config = Self::read_task_reward_config(&env);
+        Self::escrow_deposit(&env, &funder, &config.token, amount);
+        let pool = Self::task_reward_pool(&env) + amount;
+        env.storage().instance().set(&RewardKey::RewardPool, &pool);
+        env.events()
+            .publish((symbol_short!("tr_fund"), funder), pool);
+    }
+
+    pub fn claim_task_rewards(env: Env, player: Address) -> Result<i128, GameError> {
+        player.require_auth();
+        Self::ensure_not_casual(&env)?;
+        if Self::read_state(&env).phase != PHASE_ENDED {
+            return Err(GameError::GameNotEnded);
+        }
+        if env
+            .storage()
+            .instance()
+            .has(&RewardKey::RewardClaimed(player.clone()))
+        {
+            fail(&env, ModuleError::RewardsClaimed);
+        }
+        let entry = Self::read_players(&env)
+            .get(player.clone())
+            .ok_or(GameError::PlayerNotFound)?;
+
+        let config = Self::read_task_reward_config(&env);
+        let amount = Self::task_reward_rate(&env, &config) * entry.tasks_done as i128;
+        env.storage()
+            .instance()
+            .set(&RewardKey::RewardClaimed(player.clone()), &true);
+        if amount > 0 {
+            env.storage().instance().set(
+                &RewardKey::RewardPool,
+                &(Self::task_reward_pool(&env) - amount),
+            );
+            Self::escrow_release(&env, &player, &config.token, amount);
+        }
+        env.events()
+            .publish((symbol_short!("tr_claim"), player), amount);
+        Ok(amount)
+    }
+
+    pub fn get_task_reward_pool(env: Env) -> i128 {
+        Self::task_reward_pool(&env)
+    }
+}
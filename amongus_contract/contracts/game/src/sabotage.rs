@@ -0,0 +1,251 @@
+use soroban_sdk::{
+    contractimpl, contracttype, symbol_short, vec, Address, BytesN, Env, IntoVal, Symbol, Vec,
+};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::{fail, CIRCUIT_FIX, CIRCUIT_ROLE, PHASE_PLAYING};
+use crate::{
+    clock, AmongUsContract, AmongUsContractClient, DataKey, GameError, ModuleError, ProofInput,
+};
+
+pub(crate) const SABOTAGE_COMMS: Symbol = symbol_short!("comms");
+// A kind's index is its field input in fix proofs. Kinds from `FIRST_CRITICAL` on hand
+// the impostors the match unless fixed within the timeout.
+const FIRST_CRITICAL: u32 = 2;
+const SABOTAGE_KINDS: [Symbol; 4] = [
+    SABOTAGE_COMMS,
+    symbol_short!("lights"),
+    symbol_short!("reactor"),
+    symbol_short!("o2"),
+];
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum SabotageKey {
+    ActiveSabotage,
+    SabotageCooldown,
+    // Match time from which the next sabotage may be triggered.
+    SabotageReadyAt,
+    SabotageTimeout,
+    // Match time the running sabotage was triggered at.
+    SabotageTriggeredAt,
+    Repairs,
+}
+
+// One entry per completed fix, copied into the match report so stats contracts can rank
+// repairs per player and kind.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Repair {
+    pub kind: Symbol,
+    pub round: u32,
+    pub fixer: BytesN<32>,
+}
+
+impl AmongUsContract {
+    fn sabotage_index(env: &Env, kind: &Symbol) -> u32 {
+        SABOTAGE_KINDS
+            .iter()
+            .position(|k| k == kind)
+            .unwrap_or_else(|| fail(env, ModuleError::UnknownSabotage)) as u32
+    }
+
+    pub(crate) fn active_sabotage(env: &Env) -> Option<Symbol> {
+        env.storage().instance().get(&SabotageKey::ActiveSabotage)
+    }
+
+    // Due once a critical sabotage has run `sabotage_timeout_secs` of match time unfixed; a
+    // zero timeout turns it off. The timer was first counted in ledgers, and moved to match
+    // seconds with the cooldown so a paused match holds both.
+    pub(crate) fn sabotage_overdue(env: &Env) -> bool {
+        let Some(kind) = Self::active_sabotage(env) else {
+            return false;
+        };
+        let storage = env.storage().instance();
+        let timeout: u64 = storage.get(&SabotageKey::SabotageTimeout).unwrap_or(0);
+        let triggered_at: u64 = storage.get(&SabotageKey::SabotageTriggeredAt).unwrap_or(0);
+        Self::sabotage_index(env, &kind) >= FIRST_CRITICAL
+            && timeout > 0
+            && clock::has_elapsed(triggered_at, timeout, Self::match_time(env))
+    }
+
+    // Match seconds until the next sabotage may be triggered. The cooldown is shared by
+    // every impostor, so it tells a reader nothing about roles.
+    pub(crate) fn sabotage_ready_in(env: &Env) -> u64 {
+        let ready_at: u64 = env
+            .storage()
+            .instance()
+            .get(&SabotageKey::SabotageReadyAt)
+            .unwrap_or(0);
+        ready_at.saturating_sub(Self::match_time(env))
+    }
+
+    pub(crate) fn match_repairs(env: &Env) -> Vec<Repair> {
+        env.storage()
+            .instance()
+            .get(&SabotageKey::Repairs)
+            .unwrap_or(Vec::new(env))
+    }
+
+    fn ensure_proof_unused(env: &Env, proof: &ProofInput) -> Result<(), GameError> {
+        if env
+            .storage()
+            .instance()
+            .has(&DataKey::UsedNullifier(proof.nullifier.clone()))
+        {
+            return Err(GameError::NullifierUsed);
+        }
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    pub fn configure_sabotage(
+        env: Env,
+        caller: Address,
+        cooldown_secs: u64,
+        sabotage_timeout_secs: u64,
+    ) {
+        Self::require_operator(&env, &caller);
+        let storage = env.storage().instance();
+        storage.set(&SabotageKey::SabotageCooldown, &cooldown_secs);
+        storage.set(&SabotageKey::SabotageTimeout, &sabotage_timeout_secs);
+    }
+
+    // Anonymous: a role proof over `[role_root, round, kind index, nullifier]` opens one of
+    // the seated impostor commitments without naming it, so nothing here records who
+    // sabotaged. Dead impostors may sabotage too; only one sabotage runs at a time.
+    pub fn trigger_sabotage(env: Env, kind: Symbol, proof: ProofInput) -> Result<(), GameError> {
+        Self::ensure_not_ended(&env);
+        Self::ensure_not_paused(&env);
+        Self::ensure_not_casual(&env)?;
+        let mut state = Self::read_state(&env);
+        if state.phase != PHASE_PLAYING {
+            return Err(GameError::WrongPhase);
+        }
+        let index = Self::sabotage_index(&env, &kind);
+        if state.sabotage_active {
+            return Err(GameError::SabotageActive);
+        }
+        let ready_at: u64 = env
+            .storage()
+            .instance()
+            .get(&SabotageKey::SabotageReadyAt)
+            .unwrap_or(0);
+        if Self::match_time(&env) < ready_at {
+            return Err(GameError::SabotageCooldown);
+        }
+        Self::ensure_proof_unused(&env, &proof)?;
+
+        let public_inputs = vec![
+            &env,
+            Self::role_root(&env, false),
+            Self::field_input(&env, state.round),
+            Self::field_input(&env, index),
+            proof.nullifier.clone(),
+        ];
+        state.sabotage_active = true;
+        Self::write_state(&env, &state);
+        let storage = env.storage().instance();
+        storage.set(&SabotageKey::ActiveSabotage, &kind);
+        storage.set(&SabotageKey::SabotageTriggeredAt, &Self::match_time(&env));
+        // The circuit derives the nullifier from the hidden commitment, as for anonymous
+        // meetings; checking it here would need the seat the proof keeps private.
+        Self::consume_nullifier(&env, proof.nullifier);
+        if !Self::verify_circuit(&env, CIRCUIT_ROLE, proof.proof, public_inputs) {
+            return Err(GameError::InvalidProof);
+        }
+        Self::note_call(&env, &symbol_short!("sabotage"));
+        env.events()
+            .publish((symbol_short!("sabotage"), state.round), kind);
+        Ok(())
+    }
+
+    // The fix proof covers `[player_hash, round, kind index, nullifier]` on the `fix`
+    // circuit, so a task proof cannot stand in for it. The cooldown starts once fixed.
+    pub fn fix_sabotage(
+        env: Env,
+        player: Address,
+        kind: Symbol,
+        proof: ProofInput,
+    ) -> Result<(), GameError> {
+        player.require_auth();
+        Self::ensure_not_ended(&env);
+        Self::ensure_not_paused(&env);
+        let mut state = Self::read_state(&env);
+        if state.phase != PHASE_PLAYING {
+            return Err(GameError::WrongPhase);
+        }
+        let index = Self::sabotage_index(&env, &kind);
+        if Self::active_sabotage(&env) != Some(kind.clone()) {
+            return Err(GameError::SabotageNotActive);
+        }
+        let entry = Self::read_players(&env)
+            .get(player.clone())
+            .ok_or(GameError::PlayerNotFound)?;
+        if !entry.alive {
+            return Err(GameError::PlayerDead);
+        }
+        Self::ensure_proof_unused(&env, &proof)?;
+
+        state.sabotage_active = false;
+        Self::write_state(&env, &state);
+        let storage = env.storage().instance();
+        storage.remove(&SabotageKey::ActiveSabotage);
+        storage.remove(&SabotageKey::SabotageTriggeredAt);
+        let cooldown: u64 = storage.get(&SabotageKey::SabotageCooldown).unwrap_or(0);
+        storage.set(
+            &SabotageKey::SabotageReadyAt,
+            &clock::deadline(Self::match_time(&env), cooldown),
+        );
+        let public_inputs = vec![
+            &env,
+            entry.player_hash.clone(),
+            Self::field_input(&env, state.round),
+            Self::field_input(&env, index),
+            proof.nullifier.clone(),
+        ];
+        Self::ensure_derived_nullifier(
+            &env,
+            CIRCUIT_FIX,
+            Some(entry.player_hash.clone()),
+            0,
+            &proof.nullifier,
+        );
+        Self::consume_nullifier(&env, proof.nullifier);
+        Self::meter(&env, &player, Some(&proof.proof));
+        if !Self::verify_circuit(&env, CIRCUIT_FIX, proof.proof, public_inputs) {
+            return Err(GameError::InvalidProof);
+        }
+        let mut repairs = Self::match_repairs(&env);
+        repairs.push_back(Repair {
+            kind: kind.clone(),
+            round: state.round,
+            fixer: entry.player_hash.clone(),
+        });
+        env.storage()
+            .instance()
+            .set(&SabotageKey::Repairs, &repairs);
+        Self::record_action(&env, symbol_short!("fix"), entry.player_hash);
+        env.events().publish((symbol_short!("fixed"), player), kind);
+        Ok(())
+    }
+
+    // Critical timers run out between calls, so anyone may crank the match forward.
+    pub fn advance(env: Env) -> bool {
+        Self::ensure_not_paused(&env);
+        Self::settle_win(&env, symbol_short!("advance").into_val(&env))
+    }
+
+    // The `sabotage` event already tells the table; the getter is for impostor clients.
+    pub fn get_active_sabotage(
+        env: Env,
+        caller: Address,
+        grant_salt: Option<BytesN<32>>,
+    ) -> Option<Symbol> {
+        Self::ensure_read_grant(&env, &caller, grant_salt, symbol_short!("impostor"));
+        Self::active_sabotage(&env)
+    }
+}
@@ -0,0 +1,108 @@
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, Env, Symbol, Vec};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::fail;
+use crate::{AmongUsContract, AmongUsContractClient, GameError, ModuleError, Point};
+
+const MAX_SCENARIO_BODIES: u32 = 16;
+const MAX_SCENARIO_MOVES: u32 = 64;
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum ScenarioKey {
+    Scenario(u32),
+    // Timestamp the player first completed the scenario.
+    Achievement(Address, u32),
+}
+
+// A scripted single-player challenge: the player takes `role`, starts at `spawn`, must stand
+// on every pre-placed body and finish on `goal`, moving at most `max_step` units per move.
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+pub struct Scenario {
+    pub role: Symbol,
+    pub spawn: Point,
+    pub bodies: Vec<Point>,
+    pub goal: Point,
+    pub max_step: u32,
+    pub max_moves: u32,
+}
+
+impl AmongUsContract {
+    fn step_within(from: &Point, to: &Point, max_step: u32) -> bool {
+        let dx = from.x.abs_diff(to.x) as u64;
+        let dy = from.y.abs_diff(to.y) as u64;
+        dx * dx + dy * dy <= max_step as u64 * max_step as u64
+    }
+
+    // Replays the moves from the spawn; every step must be legal, so the ending alone
+    // cannot claim the objective.
+    fn replay_scenario(scenario: &Scenario, moves: &Vec<Point>) -> bool {
+        if moves.len() > scenario.max_moves {
+            return false;
+        }
+        let mut at = scenario.spawn.clone();
+        let mut visited = Vec::new(moves.env());
+        for next in moves.iter() {
+            if !Self::step_within(&at, &next, scenario.max_step) {
+                return false;
+            }
+            if scenario.bodies.contains(&next) && !visited.contains(&next) {
+                visited.push_back(next.clone());
+            }
+            at = next;
+        }
+        visited.len() == scenario.bodies.len() && at == scenario.goal
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    // Scenarios live beside the match and never touch its state, so tutorials run in any phase.
+    pub fn load_scenario(env: Env, caller: Address, scenario_id: u32, scenario: Scenario) {
+        Self::require_admin(&env, &caller);
+        if scenario.bodies.len() > MAX_SCENARIO_BODIES
+            || scenario.max_moves == 0
+            || scenario.max_moves > MAX_SCENARIO_MOVES
+        {
+            fail(&env, GameError::InvalidConfig);
+        }
+        env.storage()
+            .instance()
+            .set(&ScenarioKey::Scenario(scenario_id), &scenario);
+    }
+
+    pub fn complete_scenario(env: Env, player: Address, scenario_id: u32, moves: Vec<Point>) {
+        player.require_auth();
+        let scenario: Scenario = env
+            .storage()
+            .instance()
+            .get(&ScenarioKey::Scenario(scenario_id))
+            .unwrap_or_else(|| fail(&env, ModuleError::ScenarioNotFound));
+        let key = ScenarioKey::Achievement(player.clone(), scenario_id);
+        if env.storage().persistent().has(&key) {
+            fail(&env, ModuleError::ScenarioCompleted);
+        }
+        if !Self::replay_scenario(&scenario, &moves) {
+            fail(&env, ModuleError::ObjectiveNotMet);
+        }
+        env.storage()
+            .persistent()
+            .set(&key, &Self::ledger_now(&env));
+        env.events()
+            .publish((symbol_short!("achieved"), player), scenario_id);
+    }
+
+    pub fn get_scenario(env: Env, scenario_id: u32) -> Option<Scenario> {
+        env.storage()
+            .instance()
+            .get(&ScenarioKey::Scenario(scenario_id))
+    }
+
+    pub fn get_achievement(env: Env, player: Address, scenario_id: u32) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&ScenarioKey::Achievement(player, scenario_id))
+    }
+}
@@ -0,0 +1,220 @@
+use soroban_sdk::{
+    contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, BytesN, Env, Symbol,
+};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::{fail, CIRCUIT_ROLE, PHASE_LOBBY, WINNER_CREW, WINNER_IMPOSTOR};
+use crate::{
+    clock, AmongUsContract, AmongUsContractClient, DataKey, GameError, ModuleError, ProofInput,
+};
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum SettlementKey {
+    BondConfig,
+    PendingResult,
+}
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub struct SettlementConfig {
+    pub bond_token: Address,
+    pub bond: i128,
+    pub challenge_window: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+pub struct PendingResult {
+    pub winner: Symbol,
+    pub poster: Address,
+    pub bond: i128,
+    pub posted_at: u64,
+    pub result_hash: BytesN<32>,
+}
+
+impl AmongUsContract {
+    fn read_settlement_config(env: &Env) -> SettlementConfig {
+        env.storage()
+            .instance()
+            .get(&SettlementKey::BondConfig)
+            .unwrap_or_else(|| fail(env, ModuleError::FeatureDisabled))
+    }
+
+    fn read_pending_result(env: &Env) -> PendingResult {
+        env.storage()
+            .instance()
+            .get(&SettlementKey::PendingResult)
+            .unwrap_or_else(|| fail(env, ModuleError::NoPendingResult))
+    }
+
+    pub(crate) fn result_hash(env: &Env, winner: &Symbol) -> BytesN<32> {
+        env.crypto().sha256(&winner.clone().to_xdr(env))
+    }
+
+    // A match decided by play supersedes any posted result, so the poster gets the bond back.
+    pub(crate) fn void_pending_result(env: &Env) {
+        let Some(pending) = env
+            .storage()
+            .instance()
+            .get::<_, PendingResult>(&SettlementKey::PendingResult)
+        else {
+            return;
+        };
+        env.storage()
+            .instance()
+            .remove(&SettlementKey::PendingResult);
+        let config = Self::read_settlement_config(env);
+        Self::escrow_release(env, &pending.poster, &config.bond_token, pending.bond);
+        env.events()
+            .publish((symbol_short!("voided"), pending.poster), pending.winner);
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    pub fn configure_settlement(
+        env: Env,
+        caller: Address,
+        bond_token: Address,
+        bond: i128,
+        challenge_window: u64,
+    ) {
+        Self::require_admin(&env, &caller);
+        // The bond and window are fixed once play starts, so nobody can reshape the
+        // settlement after seeing how the match goes.
+        if env.storage().instance().has(&SettlementKey::PendingResult) {
+            fail(&env, ModuleError::ResultPending);
+        }
+        if Self::read_state(&env).phase != PHASE_LOBBY {
+            fail(&env, GameError::WrongPhase);
+        }
+        if bond <= 0 {
+            fail(&env, GameError::InvalidConfig);
+        }
+        if challenge_window == 0 {
+            fail(&env, GameError::InvalidConfig);
+        }
+        env.storage().instance().set(
+            &SettlementKey::BondConfig,
+            &SettlementConfig {
+                bond_token,
+                bond,
+                challenge_window,
+            },
+        );
+    }
+
+    pub fn post_result(env: Env, caller: Address, winner: Symbol) -> Result<(), GameError> {
+        Self::require_operator(&env, &caller);
+        Self::ensure_not_ended(&env);
+        Self::ensure_not_casual(&env)?;
+        if winner != WINNER_CREW && winner != WINNER_IMPOSTOR {
+            return Err(GameError::InvalidConfig);
+        }
+        if env.storage().instance().has(&SettlementKey::PendingResult) {
+            fail(&env, ModuleError::ResultPending);
+        }
+
+        let config = Self::read_settlement_config(&env);
+        Self::escrow_deposit(&env, &caller, &config.bond_token, config.bond);
+
+        let pending = PendingResult {
+            result_hash: Self::result_hash(&env, &winner),
+            winner: winner.clone(),
+            poster: caller.clone(),
+            bond: config.bond,
+            posted_at: Self::match_time(&env),
+        };
+        env.storage()
+            .instance()
+            .set(&SettlementKey::PendingResult, &pending);
+        env.events()
+            .publish((symbol_short!("posted"), caller), winner);
+        Ok(())
+    }
+
+    pub fn challenge_result(
+        env: Env,
+        challenger: Address,
+        proof: ProofInput,
+    ) -> Result<(), GameError> {
+        challenger.require_auth();
+        Self::ensure_not_casual(&env)?;
+        let pending = Self::read_pending_result(&env);
+        let config = Self::read_settlement_config(&env);
+        if !clock::within_window(
+            pending.posted_at,
+            config.challenge_window,
+            Self::match_time(&env),
+        ) {
+            fail(&env, ModuleError::ChallengeWindowClosed);
+        }
+
+        if env
+            .storage()
+            .instance()
+            .has(&DataKey::UsedNullifier(proof.nullifier.clone()))
+        {
+            return Err(GameError::NullifierUsed);
+        }
+        // A challenge claims the other winner, and the posted result goes in after the
+        // prover's inputs, so a proof backing the posted result, as `claim_payout` takes,
+        // never verifies as a challenge.
+        let claimed = if pending.winner == WINNER_CREW {
+            WINNER_IMPOSTOR
+        } else {
+            WINNER_CREW
+        };
+        if !proof
+            .public_inputs
+            .contains(Self::result_hash(&env, &claimed))
+        {
+            fail(&env, ModuleError::SameResult);
+        }
+
+        let mut public_inputs = proof.public_inputs.clone();
+        public_inputs.push_back(pending.result_hash.clone());
+        public_inputs.push_back(proof.nullifier.clone());
+        Self::consume_nullifier(&env, proof.nullifier);
+        env.storage()
+            .instance()
+            .remove(&SettlementKey::PendingResult);
+        if !Self::verify_circuit(&env, CIRCUIT_ROLE, proof.proof, public_inputs) {
+            return Err(GameError::InvalidProof);
+        }
+
+        Self::escrow_release(&env, &challenger, &config.bond_token, pending.bond);
+        env.events()
+            .publish((symbol_short!("challngd"), challenger), pending.winner);
+        Ok(())
+    }
+
+    pub fn finalize_result(env: Env, caller: Address) -> Result<(), GameError> {
+        caller.require_auth();
+        Self::ensure_not_ended(&env);
+        let pending = Self::read_pending_result(&env);
+        let config = Self::read_settlement_config(&env);
+        if clock::within_window(
+            pending.posted_at,
+            config.challenge_window,
+            Self::match_time(&env),
+        ) {
+            fail(&env, ModuleError::ChallengeWindowOpen);
+        }
+
+        env.storage()
+            .instance()
+            .remove(&SettlementKey::PendingResult);
+        Self::escrow_release(&env, &pending.poster, &config.bond_token, pending.bond);
+        Self::set_winner(&env, pending.winner.clone());
+        env.events()
+            .publish((symbol_short!("winner"), caller), pending.winner);
+        Ok(())
+    }
+
+    pub fn get_pending_result(env: Env) -> Option<PendingResult> {
+        env.storage().instance().get(&SettlementKey::PendingResult)
+    }
+}
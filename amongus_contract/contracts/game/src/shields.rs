@@ -0,0 +1,50 @@
+use soroban_sdk::{contractimpl, contracttype, Address, Env};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::{clock, AmongUsContract, AmongUsContractClient, EmergencyKey, WinKey};
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum ShieldKey {
+    KillShieldSecs,
+}
+
+impl AmongUsContract {
+    fn kill_shield_secs(env: &Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&ShieldKey::KillShieldSecs)
+            .unwrap_or(0)
+    }
+
+    // Match seconds until the shield drops; None while it is off or before the match starts.
+    // It covers every seat after the start and after each meeting, so it reveals no role.
+    pub(crate) fn shield_expires_in(env: &Env) -> Option<u64> {
+        let secs = Self::kill_shield_secs(env);
+        if secs == 0 {
+            return None;
+        }
+        let storage = env.storage().instance();
+        let start: u64 = storage.get(&WinKey::MatchStart)?;
+        let closed: u64 = storage.get(&EmergencyKey::MeetingClosedAt).unwrap_or(0);
+        let expires_at = clock::deadline(start.max(closed), secs);
+        Some(expires_at.saturating_sub(Self::match_time(env)))
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    // Shields every player from kills for `secs` of match time once the match starts and
+    // once each meeting closes; 0 turns it off. A pause holds it, as it does the cooldowns.
+    pub fn configure_kill_shield(env: Env, caller: Address, secs: u64) {
+        Self::require_operator(&env, &caller);
+        env.storage()
+            .instance()
+            .set(&ShieldKey::KillShieldSecs, &secs);
+    }
+
+    pub fn get_kill_shield(env: Env) -> u64 {
+        Self::kill_shield_secs(&env)
+    }
+}
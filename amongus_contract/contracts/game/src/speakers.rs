@@ -0,0 +1,133 @@
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, Bytes, BytesN, Env, Vec};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::{fail, PHASE_MEETING};
+use crate::{AmongUsContract, AmongUsContractClient, GameError, ModuleError};
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum SpeakerKey {
+    StructuredTalk,
+    SpeakerOrder(u32),
+    SpeakerTurn(u32),
+}
+
+impl AmongUsContract {
+    fn structured_talk(env: &Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&SpeakerKey::StructuredTalk)
+            .unwrap_or(false)
+    }
+
+    fn speaker_rank(env: &Env, player_hash: &BytesN<32>) -> BytesN<32> {
+        let mut seed = Bytes::from_array(env, &Self::game_scope(env).to_array());
+        seed.append(&player_hash.clone().into());
+        env.crypto().sha256(&seed)
+    }
+
+    fn speaker_order(env: &Env, meeting_id: u32) -> Vec<BytesN<32>> {
+        env.storage()
+            .instance()
+            .get(&SpeakerKey::SpeakerOrder(meeting_id))
+            .unwrap_or(Vec::new(env))
+    }
+
+    fn speaker_turn(env: &Env, meeting_id: u32) -> u32 {
+        env.storage()
+            .instance()
+            .get(&SpeakerKey::SpeakerTurn(meeting_id))
+            .unwrap_or(0)
+    }
+
+    // The game scope seeds a fixed ranking of the alive players and each meeting starts one
+    // seat further round it, so every client can rebuild the order from public state.
+    pub(crate) fn note_speaker_order(env: &Env, meeting_id: u32) {
+        let mut ranked: Vec<(BytesN<32>, BytesN<32>)> = Vec::new(env);
+        for (_, p) in Self::read_players(env).iter() {
+            if !p.alive {
+                continue;
+            }
+            let rank = Self::speaker_rank(env, &p.player_hash);
+            let at = ranked
+                .iter()
+                .position(|(other, _)| rank < other)
+                .map_or(ranked.len(), |i| i as u32);
+            ranked.insert(at, (rank, p.player_hash));
+        }
+        let mut order = Vec::new(env);
+        let count = ranked.len();
+        for i in 0..count {
+            let (_, player_hash) = ranked.get((meeting_id + i) % count).unwrap();
+            order.push_back(player_hash);
+        }
+        env.storage()
+            .instance()
+            .set(&SpeakerKey::SpeakerOrder(meeting_id), &order);
+    }
+
+    pub(crate) fn clear_speaker_order(env: &Env, meeting_id: u32) {
+        env.storage()
+            .instance()
+            .remove(&SpeakerKey::SpeakerOrder(meeting_id));
+        env.storage()
+            .instance()
+            .remove(&SpeakerKey::SpeakerTurn(meeting_id));
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    // Moderated tournaments hand the floor around with `yield_floor`; the order itself is
+    // recorded for every meeting either way.
+    pub fn configure_structured_talk(env: Env, caller: Address, enabled: bool) {
+        Self::require_operator(&env, &caller);
+        env.storage()
+            .instance()
+            .set(&SpeakerKey::StructuredTalk, &enabled);
+    }
+
+    pub fn yield_floor(env: Env, player: Address) -> Result<(), GameError> {
+        player.require_auth();
+        Self::ensure_not_paused(&env);
+        if !Self::structured_talk(&env) {
+            fail(&env, ModuleError::FeatureDisabled);
+        }
+        let state = Self::read_state(&env);
+        if state.phase != PHASE_MEETING {
+            return Err(GameError::MeetingNotActive);
+        }
+        let player_hash = Self::read_players(&env)
+            .get(player)
+            .ok_or(GameError::PlayerNotFound)?
+            .player_hash;
+        let order = Self::speaker_order(&env, state.round);
+        let turn = Self::speaker_turn(&env, state.round);
+        if order.get(turn) != Some(player_hash.clone()) {
+            fail(&env, ModuleError::NotYourTurn);
+        }
+        env.storage()
+            .instance()
+            .set(&SpeakerKey::SpeakerTurn(state.round), &(turn + 1));
+        env.events().publish(
+            (symbol_short!("floor"), player_hash),
+            (state.round, order.get(turn + 1)),
+        );
+        Ok(())
+    }
+
+    pub fn get_structured_talk(env: Env) -> bool {
+        Self::structured_talk(&env)
+    }
+
+    // Meetings are numbered by the round they open.
+    pub fn get_speaker_order(env: Env, meeting_id: u32) -> Vec<BytesN<32>> {
+        Self::speaker_order(&env, meeting_id)
+    }
+
+    // None once everyone has yielded, or for a meeting that never opened.
+    pub fn get_current_speaker(env: Env, meeting_id: u32) -> Option<BytesN<32>> {
+        Self::speaker_order(&env, meeting_id).get(Self::speaker_turn(&env, meeting_id))
+    }
+}
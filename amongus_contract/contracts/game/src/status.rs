@@ -0,0 +1,81 @@
+use soroban_sdk::{contractimpl, contracttype, Env, Symbol};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::{
+    PHASE_ENDED, PHASE_LOBBY, PHASE_MEETING, PHASE_PLAYING, WINNER_CREW, WINNER_IMPOSTOR,
+    WINNER_VOID,
+};
+use crate::{AmongUsContract, AmongUsContractClient};
+
+// Storage keeps the symbols; these codes are the stable mapping for clients that would
+// rather not decode them. New phases and winners only ever take new numbers.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum MatchPhase {
+    Lobby = 0,
+    Playing = 1,
+    Meeting = 2,
+    Ended = 3,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum MatchWinner {
+    Undecided = 0,
+    Crew = 1,
+    Impostors = 2,
+    Void = 3,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MatchStatus {
+    pub phase: MatchPhase,
+    pub winner: MatchWinner,
+    pub round: u32,
+    pub meeting_active: bool,
+}
+
+impl AmongUsContract {
+    fn phase_code(phase: &Symbol) -> MatchPhase {
+        if *phase == PHASE_PLAYING {
+            MatchPhase::Playing
+        } else if *phase == PHASE_MEETING {
+            MatchPhase::Meeting
+        } else if *phase == PHASE_ENDED {
+            MatchPhase::Ended
+        } else {
+            debug_assert!(*phase == PHASE_LOBBY, "unmapped phase");
+            MatchPhase::Lobby
+        }
+    }
+
+    fn winner_code(winner: &Symbol) -> MatchWinner {
+        if *winner == WINNER_CREW {
+            MatchWinner::Crew
+        } else if *winner == WINNER_IMPOSTOR {
+            MatchWinner::Impostors
+        } else if *winner == WINNER_VOID {
+            MatchWinner::Void
+        } else {
+            MatchWinner::Undecided
+        }
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    // `get_game_state` with the phase and winner as numeric codes.
+    pub fn get_match_status(env: Env) -> MatchStatus {
+        let state = Self::read_state(&env);
+        MatchStatus {
+            phase: Self::phase_code(&state.phase),
+            winner: Self::winner_code(&state.winner),
+            round: state.round,
+            meeting_active: state.meeting_active,
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use soroban_sdk::{contractimpl, contracttype, Env, Symbol};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::{AmongUsContract, AmongUsContractClient};
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum SyncKey {
+    PlayersVersion,
+    StateVersion,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StateDelta {
+    pub players_version: u32,
+    pub state_version: u32,
+    pub round: u32,
+}
+
+impl AmongUsContract {
+    fn sync_version(env: &Env, key: &SyncKey) -> u32 {
+        env.storage().instance().get(key).unwrap_or(0)
+    }
+
+    fn sync_delta(env: &Env, round: u32) -> StateDelta {
+        StateDelta {
+            players_version: Self::sync_version(env, &SyncKey::PlayersVersion),
+            state_version: Self::sync_version(env, &SyncKey::StateVersion),
+            round,
+        }
+    }
+
+    // Clients compare these counters with their last seen delta: a gap means a missed
+    // event, and only the getter behind the counter that moved needs refetching.
+    pub(crate) fn bump_sync(env: &Env, key: SyncKey, round: u32) {
+        let version = Self::sync_version(env, &key).wrapping_add(1);
+        env.storage().instance().set(&key, &version);
+        env.events().publish(
+            (Symbol::new(env, "state_delta"),),
+            Self::sync_delta(env, round),
+        );
+    }
+
+    pub(crate) fn note_players_changed(env: &Env) {
+        Self::bump_sync(env, SyncKey::PlayersVersion, Self::read_state(env).round);
+    }
+
+    pub(crate) fn note_state_changed(env: &Env, round: u32) {
+        Self::bump_sync(env, SyncKey::StateVersion, round);
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    pub fn get_sync_versions(env: Env) -> StateDelta {
+        Self::sync_delta(&env, Self::read_state(&env).round)
+    }
+}
@@ -0,0 +1,117 @@
+use soroban_sdk::{contractimpl, contracttype, Address, BytesN, Env, Map};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::{fail, SKIP_VOTE_TARGET};
+use crate::{clock, AmongUsContract, AmongUsContractClient, GameError, Player};
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum TallyKey {
+    AutoFinalize,
+    SignedTally(u32),
+}
+
+impl AmongUsContract {
+    pub(crate) fn clear_vote_tally(env: &Env, round: u32) {
+        env.storage()
+            .instance()
+            .remove(&TallyKey::SignedTally(round));
+    }
+
+    // The most-voted player hash; a tie at the top, or no player votes at all, is a skip.
+    fn tally_leader(env: &Env, tally: &Map<BytesN<32>, u32>) -> BytesN<32> {
+        let skip = BytesN::from_array(env, &SKIP_VOTE_TARGET);
+        let mut leader = skip.clone();
+        let mut top = 0u32;
+        let mut tied = false;
+        for (target, votes) in tally.iter() {
+            if target == skip {
+                continue;
+            }
+            if votes > top {
+                leader = target;
+                top = votes;
+                tied = false;
+            } else if votes == top {
+                tied = true;
+            }
+        }
+        if tied {
+            skip
+        } else {
+            leader
+        }
+    }
+
+    // Past the voting deadline any seated player may close the meeting. The target then comes
+    // from the stored votes, not the caller; before it, finalizing stays with the operators.
+    // The deadline runs on match time, so a pause does not eat into the vote.
+    pub(crate) fn overdue_tally_leader(env: &Env, caller: &Address) -> BytesN<32> {
+        let state = Self::read_state(env);
+        let deadline = Self::read_config(env).voting_deadline_secs;
+        if deadline == 0
+            || !clock::has_elapsed(state.meeting_started_at, deadline, Self::match_time(env))
+            || !Self::read_players(env).contains_key(caller.clone())
+        {
+            fail(env, GameError::NotAdmin);
+        }
+        let tally = env
+            .storage()
+            .instance()
+            .get(&TallyKey::SignedTally(state.round))
+            .unwrap_or(Map::new(env));
+        // Anonymous matches reject signed votes, so their whole tally sits in the anon one.
+        #[cfg(feature = "anonymous-voting")]
+        let tally = if Self::anonymous_voting(env) {
+            Self::anon_tally(env, state.round)
+        } else {
+            tally
+        };
+        Self::tally_leader(env, &tally)
+    }
+
+    // Signed votes are tallied as they land. With auto-finalize on, the vote that leaves no
+    // alive player without one closes the meeting against the tally leader.
+    pub(crate) fn note_vote_cast(
+        env: &Env,
+        voter: &Address,
+        round: u32,
+        target_hash: &BytesN<32>,
+        players: &Map<Address, Player>,
+    ) -> Result<(), GameError> {
+        let key = TallyKey::SignedTally(round);
+        let mut tally: Map<BytesN<32>, u32> =
+            env.storage().instance().get(&key).unwrap_or(Map::new(env));
+        let votes = tally.get(target_hash.clone()).unwrap_or(0) + 1;
+        tally.set(target_hash.clone(), votes);
+        env.storage().instance().set(&key, &tally);
+
+        let auto = env
+            .storage()
+            .instance()
+            .get(&TallyKey::AutoFinalize)
+            .unwrap_or(false);
+        let no_vote = BytesN::from_array(env, &[0; 32]);
+        if !auto
+            || players
+                .values()
+                .iter()
+                .any(|p| p.alive && p.voted_for_hash == no_vote)
+        {
+            return Ok(());
+        }
+        Self::resolve_meeting(env, voter.clone(), Self::tally_leader(env, &tally), None)
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    // Auto-finalized meetings take no reveal proof, so an ejection waits on `reveal_ejection`.
+    pub fn configure_auto_finalize(env: Env, caller: Address, enabled: bool) {
+        Self::require_operator(&env, &caller);
+        env.storage()
+            .instance()
+            .set(&TallyKey::AutoFinalize, &enabled);
+    }
+}
@@ -0,0 +1,156 @@
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, Env, Map, Symbol};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::{fail, PHASE_PLAYING};
+use crate::{clock, AmongUsContract, AmongUsContractClient, GameError, ModuleError};
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum TaskKey {
+    TaskStart(Address),
+    MinTaskDuration,
+    KindDurations,
+}
+
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+pub struct TaskStart {
+    pub task_id: u32,
+    pub kind: Symbol,
+    pub started_at: u64,
+}
+
+impl AmongUsContract {
+    fn min_task_duration(env: &Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&TaskKey::MinTaskDuration)
+            .unwrap_or(0)
+    }
+
+    fn kind_durations(env: &Env) -> Map<Symbol, u64> {
+        env.storage()
+            .instance()
+            .get(&TaskKey::KindDurations)
+            .unwrap_or(Map::new(env))
+    }
+
+    pub(crate) fn apply_task_duration(env: &Env, min_seconds: u64) {
+        env.storage()
+            .instance()
+            .set(&TaskKey::MinTaskDuration, &min_seconds);
+    }
+
+    // Seconds of match time before the player's open task may be proved; 0 when none is open.
+    pub(crate) fn task_ready_in(env: &Env, player: &Address) -> u64 {
+        let start: Option<TaskStart> = env
+            .storage()
+            .instance()
+            .get(&TaskKey::TaskStart(player.clone()));
+        let Some(start) = start else {
+            return 0;
+        };
+        let required = Self::min_task_duration(env)
+            .max(Self::kind_durations(env).get(start.kind).unwrap_or(0));
+        let ready_at = clock::deadline(start.started_at, required);
+        clock::elapsed(Self::match_time(env), ready_at)
+    }
+
+    pub(crate) fn apply_start_task(env: &Env, player: Address, task_id: u32) {
+        Self::ensure_not_ended(env);
+        Self::ensure_not_paused(env);
+        if Self::read_state(env).phase != PHASE_PLAYING {
+            fail(env, GameError::WrongPhase);
+        }
+
+        let entry = Self::read_players(env)
+            .get(player.clone())
+            .unwrap_or_else(|| fail(env, GameError::PlayerNotFound));
+        if !entry.alive && !Self::read_config(env).ghost_tasks_enabled {
+            fail(env, GameError::PlayerDead);
+        }
+        let map = Self::selected_map(env).unwrap_or_else(|| fail(env, ModuleError::MapNotFound));
+        let console = map
+            .consoles
+            .iter()
+            .find(|c| c.task_id == task_id)
+            .unwrap_or_else(|| fail(env, ModuleError::UnknownTask));
+        if console.position.x != entry.x || console.position.y != entry.y {
+            fail(env, GameError::NotAtConsole);
+        }
+
+        let started_at = Self::match_time(env);
+        env.storage().instance().set(
+            &TaskKey::TaskStart(player.clone()),
+            &TaskStart {
+                task_id,
+                kind: console.kind,
+                started_at,
+            },
+        );
+        env.events()
+            .publish((symbol_short!("task_strt"), player), (task_id, started_at));
+    }
+
+    // Consumes the player's open interaction so each start backs exactly one proof.
+    pub(crate) fn finish_task(env: &Env, player: &Address) {
+        let key = TaskKey::TaskStart(player.clone());
+        let start: Option<TaskStart> = env.storage().instance().get(&key);
+        let min_duration = Self::min_task_duration(env);
+        let kind_durations = Self::kind_durations(env);
+        match start {
+            Some(start) => {
+                let required =
+                    min_duration.max(kind_durations.get(start.kind.clone()).unwrap_or(0));
+                let elapsed = clock::elapsed(start.started_at, Self::match_time(env));
+                if elapsed < required {
+                    fail(env, GameError::TaskTooFast);
+                }
+                env.storage().instance().remove(&key);
+                env.events().publish(
+                    (symbol_short!("task_done"), player.clone()),
+                    (start.task_id, start.kind, elapsed),
+                );
+            }
+            None if min_duration > 0 || !kind_durations.is_empty() => {
+                fail(env, ModuleError::TaskNotStarted)
+            }
+            None => {}
+        }
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    pub fn configure_task_duration(env: Env, caller: Address, min_seconds: u64) {
+        Self::require_operator(&env, &caller);
+        Self::apply_task_duration(&env, min_seconds);
+    }
+
+    pub fn configure_task_kind_duration(env: Env, caller: Address, kind: Symbol, min_seconds: u64) {
+        Self::require_operator(&env, &caller);
+        let mut durations = Self::kind_durations(&env);
+        if min_seconds == 0 {
+            durations.remove(kind);
+        } else {
+            durations.set(kind, min_seconds);
+        }
+        env.storage()
+            .instance()
+            .set(&TaskKey::KindDurations, &durations);
+    }
+
+    pub fn get_task_kind_durations(env: Env) -> Map<Symbol, u64> {
+        Self::kind_durations(&env)
+    }
+
+    pub fn start_task(env: Env, player: Address, task_id: u32) {
+        player.require_auth();
+        Self::apply_start_task(&env, player, task_id);
+    }
+
+    pub fn get_task_start(env: Env, player: Address) -> Option<TaskStart> {
+        env.storage().instance().get(&TaskKey::TaskStart(player))
+    }
+}
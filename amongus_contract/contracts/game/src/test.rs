@@ -1,8 +1,18 @@
 #![cfg(test)]
+extern crate std;
 
 use super::*;
+use crate::constants::COLOR_PALETTE;
 use soroban_sdk::{
-    contract, contractimpl, symbol_short, testutils::Address as _, Address, BytesN, Env, Vec,
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, Events as _, Ledger},
+    token, Address, BytesN, Env, TryFromVal, Vec,
+};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::string::String;
+use crate::test_verifiers::{
+    AcceptingVerifier, CrewVerifier, CrewVerifierClient, FailingVerifier, GarbageVerifier,
+    RejectingVerifier,
 };
 
 #[contract]
@@ -10,12 +20,12 @@ pub struct MockVerifier;
 
 #[contractimpl]
 impl MockVerifier {
-    pub fn verify(_env: Env, proof_hash: BytesN<32>, _public_inputs: Vec<BytesN<32>>) -> bool {
-        proof_hash != BytesN::from_array(&_env, &[0; 32])
+    pub fn verify(_env: Env, proof: Bytes, _public_inputs: Vec<BytesN<32>>) -> bool {
+        proof != Bytes::from_array(&_env, &[0; 32])
     }
 }
 
-fn join_four_players(env: &Env, client: &AmongUsContractClient<'_>) -> Vec<Address> {
+pub(crate) fn join_four_players(env: &Env, client: &AmongUsContractClient<'_>) -> Vec<Address> {
     let p1 = Address::generate(env);
     let p2 = Address::generate(env);
     let p3 = Address::generate(env);
@@ -58,7 +68,55 @@ fn join_four_players(env: &Env, client: &AmongUsContractClient<'_>) -> Vec<Addre
     out
 }
 
+pub(crate) fn lobby_with_verifier<'a>(
+    env: &Env,
+) -> (AmongUsContractClient<'a>, Address, Address, Vec<Address>) {
+    let verifier_id = env.register_contract(None, MockVerifier);
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    client.init(&admin, &1);
+    client.set_verifier(&admin, &verifier_id);
+    let players = join_four_players(env, &client);
+    (client, contract_id, admin, players)
+}
+
+pub(crate) fn start_game_with_verifier<'a>(
+    env: &Env,
+) -> (AmongUsContractClient<'a>, Address, Address, Vec<Address>) {
+    let (client, contract_id, admin, players) = lobby_with_verifier(env);
+    client.start_game(&admin);
+    (client, contract_id, admin, players)
+}
+
+pub(crate) fn panic_message(payload: &std::boxed::Box<dyn core::any::Any + Send>) -> String {
+    payload
+        .downcast_ref::<String>()
+        .cloned()
+        .or_else(|| payload.downcast_ref::<&str>().map(|s| String::from(*s)))
+        .unwrap_or_default()
+}
+
+// Coded panics surface in the host's panic message as `Error(Contract, #code)`.
+pub(crate) fn panicked_with(
+    payload: &std::boxed::Box<dyn core::any::Any + Send>,
+    error: impl Into<soroban_sdk::Error>,
+) -> bool {
+    let code = error.into().get_code();
+    panic_message(payload).contains(&std::format!("Error(Contract, #{})", code))
+}
+
+pub(crate) fn proof_input(env: &Env, nullifier: u8) -> ProofInput {
+    ProofInput {
+        proof: Bytes::from_array(env, &[8; 32]),
+        nullifier: BytesN::from_array(env, &[nullifier; 32]),
+        public_inputs: Vec::new(env),
+    }
+}
+
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn join_and_move_player() {
     let env = Env::default();
     env.mock_all_auths();
@@ -82,6 +140,93 @@ fn join_and_move_player() {
     assert_eq!(stored.alive, true);
 }
 
+#[test]
+fn operator_runs_matches_and_rotates_without_the_cold_key() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let cold = Address::generate(&env);
+    let hot = Address::generate(&env);
+    let next_hot = Address::generate(&env);
+    client.init(&cold, &1);
+    client.set_operator(&cold, &hot);
+
+    join_four_players(&env, &client);
+    client.start_game(&hot);
+    client.set_operator(&hot, &next_hot);
+    client.pause_match(&next_hot);
+    assert_eq!(client.get_operator(), Some(next_hot.clone()));
+
+    let new_cold = Address::generate(&env);
+    client.set_admin(&cold, &new_cold);
+    client.revoke_operator(&new_cold);
+    assert_eq!((client.get_admin(), client.get_operator()), (Some(new_cold), None));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn operator_cannot_swap_verifier() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let cold = Address::generate(&env);
+    let hot = Address::generate(&env);
+    client.init(&cold, &1);
+    client.set_operator(&cold, &hot);
+    env.as_contract(&contract_id, || {
+        AmongUsContract::set_verifier(env.clone(), hot.clone(), hot.clone())
+    });
+}
+
+#[test]
+fn init_retry_is_a_no_op_but_takeover_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.init(&admin, &1);
+    client.configure_game(&admin, &8, &12);
+    client.init(&admin, &1);
+    assert_eq!(client.get_config().max_players, 8);
+
+    let squatter = Address::generate(&env);
+    assert_eq!(client.try_init(&squatter, &1), Err(Ok(GameError::AlreadyInitialized)));
+}
+
+#[test]
+fn state_delta_versions_track_player_and_state_writes() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.init(&admin, &1);
+    let players = join_four_players(&env, &client);
+    let lobby = client.get_sync_versions();
+    assert_eq!((lobby.players_version, lobby.state_version), (5, 1));
+
+    client.start_game(&admin);
+    client.submit_move(&players.get(0).unwrap(), &3, &4);
+    let delta_topic = Symbol::new(&env, "state_delta");
+    let (_, _, data) = env
+        .events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| {
+            Symbol::try_from_val(&env, &topics.get(0).unwrap()).ok() == Some(delta_topic.clone())
+        })
+        .last()
+        .unwrap();
+    let emitted: StateDelta = data.into_val(&env);
+    let playing = client.get_sync_versions();
+    assert_eq!(emitted, playing);
+    assert_eq!((playing.players_version, playing.state_version), (6, 2));
+    assert_eq!(playing.round, 1);
+}
+
 #[test]
 fn submit_vote_with_verifier() {
     let env = Env::default();
@@ -103,7 +248,7 @@ fn submit_vote_with_verifier() {
 
     let vote = VoteInput {
         target_hash: BytesN::from_array(&env, &[4; 32]),
-        proof_hash: BytesN::from_array(&env, &[8; 32]),
+        proof: Bytes::from_array(&env, &[8; 32]),
         nullifier: BytesN::from_array(&env, &[5; 32]),
     };
     client.submit_vote(&voter, &vote);
@@ -114,6 +259,7 @@ fn submit_vote_with_verifier() {
 }
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn finalize_meeting_ejects_majority_target() {
     let env = Env::default();
     env.mock_all_auths();
@@ -141,7 +287,7 @@ fn finalize_meeting_ejects_majority_target() {
         &p1,
         &VoteInput {
             target_hash: target_hash.clone(),
-            proof_hash: BytesN::from_array(&env, &[8; 32]),
+            proof: Bytes::from_array(&env, &[8; 32]),
             nullifier: BytesN::from_array(&env, &[51; 32]),
         },
     );
@@ -149,7 +295,7 @@ fn finalize_meeting_ejects_majority_target() {
         &p3,
         &VoteInput {
             target_hash: target_hash.clone(),
-            proof_hash: BytesN::from_array(&env, &[9; 32]),
+            proof: Bytes::from_array(&env, &[9; 32]),
             nullifier: BytesN::from_array(&env, &[52; 32]),
         },
     );
@@ -157,15 +303,3921 @@ fn finalize_meeting_ejects_majority_target() {
         &p4,
         &VoteInput {
             target_hash: target_hash.clone(),
-            proof_hash: BytesN::from_array(&env, &[10; 32]),
+            proof: Bytes::from_array(&env, &[10; 32]),
             nullifier: BytesN::from_array(&env, &[53; 32]),
         },
     );
 
-    client.finalize_meeting(&admin, &target_hash);
+    client.finalize_meeting(&admin, &target_hash, &None);
 
     let all_players = client.get_players();
     let ejected = all_players.get(p2).unwrap();
     assert_eq!(ejected.alive, false);
 }
 
+#[test]
+fn revealed_ejection_of_last_impostor_wins_for_crew() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, players) = start_game_with_verifier(&env);
+    client.start_meeting(&players.get(0).unwrap());
+    let target_hash = BytesN::from_array(&env, &[22; 32]);
+    for (i, voter) in [0u32, 2, 3].iter().enumerate() {
+        client.submit_vote(
+            &players.get(*voter).unwrap(),
+            &VoteInput {
+                target_hash: target_hash.clone(),
+                proof: Bytes::from_array(&env, &[8; 32]),
+                nullifier: BytesN::from_array(&env, &[60 + i as u8; 32]),
+            },
+        );
+    }
+
+    let mut forged = proof_input(&env, 70);
+    forged.proof = Bytes::from_array(&env, &[0; 32]);
+    let rejected = client.try_finalize_meeting(&admin, &target_hash, &Some(forged));
+    assert_eq!(rejected, Err(Ok(GameError::InvalidProof)));
+    assert!(client.get_players().get(players.get(1).unwrap()).unwrap().alive);
+
+    client.finalize_meeting(&admin, &target_hash, &Some(proof_input(&env, 70)));
+    let state = client.get_game_state();
+    assert_eq!((state.phase, state.winner), (symbol_short!("ended"), symbol_short!("crew")));
+}
+
+fn start_two_impostor_game<'a>(env: &Env) -> (AmongUsContractClient<'a>, Address, Vec<Address>) {
+    let client = AmongUsContractClient::new(env, &env.register_contract(None, AmongUsContract));
+    let admin = Address::generate(env);
+    client.init(&admin, &2);
+    client.set_verifier(&admin, &env.register_contract(None, MockVerifier));
+    let mut players = Vec::new(env);
+    for hash in 1..=6u8 {
+        let player = Address::generate(env);
+        join_as(env, &client, &player, hash);
+        players.push_back(player);
+    }
+    client.start_game(&admin);
+    (client, admin, players)
+}
+
+#[test]
+fn match_status_reports_numeric_phase_and_winner() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.init(&admin, &1);
+    let status = client.get_match_status();
+    assert_eq!((status.phase, status.winner), (MatchPhase::Lobby, MatchWinner::Undecided));
+
+    let players = join_four_players(&env, &client);
+    client.start_game(&admin);
+    client.start_meeting(&players.get(0).unwrap());
+    let status = client.get_match_status();
+    assert_eq!((status.phase, status.round, status.meeting_active), (MatchPhase::Meeting, 2, true));
+
+    client.end_game_admin(&admin, &symbol_short!("impost"));
+    let status = client.get_match_status();
+    assert_eq!((status.phase, status.winner), (MatchPhase::Ended, MatchWinner::Impostors));
+}
+
+#[test]
+fn game_result_interface_reads_winner_participants_and_report_hash() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, players) = start_game_with_verifier(&env);
+    let result = GameResultClient::new(&env, &contract_id);
+    assert_eq!((result.winner(), result.final_report_hash()), (None, None));
+    assert_eq!(result.participants().len(), players.len());
+
+    client.end_game_admin(&admin, &symbol_short!("crew"));
+    assert_eq!(result.winner(), Some(symbol_short!("crew")));
+    let report = client.get_match_report().unwrap();
+    assert_eq!(
+        result.final_report_hash(),
+        Some(env.crypto().sha256(&soroban_sdk::xdr::ToXdr::to_xdr(report, &env)))
+    );
+}
+
+#[test]
+fn ledger_clock_reads_the_ledger_timestamp() {
+    use game_core::TimeSource;
+    let env = Env::default();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+    assert_eq!(LedgerClock(&env).now(), 1_000);
+}
+
+#[contract]
+pub struct BlocklistPolicy;
+
+#[contractimpl]
+impl BlocklistPolicy {
+    pub fn allow_name(_env: Env, _player: Address, name: Symbol) -> bool {
+        name != symbol_short!("sus_word")
+    }
+}
+
+#[test]
+fn name_policy_contract_vets_display_names_at_join() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.init(&admin, &1);
+    let policy = env.register_contract(None, BlocklistPolicy);
+    client.configure_name_policy(&admin, &Some(policy.clone()));
+    assert_eq!(client.get_name_policy(), Some(policy));
+    join_four_players(&env, &client);
+
+    let rejected = catch_unwind(AssertUnwindSafe(|| {
+        env.as_contract(&contract_id, || {
+            AmongUsContract::join_game(
+                env.clone(),
+                Address::generate(&env),
+                symbol_short!("Pur"),
+                symbol_short!("sus_word"),
+                BytesN::from_array(&env, &[55; 32]),
+                BytesN::from_array(&env, &[5; 32]),
+            )
+        })
+    }));
+    assert!(panicked_with(&rejected.err().unwrap(), ModuleError::NameRejected));
+}
+
+fn tutorial(env: &Env) -> Scenario {
+    Scenario {
+        role: symbol_short!("crew"),
+        spawn: Point { x: 0, y: 0 },
+        bodies: vec![env, Point { x: 3, y: 4 }],
+        goal: Point { x: 6, y: 8 },
+        max_step: 5,
+        max_moves: 4,
+    }
+}
+
+#[test]
+fn completing_a_scenario_awards_its_achievement() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 500);
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.init(&admin, &1);
+    client.load_scenario(&admin, &1, &tutorial(&env));
+    let player = Address::generate(&env);
+
+    let moves = vec![&env, Point { x: 3, y: 4 }, Point { x: 6, y: 8 }];
+    client.complete_scenario(&player, &1, &moves);
+    assert_eq!(client.get_achievement(&player, &1), Some(500));
+    assert_eq!(client.get_achievement(&admin, &1), None);
+}
+
+#[test]
+fn scenario_moves_must_reach_every_body_in_legal_steps() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.init(&admin, &1);
+    client.load_scenario(&admin, &1, &tutorial(&env));
+
+    // Skipping the body reaches the goal in one oversized step.
+    let shortcut = catch_unwind(AssertUnwindSafe(|| {
+        env.as_contract(&contract_id, || {
+            AmongUsContract::complete_scenario(
+                env.clone(),
+                Address::generate(&env),
+                1,
+                vec![&env, Point { x: 6, y: 8 }],
+            )
+        })
+    }));
+    assert!(panicked_with(&shortcut.err().unwrap(), ModuleError::ObjectiveNotMet));
+}
+
+#[test]
+fn kills_beyond_kill_range_are_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, players) = start_game_with_verifier(&env);
+    client.configure_kill_range(&admin, &5);
+    let (killer, victim) = (players.get(0).unwrap(), players.get(1).unwrap());
+    client.submit_move(&killer, &10, &10);
+    client.submit_move(&victim, &16, &10);
+    let far = client.try_submit_kill_proof(&killer, &victim, &proof_input(&env, 90), &0);
+    assert_eq!(far, Err(Ok(GameError::OutOfKillRange)));
+
+    // Exactly on the range boundary counts as in range.
+    client.submit_move(&victim, &13, &14);
+    client.submit_kill_proof(&killer, &victim, &proof_input(&env, 90), &0);
+    assert!(!client.get_players().get(victim).unwrap().alive);
+}
+
+fn killed_at_ten<'a>(env: &Env) -> (AmongUsContractClient<'a>, Address, Address, Vec<Address>) {
+    let (client, contract_id, admin, players) = start_game_with_verifier(env);
+    client.configure_report_range(&admin, &5);
+    let (killer, victim) = (players.get(0).unwrap(), players.get(1).unwrap());
+    client.submit_move(&killer, &10, &10);
+    client.submit_move(&victim, &10, &10);
+    client.submit_kill_proof(&killer, &victim, &proof_input(env, 90), &0);
+    (client, contract_id, admin, players)
+}
+
+#[test]
+fn reported_body_opens_a_meeting_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, players) = killed_at_ten(&env);
+    let (reporter, victim) = (players.get(2).unwrap(), players.get(1).unwrap());
+    client.submit_move(&reporter, &13, &14);
+    client.report_body(&reporter, &victim);
+    assert_eq!(client.get_game_state().phase, symbol_short!("meeting"));
+
+    client.end_meeting(&admin);
+    let again = catch_unwind(AssertUnwindSafe(|| {
+        env.as_contract(&contract_id, || {
+            AmongUsContract::report_body(env.clone(), reporter.clone(), victim.clone())
+        })
+    }));
+    assert!(panicked_with(&again.err().unwrap(), ModuleError::NoBody));
+}
+
+#[test]
+fn emergency_meetings_respect_the_per_player_cap_and_cooldown() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, players) = start_game_with_verifier(&env);
+    client.configure_emergency_meetings(&admin, &1, &25);
+    let caller = players.get(2).unwrap();
+    client.start_meeting(&caller);
+    client.end_meeting(&admin);
+    let (killer, victim) = (players.get(0).unwrap(), players.get(1).unwrap());
+    client.submit_kill_proof(&killer, &victim, &proof_input(&env, 90), &0);
+    // A report opens a meeting without locking the button.
+    client.report_body(&players.get(3).unwrap(), &victim);
+    client.end_meeting(&admin);
+
+    let early = client.try_start_meeting(&players.get(3).unwrap());
+    assert_eq!(early, Err(Ok(GameError::MeetingCooldown)));
+    assert_eq!(client.get_meeting_cooldown(), 25);
+    client.pause_match(&admin);
+    env.ledger().with_mut(|l| l.timestamp += 60);
+    client.resume_match(&admin);
+    assert_eq!(client.get_meeting_cooldown(), 25);
+    env.ledger().with_mut(|l| l.timestamp += 25);
+    assert_eq!(client.get_meeting_cooldown(), 0);
+    assert_eq!(client.try_start_meeting(&caller), Err(Ok(GameError::ButtonUsed)));
+    client.start_meeting(&players.get(3).unwrap());
+}
+
+#[test]
+fn bodies_out_of_report_range_cannot_be_reported() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, _, players) = killed_at_ten(&env);
+    let (reporter, victim) = (players.get(2).unwrap(), players.get(1).unwrap());
+    client.submit_move(&reporter, &16, &10);
+    let far = catch_unwind(AssertUnwindSafe(|| {
+        env.as_contract(&contract_id, || {
+            AmongUsContract::report_body(env.clone(), reporter.clone(), victim.clone())
+        })
+    }));
+    assert!(panicked_with(&far.err().unwrap(), ModuleError::OutOfReach));
+}
+
+#[test]
+fn kills_wait_out_the_match_time_cooldown() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, players) = start_game_with_verifier(&env);
+    client.configure_kill_cooldown(&admin, &30);
+    let killer = players.get(0).unwrap();
+    client.submit_kill_proof(&killer, &players.get(1).unwrap(), &proof_input(&env, 90), &0);
+
+    env.ledger().with_mut(|l| l.timestamp += 10);
+    let victim = players.get(2).unwrap();
+    let early = client.try_submit_kill_proof(&killer, &victim, &proof_input(&env, 91), &0);
+    assert_eq!(early, Err(Ok(GameError::KillCooldown)));
+    assert_eq!(client.get_player_timers(&admin, &killer, &None).kill_ready_in_secs, Some(20));
+    assert_eq!(client.get_kill_cooldown(&admin, &killer, &None), 20);
+
+    // A pause holds the cooldown.
+    client.pause_match(&admin);
+    env.ledger().with_mut(|l| l.timestamp += 100);
+    client.resume_match(&admin);
+    assert_eq!(client.get_kill_cooldown(&admin, &killer, &None), 20);
+
+    env.ledger().with_mut(|l| l.timestamp += 20);
+    assert_eq!(client.get_player_timers(&admin, &killer, &None).kill_ready_in_secs, Some(0));
+    client.submit_kill_proof(&killer, &victim, &proof_input(&env, 91), &0);
+    assert!(!client.get_players().get(victim).unwrap().alive);
+}
+
+#[test]
+fn victim_idle_window_rejects_kills_on_a_victim_who_just_left_reach() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, players) = start_game_with_verifier(&env);
+    client.configure_kill_range(&admin, &5);
+    client.configure_victim_idle_window(&admin, &10);
+    let (killer, victim) = (players.get(0).unwrap(), players.get(1).unwrap());
+    client.submit_move(&killer, &10, &10);
+    client.submit_move(&victim, &30, &10);
+    // The victim walks into reach, but came from out of range inside the window.
+    client.submit_move(&victim, &12, &10);
+    let early = client.try_submit_kill_proof(&killer, &victim, &proof_input(&env, 90), &0);
+    assert_eq!(early, Err(Ok(GameError::VictimMoved)));
+
+    env.ledger().with_mut(|l| l.timestamp += 10);
+    client.submit_kill_proof(&killer, &victim, &proof_input(&env, 90), &0);
+    assert!(!client.get_players().get(victim).unwrap().alive);
+}
+
+#[test]
+fn multi_call_applies_bundled_actions_all_or_nothing() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, players) = start_game_with_verifier(&env);
+    let player = players.get(1).unwrap();
+    client.submit_task_proof(&player, &proof_input(&env, 5));
+
+    let stale = vec![
+        &env,
+        PlayerAction::Move(7, 7),
+        PlayerAction::SubmitTask(proof_input(&env, 5)),
+    ];
+    assert_eq!(client.try_multi_call(&player, &stale), Err(Ok(GameError::NullifierUsed)));
+    assert_eq!(client.get_players().get(player.clone()).unwrap().x, 0);
+
+    let actions = vec![&env, PlayerAction::Move(7, 7), PlayerAction::StartMeeting];
+    let receipts = client.multi_call(&player, &actions);
+    assert_eq!(receipts.len(), 2);
+    assert_eq!(receipts.get(1).unwrap().action_seq, receipts.get(0).unwrap().action_seq + 1);
+    assert_eq!(client.get_game_state().phase, symbol_short!("meeting"));
+}
+
+#[test]
+fn multi_call_moves_to_a_body_and_reports_it() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, players) = killed_at_ten(&env);
+    let (reporter, victim) = (players.get(2).unwrap(), players.get(1).unwrap());
+    let actions = vec![&env, PlayerAction::Move(13, 14), PlayerAction::ReportBody(victim)];
+    assert_eq!(client.multi_call(&reporter, &actions).len(), 2);
+    assert_eq!(client.get_game_state().phase, symbol_short!("meeting"));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #57)")]
+fn multi_call_rejects_an_empty_bundle() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (_, contract_id, _, players) = start_game_with_verifier(&env);
+    let _ = env.as_contract(&contract_id, || {
+        AmongUsContract::multi_call(env.clone(), players.get(0).unwrap(), Vec::new(&env))
+    });
+}
+
+#[test]
+fn call_stats_count_successful_calls_by_kind() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, players) = start_game_with_verifier(&env);
+    let player = players.get(0).unwrap();
+    client.submit_move(&player, &1, &1);
+    client.submit_move(&player, &2, &2);
+    client.submit_task_proof(&player, &proof_input(&env, 5));
+    // A rejected call reverts its count too.
+    assert!(client.try_submit_task_proof(&player, &proof_input(&env, 5)).is_err());
+
+    let stats = client.get_call_stats();
+    assert_eq!(stats.get(symbol_short!("join")), Some(4));
+    assert_eq!(stats.get(symbol_short!("move")), Some(2));
+    assert_eq!(stats.get(symbol_short!("task")), Some(1));
+    assert_eq!(stats.get(symbol_short!("vote")), None);
+}
+
+#[test]
+fn late_joiner_takes_a_seat_dropped_inside_the_grace_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let verifier_id = env.register_contract(None, MockVerifier);
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.init(&admin, &1);
+    client.set_verifier(&admin, &verifier_id);
+    let players = join_four_players(&env, &client);
+    client.configure_late_join(&admin, &60, &BytesN::from_array(&env, &[9; 32]));
+    client.start_game(&admin);
+
+    client.leave_game(&players.get(3).unwrap(), &None);
+    assert_eq!(sides_alive(&env, &contract_id), (1, 2));
+
+    let late = Address::generate(&env);
+    let hash = BytesN::from_array(&env, &[55; 32]);
+    client.late_join(&late, &symbol_short!("Pnk"), &symbol_short!("P5"), &hash);
+    let seat = client.get_players().get(late).unwrap();
+    assert_eq!(seat.role_hash, BytesN::from_array(&env, &[9; 32]));
+    assert_eq!(sides_alive(&env, &contract_id), (1, 3));
+
+    // The reserve leaf is dealt once; later departures fall back to the in-match rule.
+    let leave = client.try_leave_game(&players.get(2).unwrap(), &None);
+    assert_eq!(leave, Err(Ok(GameError::WrongPhase)));
+}
+
+fn start_late_join_game<'a>(
+    env: &Env,
+    impostors: u32,
+) -> (AmongUsContractClient<'a>, Address, Address, Vec<Address>) {
+    let verifier_id = env.register_contract(None, MockVerifier);
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.init(&admin, &impostors);
+    client.set_verifier(&admin, &verifier_id);
+    let mut players = join_four_players(env, &client);
+    for (i, color) in [symbol_short!("Blk"), symbol_short!("Wht")].iter().enumerate() {
+        if impostors < 2 {
+            break;
+        }
+        let player = Address::generate(env);
+        let seat = 5 + i as u8;
+        client.join_game(
+            &player,
+            color,
+            &symbol_short!("P"),
+            &BytesN::from_array(env, &[seat * 11; 32]),
+            &BytesN::from_array(env, &[seat; 32]),
+        );
+        players.push_back(player);
+    }
+    client.configure_late_join(&admin, &60, &BytesN::from_array(env, &[9; 32]));
+    client.start_game(&admin);
+    (client, contract_id, admin, players)
+}
+
+fn sides_alive(env: &Env, contract_id: &Address) -> (u32, u32) {
+    env.as_contract(contract_id, || {
+        (AmongUsContract::impostors_alive(env), AmongUsContract::crew_alive(env))
+    })
+}
+
+#[test]
+fn impostor_leaving_mid_match_costs_the_impostors_not_the_crew() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, _, players) = start_late_join_game(&env, 1);
+    assert_eq!(sides_alive(&env, &contract_id), (1, 3));
+    client.leave_game(&players.get(3).unwrap(), &Some(proof_input(&env, 1)));
+    // The only impostor revealed on the way out, so the crew has won.
+    assert_eq!(sides_alive(&env, &contract_id), (0, 3));
+    assert_eq!(client.get_game_state().winner, symbol_short!("crew"));
+}
+
+fn eject_by_vote(
+    env: &Env,
+    client: &AmongUsContractClient,
+    admin: &Address,
+    voters: &[Address],
+    target_hash: &BytesN<32>,
+    nullifier: u8,
+    reveal: &Option<ProofInput>,
+) {
+    for (i, voter) in voters.iter().enumerate() {
+        client.submit_vote(
+            voter,
+            &VoteInput {
+                target_hash: target_hash.clone(),
+                proof: Bytes::from_array(env, &[8; 32]),
+                nullifier: BytesN::from_array(env, &[nullifier + i as u8; 32]),
+            },
+        );
+    }
+    client.finalize_meeting(admin, target_hash, reveal);
+}
+
+#[test]
+fn unrevealed_ejection_holds_parity_until_revealed_or_expired() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, players) = start_late_join_game(&env, 2);
+    let victim = players.get(1).unwrap();
+    client.submit_kill_proof(&players.get(0).unwrap(), &victim, &proof_input(&env, 1), &0);
+    assert_eq!(sides_alive(&env, &contract_id), (2, 3));
+    let voters = [players.get(0).unwrap(), players.get(2).unwrap(), players.get(3).unwrap()];
+
+    // Counting the ejected impostor as crew would make it 2 against 2 and end the match.
+    client.report_body(&voters[0], &victim);
+    let first = BytesN::from_array(&env, &[66; 32]);
+    eject_by_vote(&env, &client, &admin, &voters, &first, 10, &None);
+    assert_eq!(sides_alive(&env, &contract_id), (2, 3));
+    assert_eq!(client.get_game_state().phase, symbol_short!("playing"));
+    client.reveal_ejection(&proof_input(&env, 20));
+    assert_eq!(sides_alive(&env, &contract_id), (1, 3));
+
+    // Nobody reveals this one, so once the window runs out it costs the crew.
+    client.start_meeting(&voters[0]);
+    let second = BytesN::from_array(&env, &[55; 32]);
+    eject_by_vote(&env, &client, &admin, &voters, &second, 30, &None);
+    assert_eq!(sides_alive(&env, &contract_id), (1, 3));
+    env.ledger().with_mut(|l| l.timestamp += 61);
+    client.check_win_conditions();
+    assert_eq!(sides_alive(&env, &contract_id), (1, 2));
+    assert_eq!(client.get_game_state().phase, symbol_short!("playing"));
+    let late = catch_unwind(AssertUnwindSafe(|| {
+        env.as_contract(&contract_id, || {
+            AmongUsContract::reveal_ejection(env.clone(), proof_input(&env, 21))
+        })
+    }));
+    assert!(panicked_with(&late.err().unwrap(), ModuleError::NothingToReveal));
+}
+
+#[test]
+fn refilled_impostor_seat_restores_the_impostor_count() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, players) = start_late_join_game(&env, 2);
+    client.kick_player(&admin, &players.get(3).unwrap(), &Some(proof_input(&env, 1)));
+    assert_eq!(sides_alive(&env, &contract_id), (1, 4));
+    let late = Address::generate(&env);
+    let hash = BytesN::from_array(&env, &[77; 32]);
+    client.late_join(&late, &symbol_short!("Pnk"), &symbol_short!("P7"), &hash);
+    assert_eq!(sides_alive(&env, &contract_id), (2, 4));
+    assert_eq!(client.get_game_state().phase, symbol_short!("playing"));
+}
+
+#[test]
+fn two_impostors_win_once_they_match_the_crew() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, players) = start_two_impostor_game(&env);
+    let killer = players.get(0).unwrap();
+    client.submit_kill_proof(&killer, &players.get(2).unwrap(), &proof_input(&env, 1), &0);
+    assert_eq!(client.get_game_state().phase, symbol_short!("playing"));
+
+    // Four players are still alive, but two of them are impostors facing two crew.
+    client.submit_kill_proof(&killer, &players.get(3).unwrap(), &proof_input(&env, 2), &0);
+    let state = client.get_game_state();
+    assert_eq!((state.phase, state.winner), (symbol_short!("ended"), symbol_short!("impost")));
+}
+
+#[test]
+fn skip_plurality_keeps_the_target_on_board() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, players) = start_game_with_verifier(&env);
+    client.start_meeting(&players.get(0).unwrap());
+    let skip = BytesN::from_array(&env, &[0xff; 32]);
+    let target = BytesN::from_array(&env, &[44; 32]);
+    for (i, choice) in [(0u32, &skip), (1, &skip), (2, &target)] {
+        client.submit_vote(
+            &players.get(i).unwrap(),
+            &VoteInput {
+                target_hash: choice.clone(),
+                proof: Bytes::from_array(&env, &[8; 32]),
+                nullifier: BytesN::from_array(&env, &[40 + i as u8; 32]),
+            },
+        );
+    }
+    client.finalize_meeting(&admin, &target, &None);
+    assert!(client.get_players().get(players.get(3).unwrap()).unwrap().alive);
+
+    let skipped = symbol_short!("skipped").to_val();
+    let (_, _, data) = env
+        .events()
+        .all()
+        .iter()
+        .find(|(_, topics, _)| topics.get(0).unwrap().shallow_eq(&skipped))
+        .unwrap();
+    let (kept, skips): (BytesN<32>, u32) = data.into_val(&env);
+    assert_eq!((kept, skips), (target, 2));
+}
+
+#[test]
+fn last_vote_finalizes_the_meeting_when_auto_finalize_is_on() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, players) = start_game_with_verifier(&env);
+    client.configure_auto_finalize(&admin, &true);
+    client.start_meeting(&players.get(0).unwrap());
+    let target = BytesN::from_array(&env, &[44; 32]);
+    for (i, choice) in [(0u32, 44u8), (1, 44), (2, 44), (3, 11)] {
+        assert_eq!(client.get_game_state().phase, symbol_short!("meeting"));
+        client.submit_vote(
+            &players.get(i).unwrap(),
+            &VoteInput {
+                target_hash: BytesN::from_array(&env, &[choice; 32]),
+                proof: Bytes::from_array(&env, &[8; 32]),
+                nullifier: BytesN::from_array(&env, &[40 + i as u8; 32]),
+            },
+        );
+    }
+
+    assert_eq!(client.get_game_state().phase, symbol_short!("playing"));
+    let ejected = client.get_players().get(players.get(3).unwrap()).unwrap();
+    assert!(!ejected.alive && ejected.player_hash == target);
+}
+
+fn overdue_meeting<'a>(
+    env: &Env,
+) -> (AmongUsContractClient<'a>, Address, Address, Vec<Address>) {
+    let (client, contract_id, admin, players) = start_game_with_verifier(env);
+    client.configure_voting_deadline(&admin, &50);
+    client.start_meeting(&players.get(0).unwrap());
+    for i in 0..3u32 {
+        client.submit_vote(
+            &players.get(i).unwrap(),
+            &VoteInput {
+                target_hash: BytesN::from_array(env, &[44; 32]),
+                proof: Bytes::from_array(env, &[8; 32]),
+                nullifier: BytesN::from_array(env, &[40 + i as u8; 32]),
+            },
+        );
+    }
+    (client, contract_id, admin, players)
+}
+
+#[test]
+fn players_cannot_finalize_before_the_voting_deadline() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, players) = overdue_meeting(&env);
+    env.ledger().with_mut(|l| l.timestamp += 20);
+    // Time spent paused does not count toward the deadline.
+    client.pause_match(&admin);
+    env.ledger().with_mut(|l| l.timestamp += 100);
+    client.resume_match(&admin);
+    env.ledger().with_mut(|l| l.timestamp += 29);
+    let early = catch_unwind(AssertUnwindSafe(|| {
+        env.as_contract(&contract_id, || {
+            AmongUsContract::finalize_meeting(
+                env.clone(),
+                players.get(1).unwrap(),
+                BytesN::from_array(&env, &[22; 32]),
+                None,
+            )
+        })
+    }));
+    assert!(panicked_with(&early.err().unwrap(), GameError::NotAdmin));
+}
+
+#[test]
+fn any_player_finalizes_from_stored_votes_after_the_deadline() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, players) = overdue_meeting(&env);
+    env.ledger().with_mut(|l| l.timestamp += 50);
+    // The caller names themselves, but the stored votes decide who leaves.
+    let caller = players.get(1).unwrap();
+    client.finalize_meeting(&caller, &BytesN::from_array(&env, &[22; 32]), &None);
+    assert_eq!(client.get_game_state().phase, symbol_short!("playing"));
+    assert!(!client.get_players().get(players.get(3).unwrap()).unwrap().alive);
+    assert!(client.get_players().get(caller).unwrap().alive);
+}
+
+#[test]
+fn kill_shield_holds_kills_after_the_start_and_each_meeting() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, players) = start_game_with_verifier(&env);
+    client.configure_kill_shield(&admin, &30);
+    let killer = players.get(0).unwrap();
+    let victim = players.get(1).unwrap();
+    env.ledger().with_mut(|l| l.timestamp += 10);
+    let early = client.try_submit_kill_proof(&killer, &victim, &proof_input(&env, 90), &0);
+    assert_eq!(early, Err(Ok(GameError::KillCooldown)));
+    let crew = players.get(2).unwrap();
+    assert_eq!(client.get_player_timers(&crew, &crew, &None).shield_expires_in_secs, Some(20));
+
+    env.ledger().with_mut(|l| l.timestamp += 20);
+    client.submit_kill_proof(&killer, &victim, &proof_input(&env, 90), &0);
+    client.start_meeting(&crew);
+    client.end_meeting(&admin);
+    let victim = players.get(3).unwrap();
+    let again = client.try_submit_kill_proof(&killer, &victim, &proof_input(&env, 91), &0);
+    assert_eq!(again, Err(Ok(GameError::KillCooldown)));
+    assert_eq!(client.get_player_timers(&crew, &crew, &None).shield_expires_in_secs, Some(30));
+}
+
+#[test]
+fn idle_seats_may_be_kicked_by_anyone_past_the_afk_deadline() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin) = casual_game(&env);
+    client.configure_afk_timeout(&admin, &60);
+    let players = client.get_players().keys();
+    let idle = players.get(3).unwrap();
+    env.ledger().with_mut(|l| l.timestamp += 45);
+    assert_eq!(client.get_player_timers(&idle, &idle, &None).afk_deadline_in_secs, Some(15));
+
+    env.ledger().with_mut(|l| l.timestamp += 15);
+    let stranger = Address::generate(&env);
+    client.kick_player(&stranger, &idle, &None);
+    assert!(!client.get_players().contains_key(idle));
+
+    // A seat still inside its deadline is the operator's call.
+    client.configure_afk_timeout(&admin, &600);
+    let early = catch_unwind(AssertUnwindSafe(|| {
+        env.as_contract(&contract_id, || {
+            let seat = players.get(2).unwrap();
+            AmongUsContract::kick_player(env.clone(), stranger.clone(), seat, None)
+        })
+    }));
+    assert!(panicked_with(&early.err().unwrap(), GameError::NotAdmin));
+}
+
+#[test]
+fn comms_sabotage_blocks_tasks_until_fixed_then_cools_down() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, players) = start_game_with_verifier(&env);
+    client.configure_sabotage(&admin, &5, &0);
+    let crew = players.get(1).unwrap();
+    let comms = symbol_short!("comms");
+    client.trigger_sabotage(&comms, &proof_input(&env, 1));
+    assert!(client.get_game_state().sabotage_active);
+    assert_eq!(client.get_active_sabotage(&admin, &None), Some(comms.clone()));
+
+    let blocked = client.try_submit_task_proof(&crew, &proof_input(&env, 2));
+    assert_eq!(blocked, Err(Ok(GameError::SabotageActive)));
+    let again = client.try_trigger_sabotage(&symbol_short!("lights"), &proof_input(&env, 3));
+    assert_eq!(again, Err(Ok(GameError::SabotageActive)));
+
+    client.fix_sabotage(&crew, &comms, &proof_input(&env, 4));
+    assert!(!client.get_game_state().sabotage_active);
+    client.submit_task_proof(&crew, &proof_input(&env, 5));
+    let early = client.try_trigger_sabotage(&comms, &proof_input(&env, 6));
+    assert_eq!(early, Err(Ok(GameError::SabotageCooldown)));
+    // Crew cannot sabotage, so their own timers leave the wait out.
+    assert_eq!(client.get_player_timers(&crew, &crew, &None).sabotage_ready_in_secs, None);
+    assert_eq!(client.get_player_timers(&admin, &crew, &None).sabotage_ready_in_secs, Some(5));
+    env.ledger().with_mut(|l| l.timestamp += 5);
+    assert_eq!(client.get_player_timers(&admin, &crew, &None).sabotage_ready_in_secs, Some(0));
+    client.trigger_sabotage(&comms, &proof_input(&env, 6));
+}
+
+#[test]
+fn sabotage_proof_opens_the_role_root_without_naming_the_impostor() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, _) = start_game_with_verifier(&env);
+    let costs = client.get_cost_report();
+    client.trigger_sabotage(&symbol_short!("o2"), &proof_input(&env, 1));
+    let (_, topics, _) = env.events().all().last().unwrap();
+    assert_eq!(topics, (symbol_short!("sabotage"), 1u32).into_val(&env));
+    assert_eq!(client.get_cost_report(), costs);
+    assert!(env.auths().is_empty());
+}
+
+#[test]
+fn unfixed_reactor_hands_impostors_the_match_on_advance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, players) = start_game_with_verifier(&env);
+    client.configure_sabotage(&admin, &0, &10);
+    client.trigger_sabotage(&symbol_short!("lights"), &proof_input(&env, 1));
+    env.ledger().with_mut(|l| l.timestamp += 20);
+    // Lights is not critical, so it never runs out.
+    assert!(!client.advance());
+    client.fix_sabotage(&players.get(1).unwrap(), &symbol_short!("lights"), &proof_input(&env, 2));
+
+    client.trigger_sabotage(&symbol_short!("reactor"), &proof_input(&env, 3));
+    env.ledger().with_mut(|l| l.timestamp += 9);
+    assert!(!client.advance());
+    env.ledger().with_mut(|l| l.timestamp += 1);
+    assert!(client.advance());
+    assert_eq!(client.get_game_state().winner, symbol_short!("impost"));
+    let repair = Repair {
+        kind: symbol_short!("lights"),
+        round: 1,
+        fixer: BytesN::from_array(&env, &[22; 32]),
+    };
+    assert_eq!(client.get_match_report().unwrap().repairs, vec![&env, repair]);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #99)")]
+fn unknown_sabotage_kinds_are_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (_, contract_id, _, _) = start_game_with_verifier(&env);
+    let _ = env.as_contract(&contract_id, || {
+        AmongUsContract::trigger_sabotage(env.clone(), symbol_short!("doors"), proof_input(&env, 1))
+    });
+}
+
+#[test]
+fn paused_match_holds_the_reactor_timer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, _) = start_game_with_verifier(&env);
+    client.configure_sabotage(&admin, &0, &10);
+    client.trigger_sabotage(&symbol_short!("reactor"), &proof_input(&env, 1));
+    env.ledger().with_mut(|l| l.timestamp += 4);
+    client.pause_match(&admin);
+    env.ledger().with_mut(|l| l.timestamp += 100);
+    client.resume_match(&admin);
+    assert!(!client.check_win_conditions());
+    env.ledger().with_mut(|l| l.timestamp += 6);
+    assert!(client.check_win_conditions());
+}
+
+#[test]
+fn revealed_ejection_keeps_two_impostor_match_running_until_parity() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, players) = start_two_impostor_game(&env);
+    client.start_meeting(&players.get(2).unwrap());
+    let impostor_hash = BytesN::from_array(&env, &[2; 32]);
+    for i in [0u32, 2, 3, 4] {
+        client.submit_vote(
+            &players.get(i).unwrap(),
+            &VoteInput {
+                target_hash: impostor_hash.clone(),
+                proof: Bytes::from_array(&env, &[8; 32]),
+                nullifier: BytesN::from_array(&env, &[40 + i as u8; 32]),
+            },
+        );
+    }
+    client.finalize_meeting(&admin, &impostor_hash, &Some(proof_input(&env, 50)));
+    assert_eq!(client.get_game_state().phase, symbol_short!("playing"));
+
+    // One impostor against four crew: the first two kills leave the match open.
+    let killer = players.get(0).unwrap();
+    client.submit_kill_proof(&killer, &players.get(2).unwrap(), &proof_input(&env, 11), &0);
+    client.submit_kill_proof(&killer, &players.get(3).unwrap(), &proof_input(&env, 12), &0);
+    assert_eq!(client.get_game_state().phase, symbol_short!("playing"));
+    client.submit_kill_proof(&killer, &players.get(4).unwrap(), &proof_input(&env, 13), &0);
+    assert_eq!(client.get_game_state().winner, symbol_short!("impost"));
+}
+
+#[test]
+fn survival_mode_hands_the_crew_a_win_when_time_runs_out() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.init(&admin, &1);
+    client.configure_game_mode(&admin, &GameMode::Survival, &300);
+    assert_eq!(
+        client.get_win_conditions(),
+        vec![
+            &env,
+            WinCondition::ImpostorsEliminated,
+            WinCondition::ImpostorParity,
+            WinCondition::SurvivalTimeout
+        ]
+    );
+
+    join_four_players(&env, &client);
+    client.start_game(&admin);
+    env.ledger().with_mut(|l| l.timestamp += 299);
+    assert!(!client.check_win_conditions());
+    env.ledger().with_mut(|l| l.timestamp += 1);
+    assert!(client.check_win_conditions());
+    let state = client.get_game_state();
+    assert_eq!((state.phase, state.winner), (symbol_short!("ended"), symbol_short!("crew")));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #27)")]
+fn ejected_role_commitment_cannot_back_new_proofs() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let verifier_id = env.register_contract(None, MockVerifier);
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.init(&admin, &1);
+    client.set_verifier(&admin, &verifier_id);
+    let players = join_four_players(&env, &client);
+    client.start_game(&admin);
+
+    let p1 = players.get(0).unwrap();
+    let p3 = players.get(2).unwrap();
+    let p4 = players.get(3).unwrap();
+
+    client.start_meeting(&p1);
+    let target_hash = BytesN::from_array(&env, &[22; 32]);
+    for (i, voter) in [p1.clone(), p3, p4].iter().enumerate() {
+        client.submit_vote(
+            voter,
+            &VoteInput {
+                target_hash: target_hash.clone(),
+                proof: Bytes::from_array(&env, &[8; 32]),
+                nullifier: BytesN::from_array(&env, &[60 + i as u8; 32]),
+            },
+        );
+    }
+    client.finalize_meeting(&admin, &target_hash, &None);
+
+    let mut public_inputs = Vec::new(&env);
+    public_inputs.push_back(BytesN::from_array(&env, &[2; 32]));
+    let proof = ProofInput {
+        proof: Bytes::from_array(&env, &[8; 32]),
+        nullifier: BytesN::from_array(&env, &[70; 32]),
+        public_inputs,
+    };
+    let _ = env.as_contract(&contract_id, || {
+        AmongUsContract::submit_task_proof(env.clone(), p1, proof)
+    });
+}
+
+#[test]
+fn task_quota_resets_each_round() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, players) = start_game_with_verifier(&env);
+    client.configure_quotas(&admin, &1, &1);
+
+    let p1 = players.get(0).unwrap();
+    client.submit_task_proof(&p1, &proof_input(&env, 80));
+
+    client.start_meeting(&p1);
+    client.end_meeting(&admin);
+    client.submit_task_proof(&p1, &proof_input(&env, 81));
+
+    let stored = client.get_players().get(p1).unwrap();
+    assert_eq!(stored.tasks_done, 2);
+}
+
+#[test]
+fn task_proofs_must_open_the_seat_role_commitment_as_crew() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, players) = start_game_with_verifier(&env);
+    let verifier_id = env.register_contract(None, CrewVerifier);
+    CrewVerifierClient::new(&env, &verifier_id).bind(&BytesN::from_array(&env, &[1; 32]));
+    client.set_verifier(&admin, &verifier_id);
+
+    let impostor = client.try_submit_task_proof(&players.get(0).unwrap(), &proof_input(&env, 80));
+    assert_eq!(impostor, Err(Ok(GameError::InvalidProof)));
+    client.submit_task_proof(&players.get(1).unwrap(), &proof_input(&env, 81));
+    assert_eq!(client.get_players().get(players.get(1).unwrap()).unwrap().tasks_done, 1);
+}
+
+#[test]
+fn ghost_tasks_let_dead_crew_keep_proving_tasks_but_nothing_else() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, players) = start_game_with_verifier(&env);
+    let (killer, ghost) = (players.get(0).unwrap(), players.get(1).unwrap());
+    client.submit_kill_proof(&killer, &ghost, &proof_input(&env, 90), &0);
+    let blocked = client.try_submit_task_proof(&ghost, &proof_input(&env, 80));
+    assert_eq!(blocked, Err(Ok(GameError::PlayerDead)));
+
+    client.configure_ghost_tasks(&admin, &true);
+    client.submit_task_proof(&ghost, &proof_input(&env, 80));
+    assert_eq!(client.get_players().get(ghost.clone()).unwrap().tasks_done, 1);
+    assert_eq!(client.try_start_meeting(&ghost), Err(Ok(GameError::PlayerDead)));
+}
+
+#[test]
+fn ejected_crew_keep_proving_tasks_as_ghosts() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, players) = start_game_with_verifier(&env);
+    client.configure_ghost_tasks(&admin, &true);
+    let ejected = players.get(1).unwrap();
+    client.start_meeting(&players.get(0).unwrap());
+    let voters = [players.get(0).unwrap(), players.get(2).unwrap(), players.get(3).unwrap()];
+    let target = BytesN::from_array(&env, &[22; 32]);
+    eject_by_vote(&env, &client, &admin, &voters, &target, 10, &None);
+    assert!(!client.get_players().get(ejected.clone()).unwrap().alive);
+
+    // The seat's role is spent by the ejection, but only other seats' proofs lose it.
+    client.submit_task_proof(&ejected, &proof_input(&env, 80));
+    assert_eq!(client.get_players().get(ejected).unwrap().tasks_done, 1);
+}
+
+#[test]
+fn task_quota_rejects_extra_proofs_in_round() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, players) = start_game_with_verifier(&env);
+    client.configure_quotas(&admin, &1, &1);
+
+    let p1 = players.get(0).unwrap();
+    client.submit_task_proof(&p1, &proof_input(&env, 80));
+    assert_eq!(
+        client.try_submit_task_proof(&p1, &proof_input(&env, 81)),
+        Err(Ok(GameError::QuotaReached))
+    );
+}
+
+fn checkpoint(env: &Env, seq: u32, players: &Vec<Address>, x: u32) -> PositionCheckpoint {
+    let mut updates = Vec::new(env);
+    for player in players.iter() {
+        updates.push_back(PositionUpdate { player, x, y: x + 1 });
+    }
+    PositionCheckpoint { seq, updates }
+}
+
+#[test]
+fn checkpoint_applies_signed_positions() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, players) = start_game_with_verifier(&env);
+
+    client.checkpoint_positions(&admin, &checkpoint(&env, 1, &players, 30));
+
+    assert_eq!(client.get_checkpoint_seq(), 1);
+    for player in players.iter() {
+        let stored = client.get_players().get(player).unwrap();
+        assert_eq!((stored.x, stored.y), (30, 31));
+    }
+}
+
+#[test]
+fn conflicting_checkpoint_slashes_submitter() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, players) = start_game_with_verifier(&env);
+    let p1 = players.get(0).unwrap();
+
+    client.checkpoint_positions(&p1, &checkpoint(&env, 1, &players, 30));
+    client.checkpoint_positions(&p1, &checkpoint(&env, 1, &players, 90));
+    assert_eq!(client.get_players().get(p1.clone()).unwrap().x, 30);
+
+    let next = checkpoint(&env, 2, &players, 40);
+    assert_eq!(
+        client.try_checkpoint_positions(&p1, &next),
+        Err(Ok(GameError::SubmitterSlashed))
+    );
+}
+
+fn bond_token(env: &Env, holder: &Address, amount: i128) -> Address {
+    let issuer = Address::generate(env);
+    let token_id = env.register_stellar_asset_contract(issuer);
+    token::StellarAssetClient::new(env, &token_id).mint(holder, &amount);
+    token_id
+}
+
+#[test]
+fn unchallenged_result_settles_after_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, _) = lobby_with_verifier(&env);
+    let token_id = bond_token(&env, &admin, 500);
+    client.configure_settlement(&admin, &token_id, &100, &60);
+    client.start_game(&admin);
+    assert_eq!(client.get_config().max_players, 15);
+
+    client.post_result(&admin, &symbol_short!("crew"));
+    assert_eq!(token::Client::new(&env, &token_id).balance(&admin), 400);
+
+    env.ledger().with_mut(|l| l.timestamp += 61);
+    client.finalize_result(&admin);
+
+    let state = client.get_game_state();
+    assert_eq!(state.winner, symbol_short!("crew"));
+    assert_eq!(token::Client::new(&env, &token_id).balance(&admin), 500);
+    assert!(client.get_pending_result().is_none());
+}
+
+#[test]
+fn settlement_terms_are_fixed_once_a_result_is_posted() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, _) = lobby_with_verifier(&env);
+    let token_id = bond_token(&env, &admin, 500);
+    client.configure_settlement(&admin, &token_id, &100, &60);
+    client.start_game(&admin);
+    client.post_result(&admin, &symbol_short!("crew"));
+
+    // The outcome is known, so the bond and window can no longer move.
+    let reshaped = catch_unwind(AssertUnwindSafe(|| {
+        env.as_contract(&contract_id, || {
+            let token = token_id.clone();
+            AmongUsContract::configure_settlement(env.clone(), admin.clone(), token, 1, 1)
+        })
+    }));
+    assert!(panicked_with(&reshaped.err().unwrap(), ModuleError::ResultPending));
+}
+
+#[test]
+fn successful_challenge_claims_bond() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, players) = lobby_with_verifier(&env);
+    let token_id = bond_token(&env, &admin, 500);
+    client.configure_settlement(&admin, &token_id, &100, &60);
+    client.start_game(&admin);
+    client.post_result(&admin, &symbol_short!("impost"));
+
+    let challenger = players.get(1).unwrap();
+    let mut proof = proof_input(&env, 90);
+    proof
+        .public_inputs
+        .push_back(AmongUsContract::result_hash(&env, &symbol_short!("crew")));
+    client.challenge_result(&challenger, &proof);
+
+    assert_eq!(token::Client::new(&env, &token_id).balance(&challenger), 100);
+    assert!(client.get_pending_result().is_none());
+    assert_eq!(client.get_game_state().phase, symbol_short!("playing"));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #93)")]
+fn proof_backing_the_posted_result_cannot_challenge_it() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, players) = lobby_with_verifier(&env);
+    let token_id = bond_token(&env, &admin, 500);
+    client.configure_settlement(&admin, &token_id, &100, &60);
+    client.start_game(&admin);
+    client.post_result(&admin, &symbol_short!("impost"));
+
+    let mut supporting = proof_input(&env, 90);
+    supporting
+        .public_inputs
+        .push_back(client.get_pending_result().unwrap().result_hash);
+    let _ = env.as_contract(&contract_id, || {
+        AmongUsContract::challenge_result(env.clone(), players.get(1).unwrap(), supporting)
+    });
+}
+
+#[test]
+fn match_ended_by_play_voids_the_posted_result() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, _) = lobby_with_verifier(&env);
+    let token_id = bond_token(&env, &admin, 500);
+    client.configure_settlement(&admin, &token_id, &100, &60);
+    client.start_game(&admin);
+    client.post_result(&admin, &symbol_short!("impost"));
+    assert_eq!(client.get_escrow_balance(&token_id), 100);
+
+    client.end_game_admin(&admin, &symbol_short!("crew"));
+    assert!(client.get_pending_result().is_none());
+    assert_eq!(token::Client::new(&env, &token_id).balance(&admin), 500);
+    assert_eq!(client.get_game_state().winner, symbol_short!("crew"));
+
+    env.ledger().with_mut(|l| l.timestamp += 61);
+    let late = catch_unwind(AssertUnwindSafe(|| {
+        env.as_contract(&contract_id, || {
+            AmongUsContract::finalize_result(env.clone(), admin.clone())
+        })
+    }));
+    assert!(panicked_with(&late.err().unwrap(), GameError::GameEnded));
+}
+
+#[cfg(feature = "economy")]
+fn staked_lobby<'a>(
+    env: &Env,
+) -> (AmongUsContractClient<'a>, Address, Address, Address, Vec<Address>) {
+    let verifier_id = env.register_contract(None, MockVerifier);
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.init(&admin, &1);
+    client.set_verifier(&admin, &verifier_id);
+
+    let stake_token = env.register_stellar_asset_contract(Address::generate(env));
+    let reward_token = env.register_stellar_asset_contract(contract_id.clone());
+    client.configure_economy(
+        &admin,
+        &EconomyConfig {
+            stake_token: stake_token.clone(),
+            stake_amount: 30,
+            reward_token: reward_token.clone(),
+            mint_rewards: true,
+            reward_minter: reward_token.clone(),
+            reward_per_winner: 5,
+        },
+    );
+
+    let stake_admin = token::StellarAssetClient::new(env, &stake_token);
+    let mut players = Vec::new(env);
+    for i in 0..4u8 {
+        let player = Address::generate(env);
+        stake_admin.mint(&player, &30);
+        client.join_game(
+            &player,
+            &COLOR_PALETTE[i as usize],
+            &symbol_short!("P"),
+            &BytesN::from_array(env, &[10 + i; 32]),
+            &BytesN::from_array(env, &[i + 1; 32]),
+        );
+        players.push_back(player);
+    }
+    (client, contract_id, admin, stake_token, players)
+}
+
+#[cfg(feature = "economy")]
+fn staked_game<'a>(
+    env: &Env,
+) -> (AmongUsContractClient<'a>, Address, Address, Address, Vec<Address>) {
+    let (client, contract_id, admin, stake_token, players) = staked_lobby(env);
+    client.start_game(&admin);
+    (client, contract_id, admin, stake_token, players)
+}
+
+#[cfg(feature = "economy")]
+fn payout_proof(env: &Env, contract_id: &Address, role: u8, winner: Symbol) -> ProofInput {
+    let mut proof = proof_input(env, 90 + role);
+    proof.public_inputs.push_back(BytesN::from_array(env, &[role; 32]));
+    proof.public_inputs.push_back(env.as_contract(contract_id, || {
+        AmongUsContract::result_hash(env, &winner)
+    }));
+    proof
+}
+
+#[cfg(feature = "economy")]
+#[test]
+fn winners_claim_stake_share_and_minted_reward() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, stake_token, players) = staked_game(&env);
+    assert_eq!(client.get_escrow_balance(&stake_token), 120);
+    client.end_game_admin(&admin, &symbol_short!("crew"));
+
+    let winner = players.get(0).unwrap();
+    let proof = payout_proof(&env, &contract_id, 1, symbol_short!("crew"));
+    assert_eq!(client.claim_payout(&winner, &proof), 40);
+
+    let reward_token = client.get_economy().unwrap().reward_token;
+    assert_eq!(token::Client::new(&env, &stake_token).balance(&winner), 40);
+    assert_eq!(token::Client::new(&env, &reward_token).balance(&winner), 5);
+    assert_eq!(client.get_escrow_balance(&stake_token), 80);
+}
+
+#[cfg(feature = "economy")]
+#[test]
+fn rake_is_split_between_burn_and_treasury() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, stake_token, players) = staked_game(&env);
+    let treasury = Address::generate(&env);
+    client.configure_rake(&admin, &treasury, &1_000, &5_000);
+    client.end_game_admin(&admin, &symbol_short!("crew"));
+
+    let winner = players.get(0).unwrap();
+    let proof = payout_proof(&env, &contract_id, 1, symbol_short!("crew"));
+    assert_eq!(client.claim_payout(&winner, &proof), 36);
+
+    let stake = token::Client::new(&env, &stake_token);
+    assert_eq!(stake.balance(&treasury), 6);
+    assert_eq!(client.get_escrow_balance(&stake_token), 72);
+    assert_eq!(stake.balance(&contract_id), 72);
+}
+
+#[cfg(feature = "economy")]
+#[test]
+fn task_rewards_split_underfunded_pool_by_tasks() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, players) = start_game_with_verifier(&env);
+    let sponsor = Address::generate(&env);
+    let token_id = bond_token(&env, &sponsor, 25);
+    client.configure_task_rewards(&admin, &token_id, &10);
+    client.fund_task_rewards(&sponsor, &25);
+
+    let p1 = players.get(0).unwrap();
+    let p2 = players.get(1).unwrap();
+    client.submit_task_proof(&p1, &proof_input(&env, 100));
+    client.submit_task_proof(&p1, &proof_input(&env, 101));
+    client.submit_task_proof(&p2, &proof_input(&env, 102));
+    client.end_game_admin(&admin, &symbol_short!("impost"));
+
+    assert_eq!(client.claim_task_rewards(&p1), 16);
+    assert_eq!(client.claim_task_rewards(&p2), 8);
+    assert_eq!(client.get_task_reward_pool(), 1);
+    assert_eq!(token::Client::new(&env, &token_id).balance(&p1), 16);
+}
+
+#[test]
+fn referral_joins_credit_the_code_owner_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.init(&admin, &1);
+    let referrer = Address::generate(&env);
+    let code = symbol_short!("alpha");
+    client.register_referral_code(&referrer, &code);
+    assert_eq!(client.get_referral_code_owner(&code), Some(referrer.clone()));
+
+    let (p1, p2) = (Address::generate(&env), Address::generate(&env));
+    for (player, hash) in [(&p1, 1u8), (&p2, 2u8)] {
+        client.join_with_referral(
+            player,
+            &code,
+            &COLOR_PALETTE[hash as usize],
+            &symbol_short!("P"),
+            &BytesN::from_array(&env, &[hash; 32]),
+            &BytesN::from_array(&env, &[hash; 32]),
+        );
+    }
+    client.leave_game(&p1, &None);
+    client.join_with_referral(
+        &p1,
+        &code,
+        &COLOR_PALETTE[1],
+        &symbol_short!("P"),
+        &BytesN::from_array(&env, &[1; 32]),
+        &BytesN::from_array(&env, &[1; 32]),
+    );
+    assert_eq!(client.get_referral_points(&referrer), 2);
+
+    let sponsor = Address::generate(&env);
+    let token_id = bond_token(&env, &sponsor, 15);
+    client.configure_referral_rewards(&admin, &token_id, &5);
+    client.fund_referral_rewards(&sponsor, &15);
+    assert_eq!(client.claim_referral_rewards(&referrer), 10);
+    assert_eq!(client.claim_referral_rewards(&referrer), 0);
+    assert_eq!(client.get_referral_pool(), 5);
+    assert_eq!(token::Client::new(&env, &token_id).balance(&referrer), 10);
+}
+
+#[cfg(feature = "economy")]
+#[test]
+fn bounty_pays_first_voter_for_ejected_target() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, players) = start_game_with_verifier(&env);
+    let sponsor = Address::generate(&env);
+    let token_id = bond_token(&env, &sponsor, 50);
+    let target_hash = BytesN::from_array(&env, &[22; 32]);
+    client.post_bounty(&sponsor, &target_hash, &token_id, &50);
+
+    let p1 = players.get(0).unwrap();
+    let p3 = players.get(2).unwrap();
+    let p4 = players.get(3).unwrap();
+    client.start_meeting(&p1);
+    for (i, voter) in [p3.clone(), p1, p4].iter().enumerate() {
+        client.submit_vote(
+            voter,
+            &VoteInput {
+                target_hash: target_hash.clone(),
+                proof: Bytes::from_array(&env, &[8; 32]),
+                nullifier: BytesN::from_array(&env, &[110 + i as u8; 32]),
+            },
+        );
+    }
+    client.finalize_meeting(&admin, &target_hash, &None);
+
+    assert_eq!(token::Client::new(&env, &token_id).balance(&p3), 50);
+    assert!(client.get_bounties(&target_hash).is_empty());
+}
+
+#[cfg(feature = "economy")]
+#[test]
+fn unclaimed_bounty_refunds_after_game_end() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, _) = start_game_with_verifier(&env);
+    let sponsor = Address::generate(&env);
+    let token_id = bond_token(&env, &sponsor, 50);
+    let target_hash = BytesN::from_array(&env, &[33; 32]);
+    client.post_bounty(&sponsor, &target_hash, &token_id, &50);
+    client.end_game_admin(&admin, &symbol_short!("crew"));
+
+    assert_eq!(client.refund_bounties(&sponsor, &target_hash), 50);
+    assert_eq!(token::Client::new(&env, &token_id).balance(&sponsor), 50);
+}
+
+#[cfg(feature = "economy")]
+#[test]
+fn voided_match_refunds_stakes_after_verifier_shutdown() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, stake_token, players) = staked_game(&env);
+    client.set_verifier_enabled(&admin, &false, &IncidentReason::VerifierFault);
+    client.void_match(&admin, &IncidentReason::VerifierFault);
+
+    let stake = token::Client::new(&env, &stake_token);
+    for player in players.iter() {
+        assert_eq!(stake.balance(&player), 30);
+    }
+    assert_eq!(client.get_game_state().winner, symbol_short!("void"));
+    assert_eq!(client.get_match_status().winner, MatchWinner::Void);
+    let incidents = client.get_incidents(&0);
+    assert_eq!(incidents.len(), 2);
+    assert_eq!(incidents.get(0).unwrap().kind, symbol_short!("vrf_off"));
+    assert_eq!(incidents.get(1).unwrap().kind, symbol_short!("void"));
+    assert_eq!(incidents.get(1).unwrap().reason, IncidentReason::VerifierFault);
+}
+
+#[cfg(feature = "economy")]
+#[test]
+fn voided_match_is_covered_by_the_insurance_share_reserved_at_start() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, stake_token, players) = staked_lobby(&env);
+    client.configure_rake(&admin, &Address::generate(&env), &1_000, &0);
+    client.configure_insurance(&admin, &5_000);
+    client.start_game(&admin);
+    // Half of the 10% rake on the 120 pot is set aside before anyone can claim.
+    assert_eq!(client.get_insurance_pool(), 6);
+
+    client.set_paused(&admin, &true, &IncidentReason::Maintenance);
+    client.void_match(&admin, &IncidentReason::Maintenance);
+    let stake = token::Client::new(&env, &stake_token);
+    for player in players.iter() {
+        assert_eq!(stake.balance(&player), 30);
+    }
+    assert_eq!(client.get_insurance_pool(), 0);
+
+    let proof = payout_proof(&env, &contract_id, 1, symbol_short!("void"));
+    let claim = client.try_claim_payout(&players.get(0).unwrap(), &proof);
+    assert_eq!(claim, Err(Ok(GameError::GameNotEnded)));
+}
+
+#[cfg(feature = "economy")]
+#[test]
+#[should_panic(expected = "Error(Contract, #68)")]
+fn void_requires_active_incident() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, _, _) = staked_game(&env);
+    client.set_paused(&admin, &true, &IncidentReason::Maintenance);
+    client.set_paused(&admin, &false, &IncidentReason::Maintenance);
+    let _ = env.as_contract(&contract_id, || {
+        AmongUsContract::void_match(env.clone(), admin, IncidentReason::Unspecified)
+    });
+}
+
+#[cfg(feature = "economy")]
+#[test]
+fn rake_feeds_insurance_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, _, players) = staked_game(&env);
+    let treasury = Address::generate(&env);
+    client.configure_rake(&admin, &treasury, &1_000, &0);
+    client.configure_insurance(&admin, &5_000);
+    client.end_game_admin(&admin, &symbol_short!("crew"));
+
+    let proof = payout_proof(&env, &contract_id, 1, symbol_short!("crew"));
+    client.claim_payout(&players.get(0).unwrap(), &proof);
+    assert_eq!(client.get_insurance_pool(), 6);
+}
+
+#[cfg(feature = "economy")]
+#[test]
+fn insurance_reserved_at_start_is_not_taken_again_at_payout() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, stake_token, players) = staked_lobby(&env);
+    let treasury = Address::generate(&env);
+    client.configure_rake(&admin, &treasury, &1_000, &0);
+    client.configure_insurance(&admin, &5_000);
+    client.start_game(&admin);
+    client.end_game_admin(&admin, &symbol_short!("crew"));
+
+    let proof = payout_proof(&env, &contract_id, 1, symbol_short!("crew"));
+    assert_eq!(client.claim_payout(&players.get(0).unwrap(), &proof), 36);
+    assert_eq!(client.get_insurance_pool(), 6);
+    assert_eq!(token::Client::new(&env, &stake_token).balance(&treasury), 6);
+}
+
+fn test_map(env: &Env) -> MapConfig {
+    let mut spawns = Vec::new(env);
+    spawns.push_back(Point { x: 50, y: 50 });
+    spawns.push_back(Point { x: 60, y: 50 });
+    let mut consoles = Vec::new(env);
+    consoles.push_back(Console {
+        task_id: 1,
+        kind: symbol_short!("short"),
+        position: Point { x: 20, y: 30 },
+    });
+    consoles.push_back(Console {
+        task_id: 2,
+        kind: symbol_short!("long"),
+        position: Point { x: 20, y: 30 },
+    });
+    MapConfig {
+        width: 100,
+        height: 80,
+        rooms: Vec::new(env),
+        vents: Vec::new(env),
+        consoles,
+        spawns,
+        emergency_button: Point { x: 50, y: 40 },
+    }
+}
+
+#[test]
+fn batch_admin_applies_tournament_setup_atomically() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let operator = Address::generate(&env);
+    client.init(&admin, &1);
+    client.batch_admin(
+        &admin,
+        &Vec::from_array(
+            &env,
+            [
+                AdminOp::ConfigureGame(8, 12),
+                AdminOp::RegisterMap(7, test_map(&env)),
+                AdminOp::SelectMap(7),
+                AdminOp::SetOperator(operator.clone()),
+            ],
+        ),
+    );
+    assert_eq!(client.get_config().max_players, 8);
+    assert_eq!(client.get_selected_map().unwrap().map_id, 7);
+    assert_eq!(client.get_operator(), Some(operator));
+
+    let failing = Vec::from_array(
+        &env,
+        [AdminOp::ConfigureQuotas(4, 1), AdminOp::SelectMap(99)],
+    );
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        env.as_contract(&contract_id, || {
+            AmongUsContract::batch_admin(env.clone(), admin.clone(), failing.clone())
+        })
+    }));
+    assert!(panicked_with(&result.err().unwrap(), ModuleError::MapNotFound));
+    let config = env.as_contract(&contract_id, || AmongUsContract::read_config(&env));
+    assert_eq!(config.max_tasks_per_round, 10);
+}
+
+#[test]
+fn selected_map_assigns_spawns() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.init(&admin, &1);
+    client.register_map(&admin, &7, &test_map(&env));
+    client.select_map(&admin, &7);
+
+    let players = join_four_players(&env, &client);
+    let stored = client.get_players();
+    let p2 = stored.get(players.get(1).unwrap()).unwrap();
+    let p3 = stored.get(players.get(2).unwrap()).unwrap();
+    assert_eq!((p2.x, p2.y), (60, 50));
+    assert_eq!((p3.x, p3.y), (50, 50));
+    let selected = client.get_selected_map().unwrap();
+    assert_eq!((selected.map_id, selected.version), (7, 1));
+}
+
+#[test]
+fn match_report_pins_map_hash_after_registry_update() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.init(&admin, &1);
+    client.register_map(&admin, &7, &test_map(&env));
+    client.select_map(&admin, &7);
+    join_four_players(&env, &client);
+    client.start_game(&admin);
+
+    let expected = env.crypto().sha256(&test_map(&env).to_xdr(&env));
+    assert_eq!(client.get_game_state().map_hash, expected);
+    assert!(client.get_match_report().is_none());
+
+    let mut updated = test_map(&env);
+    updated.height = 90;
+    client.register_map(&admin, &7, &updated);
+    client.end_game_admin(&admin, &symbol_short!("crew"));
+
+    let report = client.get_match_report().unwrap();
+    assert_eq!(report.map_hash, expected);
+    assert_eq!(report.winner, symbol_short!("crew"));
+    assert_eq!((report.rounds, report.players), (1, 4));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #71)")]
+fn moves_are_bounded_by_selected_map() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.init(&admin, &1);
+    client.register_map(&admin, &7, &test_map(&env));
+    client.select_map(&admin, &7);
+    let players = join_four_players(&env, &client);
+    client.start_game(&admin);
+
+    let player = players.get(0).unwrap();
+    client.submit_move(&player, &99, &79);
+    let _ = env.as_contract(&contract_id, || {
+        AmongUsContract::submit_move(env.clone(), player, 100, 10)
+    });
+}
+
+#[test]
+fn approved_submission_adds_version_without_moving_selected_map() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.init(&admin, &1);
+    client.register_map(&admin, &7, &test_map(&env));
+    client.select_map(&admin, &7);
+
+    let author = Address::generate(&env);
+    let token_id = bond_token(&env, &author, 40);
+    client.configure_map_bond(&admin, &token_id, &20);
+
+    let mut updated = test_map(&env);
+    updated.width = 200;
+    let approved = client.submit_map(&author, &7, &updated);
+    let rejected = client.submit_map(&author, &8, &test_map(&env));
+    assert_eq!(token::Client::new(&env, &token_id).balance(&author), 0);
+
+    assert_eq!(client.approve_map(&admin, &approved), 2);
+    client.reject_map(&admin, &rejected);
+
+    let token = token::Client::new(&env, &token_id);
+    assert_eq!(token.balance(&author), 20);
+    assert_eq!(token.balance(&admin), 20);
+    assert_eq!(client.get_map(&7).unwrap().width, 200);
+    assert!(client.get_map(&8).is_none());
+    assert_eq!(client.get_selected_map().unwrap().version, 1);
+}
+
+fn start_mapped_game<'a>(
+    env: &Env,
+) -> (AmongUsContractClient<'a>, Address, Address, Vec<Address>) {
+    let verifier_id = env.register_contract(None, MockVerifier);
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.init(&admin, &1);
+    client.set_verifier(&admin, &verifier_id);
+    client.register_map(&admin, &7, &test_map(env));
+    client.select_map(&admin, &7);
+    let players = join_four_players(env, &client);
+    client.start_game(&admin);
+    (client, contract_id, admin, players)
+}
+
+#[test]
+fn task_proof_waits_for_minimum_duration_after_start() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, players) = start_mapped_game(&env);
+    client.configure_task_duration(&admin, &30);
+
+    let player = players.get(0).unwrap();
+    client.submit_move(&player, &20, &30);
+    client.start_task(&player, &1);
+    assert_eq!(client.get_task_start(&player).unwrap().task_id, 1);
+
+    env.ledger().with_mut(|l| l.timestamp += 30);
+    client.submit_task_proof(&player, &proof_input(&env, 1));
+    assert!(client.get_task_start(&player).is_none());
+    assert_eq!(client.get_players().get(player).unwrap().tasks_done, 1);
+}
+
+#[test]
+fn player_timers_track_task_wait_quotas_and_button() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, players) = start_mapped_game(&env);
+    client.configure_task_duration(&admin, &30);
+    let player = players.get(0).unwrap();
+    client.submit_move(&player, &20, &30);
+    client.start_task(&player, &1);
+    env.ledger().with_mut(|l| l.timestamp += 12);
+    let timers = client.get_player_timers(&player, &player, &None);
+    assert_eq!((timers.task_ready_in_secs, timers.tasks_left, timers.kills_left), (18, 10, None));
+    assert_eq!((timers.afk_deadline_in_secs, timers.shield_expires_in_secs), (None, None));
+    assert_eq!(timers.emergency_meetings_left, 1);
+
+    env.ledger().with_mut(|l| l.timestamp += 18);
+    client.submit_task_proof(&player, &proof_input(&env, 1));
+    client.submit_move(&player, &50, &40);
+    client.start_meeting(&player);
+    let timers = client.get_player_timers(&player, &player, &None);
+    assert_eq!((timers.task_ready_in_secs, timers.tasks_left), (0, 10));
+    assert_eq!(timers.emergency_meetings_left, 0);
+}
+
+#[test]
+fn role_proof_grants_expiring_read_capability() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, players) = start_mapped_game(&env);
+    client.configure_read_grants(&admin, &60);
+    let (impostor, other) = (players.get(0).unwrap(), players.get(1).unwrap());
+    let salt = BytesN::from_array(&env, &[7; 32]);
+    let handle = client.get_grant_handle(&impostor, &salt);
+    let expires_at = client.claim_read_grant(&handle, &proof_input(&env, 40));
+    assert_eq!(client.get_read_grant(&handle).unwrap().expires_at, expires_at);
+    let (_, topics, _) = env.events().all().last().unwrap();
+    assert_eq!(topics, (symbol_short!("grant"),).into_val(&env));
+    let timers = client.get_player_timers(&impostor, &other, &Some(salt.clone()));
+    assert_eq!(timers.kills_left, Some(3));
+    assert_eq!(client.get_player_timers(&admin, &other, &None).kills_left, Some(3));
+
+    env.ledger().with_mut(|l| l.timestamp += 61);
+    let expired = catch_unwind(AssertUnwindSafe(|| {
+        env.as_contract(&contract_id, || {
+            AmongUsContract::get_player_timers(
+                env.clone(),
+                impostor.clone(),
+                other.clone(),
+                Some(salt.clone()),
+            )
+        })
+    }));
+    assert!(panicked_with(&expired.err().unwrap(), ModuleError::ReadGrantRequired));
+}
+
+#[test]
+fn impostor_grant_opens_side_counts_vitals_and_sabotage() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, players) = start_game_with_verifier(&env);
+    client.configure_sabotage(&admin, &5, &0);
+    let (impostor, victim) = (players.get(0).unwrap(), players.get(1).unwrap());
+    let salt = Some(BytesN::from_array(&env, &[7; 32]));
+    let handle = client.get_grant_handle(&impostor, &salt.clone().unwrap());
+    client.claim_read_grant(&handle, &proof_input(&env, 40));
+    client.submit_kill_proof(&impostor, &victim, &proof_input(&env, 41), &0);
+    client.trigger_sabotage(&symbol_short!("comms"), &proof_input(&env, 42));
+
+    assert_eq!(client.get_impostors_alive(&impostor, &salt), 1);
+    assert_eq!(client.get_crew_alive(&impostor, &salt), 2);
+    assert_eq!(client.get_vitals(&impostor, &salt), vec![&env, victim]);
+    assert_eq!(client.get_active_sabotage(&impostor, &salt), Some(symbol_short!("comms")));
+    let timers = client.get_player_timers(&impostor, &impostor, &salt);
+    assert_eq!((timers.kills_left, timers.kill_ready_in_secs), (Some(2), Some(0)));
+
+    // A player without a grant still reads their own countdowns, minus the kill fields.
+    let crew = players.get(2).unwrap();
+    let timers = client.get_player_timers(&crew, &crew, &None);
+    assert_eq!((timers.kills_left, timers.kill_ready_in_secs), (None, None));
+    assert_eq!(timers.tasks_left, 10);
+}
+
+// Each read runs on a fresh match: a failed host call leaves its env unusable.
+fn assert_read_denied(read: impl FnOnce(&Env, Address)) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (_, contract_id, _, players) = start_game_with_verifier(&env);
+    let crew = players.get(1).unwrap();
+    let denied = catch_unwind(AssertUnwindSafe(|| {
+        env.as_contract(&contract_id, || read(&env, crew))
+    }));
+    assert!(panicked_with(&denied.err().unwrap(), ModuleError::ReadGrantRequired));
+}
+
+#[test]
+fn role_revealing_reads_are_denied_without_a_grant() {
+    assert_read_denied(|env, crew| {
+        AmongUsContract::get_impostors_alive(env.clone(), crew, None);
+    });
+    assert_read_denied(|env, crew| {
+        AmongUsContract::get_crew_alive(env.clone(), crew, None);
+    });
+    assert_read_denied(|env, crew| {
+        AmongUsContract::get_vitals(env.clone(), crew, None);
+    });
+    assert_read_denied(|env, crew| {
+        AmongUsContract::get_active_sabotage(env.clone(), crew, None);
+    });
+    assert_read_denied(|env, crew| {
+        AmongUsContract::get_kill_cooldown(env.clone(), crew.clone(), crew, None);
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #48)")]
+fn instant_task_proof_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, players) = start_mapped_game(&env);
+    client.configure_task_duration(&admin, &30);
+
+    let player = players.get(0).unwrap();
+    client.submit_move(&player, &20, &30);
+    client.start_task(&player, &1);
+    env.ledger().with_mut(|l| l.timestamp += 10);
+    let _ = env.as_contract(&contract_id, || {
+        AmongUsContract::submit_task_proof(env.clone(), player.clone(), proof_input(&env, 1))
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #48)")]
+fn long_tasks_need_their_kind_duration() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, players) = start_mapped_game(&env);
+    client.configure_task_kind_duration(&admin, &symbol_short!("short"), &5);
+    client.configure_task_kind_duration(&admin, &symbol_short!("long"), &60);
+
+    let player = players.get(0).unwrap();
+    client.submit_move(&player, &20, &30);
+    client.start_task(&player, &1);
+    env.ledger().with_mut(|l| l.timestamp += 5);
+    client.submit_task_proof(&player, &proof_input(&env, 1));
+
+    client.start_task(&player, &2);
+    env.ledger().with_mut(|l| l.timestamp += 30);
+    env.as_contract(&contract_id, || {
+        AmongUsContract::finish_task(&env, &player);
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #60)")]
+fn emergency_button_requires_location() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (_, contract_id, _, players) = start_mapped_game(&env);
+    let _ = env.as_contract(&contract_id, || {
+        AmongUsContract::start_meeting(env.clone(), players.get(0).unwrap())
+    });
+}
+
+#[test]
+fn emergency_button_is_single_use_per_round() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, players) = start_mapped_game(&env);
+    let caller = players.get(0).unwrap();
+    client.submit_move(&caller, &50, &40);
+    client.start_meeting(&caller);
+    let state = client.get_game_state();
+    assert_eq!(state.button_used_round, state.round);
+    client.end_meeting(&admin);
+
+    assert_eq!(client.try_start_meeting(&caller), Err(Ok(GameError::ButtonUsed)));
+}
+
+#[test]
+fn nearby_players_filters_by_radius() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, players) = start_game_with_verifier(&env);
+    client.submit_move(&players.get(0).unwrap(), &10, &10);
+    client.submit_move(&players.get(1).unwrap(), &13, &14);
+    client.submit_move(&players.get(2).unwrap(), &14, &14);
+    client.submit_move(&players.get(3).unwrap(), &90, &90);
+
+    let nearby = client.get_nearby_players(&10, &10, &5);
+    assert_eq!(nearby.len(), 2);
+    let second = BytesN::from_array(&env, &[22; 32]);
+    assert!(nearby
+        .iter()
+        .any(|p| p.player_hash == second && (p.x, p.y) == (13, 14)));
+    assert!(client.get_nearby_players(&50, &50, &5).is_empty());
+}
+
+#[test]
+fn packed_positions_follow_get_players_order() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, players) = start_game_with_verifier(&env);
+    client.submit_move(&players.get(1).unwrap(), &258, &7);
+    let (killer, victim) = (players.get(0).unwrap(), players.get(1).unwrap());
+    client.submit_kill_proof(&killer, &victim, &proof_input(&env, 90), &0);
+
+    let packed = client.get_positions_packed();
+    let roster = client.get_players();
+    assert_eq!(packed.len(), roster.len() * 9);
+    let seat = roster.keys().first_index_of(victim).unwrap();
+    let mut record = [0u8; 9];
+    packed.slice(seat * 9..seat * 9 + 9).copy_into_slice(&mut record);
+    assert_eq!(record, [0, 0, 1, 2, 0, 0, 0, 7, 0]);
+}
+
+#[cfg(feature = "fog-of-war")]
+fn start_fog_game<'a>(
+    env: &Env,
+) -> (AmongUsContractClient<'a>, Address, Address, Vec<Address>) {
+    let verifier_id = env.register_contract(None, MockVerifier);
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.init(&admin, &1);
+    client.set_verifier(&admin, &verifier_id);
+    client.configure_fog(&admin, &true, &5);
+    let players = join_four_players(env, &client);
+    for (i, player) in players.iter().enumerate() {
+        client.commit_spawn(&player, &BytesN::from_array(env, &[100 + i as u8; 32]));
+    }
+    client.start_game(&admin);
+    (client, contract_id, admin, players)
+}
+
+#[cfg(feature = "fog-of-war")]
+#[test]
+fn fog_moves_replace_commitments_and_kills_bind_them() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, players) = start_fog_game(&env);
+    let killer = players.get(0).unwrap();
+    let victim = players.get(1).unwrap();
+
+    let moved = BytesN::from_array(&env, &[150; 32]);
+    client.submit_fog_move(&killer, &moved, &proof_input(&env, 1));
+    assert_eq!(client.get_position_commitment(&killer), Some(moved.clone()));
+
+    let mut proof = proof_input(&env, 2);
+    proof.public_inputs.push_back(moved);
+    proof
+        .public_inputs
+        .push_back(BytesN::from_array(&env, &[101; 32]));
+    client.submit_kill_proof(&killer, &victim, &proof, &0);
+    assert!(!client.get_players().get(victim).unwrap().alive);
+}
+
+#[cfg(feature = "fog-of-war")]
+#[test]
+#[should_panic(expected = "Error(Contract, #65)")]
+fn fog_kill_without_adjacency_inputs_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (_, contract_id, _, players) = start_fog_game(&env);
+    let _ = env.as_contract(&contract_id, || {
+        AmongUsContract::submit_kill_proof(
+            env.clone(),
+            players.get(0).unwrap(),
+            players.get(1).unwrap(),
+            proof_input(&env, 2),
+            0,
+        )
+    });
+}
+
+#[cfg(feature = "fog-of-war")]
+#[test]
+#[should_panic(expected = "Error(Contract, #64)")]
+fn fog_mode_rejects_public_moves() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (_, contract_id, _, players) = start_fog_game(&env);
+    let _ = env.as_contract(&contract_id, || {
+        AmongUsContract::submit_move(env.clone(), players.get(0).unwrap(), 1, 1)
+    });
+}
+
+#[cfg(feature = "fog-of-war")]
+#[test]
+fn fog_decoy_updates_public_position() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, players) = start_fog_game(&env);
+    client.configure_decoy_radius(&admin, &3);
+
+    let player = players.get(0).unwrap();
+    client.publish_decoy(&player, &12, &7, &proof_input(&env, 3));
+    let entry = client.get_players().get(player).unwrap();
+    assert_eq!((entry.x, entry.y), (12, 7));
+}
+
+#[cfg(feature = "anonymous-voting")]
+#[test]
+fn anonymous_meeting_needs_no_caller_and_burns_nullifier() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, _) = start_game_with_verifier(&env);
+    client.configure_anonymous_meetings(&admin, &true);
+
+    client.start_meeting_anon(&proof_input(&env, 9));
+    let state = client.get_game_state();
+    assert_eq!(state.phase, symbol_short!("meeting"));
+    assert_eq!(state.round, 2);
+    client.end_meeting(&admin);
+
+    let burned = env.as_contract(&contract_id, || {
+        env.storage()
+            .instance()
+            .has(&DataKey::UsedNullifier(BytesN::from_array(&env, &[9; 32])))
+    });
+    assert!(burned);
+}
+
+#[cfg(feature = "anonymous-voting")]
+#[test]
+fn suspicion_signals_build_an_anonymous_heat_map() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, players) = start_game_with_verifier(&env);
+    client.configure_suspicion_signals(&admin, &true);
+    client.start_meeting(&players.get(0).unwrap());
+    let round = client.get_game_state().round;
+    let signal = |target: u8, nullifier: u8| VoteInput {
+        target_hash: BytesN::from_array(&env, &[target; 32]),
+        proof: Bytes::from_array(&env, &[8; 32]),
+        nullifier: BytesN::from_array(&env, &[nullifier; 32]),
+    };
+
+    assert_eq!(client.signal_suspicion(&signal(3, 50)), 1);
+    assert_eq!(client.signal_suspicion(&signal(3, 51)), 2);
+    assert_eq!(client.signal_suspicion(&signal(4, 52)), 1);
+    let heat = client.get_suspicion_heat(&round);
+    assert_eq!(heat.get(BytesN::from_array(&env, &[3; 32])), Some(2));
+    assert_eq!(heat.get(BytesN::from_array(&env, &[4; 32])), Some(1));
+    // Signals never touch the formal tally.
+    assert!(client.get_players().values().iter().all(|p| p.voted_for_hash == [0; 32]));
+
+    assert_eq!(
+        client.try_signal_suspicion(&signal(4, 50)),
+        Err(Ok(GameError::NullifierUsed))
+    );
+}
+
+#[test]
+fn meeting_close_repairs_drifted_side_counts() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, players) = start_game_with_verifier(&env);
+    let p1 = players.get(0).unwrap();
+    client.submit_kill_proof(&p1, &players.get(1).unwrap(), &proof_input(&env, 2), &0);
+    assert_eq!(sides_alive(&env, &contract_id), (1, 2));
+
+    env.as_contract(&contract_id, || {
+        env.storage().instance().set(&DataKey::CrewAlive, &3u32)
+    });
+    client.start_meeting(&p1);
+    client.end_meeting(&admin);
+
+    assert_eq!(sides_alive(&env, &contract_id), (1, 2));
+    let drift = env
+        .events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| {
+            Symbol::try_from_val(&env, &topics.get(0).unwrap()).ok() == Some(symbol_short!("drift"))
+        })
+        .map(|(_, _, data)| <(u32, u32)>::try_from_val(&env, &data).unwrap())
+        .collect::<std::vec::Vec<_>>();
+    assert_eq!(drift, std::vec![(3, 2)]);
+}
+
+#[test]
+fn meeting_close_ends_match_once_tasks_meet_target() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, players) = start_game_with_verifier(&env);
+    let p1 = players.get(0).unwrap();
+    client.submit_task_proof(&p1, &proof_input(&env, 1));
+    client.start_meeting(&p1);
+    client.configure_game(&admin, &10, &1);
+    client.end_meeting(&admin);
+
+    let state = client.get_game_state();
+    assert_eq!((state.phase, state.winner), (PHASE_ENDED, WINNER_CREW));
+}
+
+#[test]
+fn round_timeline_orders_actions_with_timestamps() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, players) = start_game_with_verifier(&env);
+    let p1 = players.get(0).unwrap();
+    client.submit_move(&p1, &3, &4);
+    env.ledger().with_mut(|l| l.timestamp += 12);
+    client.submit_kill_proof(&p1, &players.get(1).unwrap(), &proof_input(&env, 1), &0);
+    client.start_meeting(&p1);
+
+    let round_one = client.get_round_timeline(&1);
+    assert_eq!(round_one.len(), 2);
+    let moved = round_one.get(0).unwrap();
+    let killed = round_one.get(1).unwrap();
+    assert_eq!((moved.action_seq, moved.kind), (1, symbol_short!("move")));
+    assert_eq!(killed.kind, symbol_short!("kill"));
+    assert_eq!(killed.subject_hash, BytesN::from_array(&env, &[22; 32]));
+    assert_eq!(killed.timestamp - moved.timestamp, 12);
+
+    let round_two = client.get_round_timeline(&2);
+    assert_eq!(round_two.get(0).unwrap().kind, symbol_short!("meeting"));
+}
+
+#[test]
+fn receipts_match_the_timeline_entry_they_created() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, players) = start_game_with_verifier(&env);
+    let p1 = players.get(0).unwrap();
+    let moved = client.submit_move(&p1, &3, &4);
+    env.ledger().with_mut(|l| l.timestamp += 5);
+    let tasked = client.submit_task_proof(&p1, &proof_input(&env, 1));
+    let meeting = client.start_meeting(&p1);
+
+    let entry = client.get_round_timeline(&1).get(1).unwrap();
+    assert_eq!((tasked.action_seq, tasked.round), (entry.action_seq, 1));
+    assert_eq!(tasked.timestamp, entry.timestamp);
+    assert_eq!(tasked.timestamp - moved.timestamp, 5);
+    assert_eq!((meeting.action_seq, meeting.round), (3, 2));
+}
+
+#[test]
+fn archived_replays_verify_against_the_events_root() {
+    use soroban_sdk::xdr::ToXdr;
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, players) = start_game_with_verifier(&env);
+    let p1 = players.get(0).unwrap();
+    client.submit_move(&p1, &3, &4);
+    client.submit_task_proof(&players.get(1).unwrap(), &proof_input(&env, 1));
+    client.start_meeting(&p1);
+
+    // An archive that kept every entry folds to the chain's root.
+    let mut root = BytesN::from_array(&env, &[0; 32]);
+    for round in 0..=client.get_game_state().round {
+        for entry in client.get_round_timeline(&round).iter() {
+            let mut preimage = Bytes::from_array(&env, &root.to_array());
+            preimage.append(&(round, entry).to_xdr(&env));
+            root = env.crypto().sha256(&preimage);
+        }
+    }
+    assert_eq!(root, client.get_events_root());
+    let stand_in = BytesN::from_array(&env, &[5; 32]);
+    assert!(!client.verify_replay(&root, &stand_in));
+
+    client.end_game_admin(&admin, &symbol_short!("crew"));
+    let final_hash = GameResultClient::new(&env, &contract_id).final_report_hash().unwrap();
+    assert!(client.verify_replay(&root, &final_hash));
+    assert!(!client.verify_replay(&stand_in, &final_hash));
+    assert!(!client.verify_replay(&root, &stand_in));
+}
+
+#[test]
+fn timeline_depth_is_configurable_up_to_its_ceiling() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, players) = start_game_with_verifier(&env);
+    client.configure_timeline_depth(&admin, &2);
+    let p1 = players.get(0).unwrap();
+    for x in 1..=3u32 {
+        client.submit_move(&p1, &x, &4);
+    }
+    let timeline = client.get_round_timeline(&1);
+    assert_eq!(timeline.len(), 2);
+    assert_eq!(timeline.get(0).unwrap().action_seq, 2);
+
+    let unbounded = catch_unwind(AssertUnwindSafe(|| {
+        env.as_contract(&contract_id, || {
+            AmongUsContract::configure_timeline_depth(env.clone(), admin.clone(), 257)
+        })
+    }));
+    assert!(panicked_with(&unbounded.err().unwrap(), GameError::InvalidConfig));
+}
+
+#[test]
+fn gc_keeps_only_the_report_after_retention() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, players) = start_game_with_verifier(&env);
+    client.configure_retention(&admin, &60);
+    client.submit_move(&players.get(0).unwrap(), &3, &4);
+    client.submit_task_proof(&players.get(1).unwrap(), &proof_input(&env, 7));
+    client.end_game_admin(&admin, &symbol_short!("crew"));
+    env.ledger().with_mut(|l| l.timestamp += 60);
+
+    let report_hash = client.gc();
+    assert_eq!(client.get_report_hash(), Some(report_hash));
+    assert_eq!(client.get_match_report().unwrap().players, 4);
+    assert!(client.get_players().is_empty());
+    assert!(client.get_round_timeline(&1).is_empty());
+    assert!(client.get_cost_report().is_empty());
+    let spent = env.as_contract(&contract_id, || {
+        env.storage()
+            .instance()
+            .has(&DataKey::UsedNullifier(BytesN::from_array(&env, &[7; 32])))
+    });
+    assert!(!spent);
+
+    let again = catch_unwind(AssertUnwindSafe(|| {
+        env.as_contract(&contract_id, || AmongUsContract::gc(env.clone()))
+    }));
+    assert!(panicked_with(&again.err().unwrap(), ModuleError::AlreadyCollected));
+}
+
+#[cfg(feature = "economy")]
+#[test]
+fn gc_sends_unclaimed_stake_to_the_treasury() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, stake_token, players) = staked_game(&env);
+    let treasury = Address::generate(&env);
+    client.configure_rake(&admin, &treasury, &1_000, &0);
+    client.configure_retention(&admin, &60);
+    client.end_game_admin(&admin, &symbol_short!("crew"));
+    let proof = payout_proof(&env, &contract_id, 1, symbol_short!("crew"));
+    assert_eq!(client.claim_payout(&players.get(0).unwrap(), &proof), 36);
+    env.ledger().with_mut(|l| l.timestamp += 60);
+
+    client.gc();
+    // The rake plus the two shares nobody claimed.
+    assert_eq!(token::Client::new(&env, &stake_token).balance(&treasury), 84);
+    assert_eq!(client.get_escrow_balance(&stake_token), 0);
+}
+
+#[cfg(feature = "economy")]
+#[test]
+#[should_panic(expected = "Error(Contract, #100)")]
+fn gc_waits_for_unclaimed_stake_without_a_treasury() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, _, _) = staked_game(&env);
+    client.configure_retention(&admin, &60);
+    client.end_game_admin(&admin, &symbol_short!("crew"));
+    env.ledger().with_mut(|l| l.timestamp += 60);
+    env.as_contract(&contract_id, || AmongUsContract::gc(env.clone()));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #84)")]
+fn gc_waits_for_the_retention_period() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, _) = start_game_with_verifier(&env);
+    client.end_game_admin(&admin, &symbol_short!("crew"));
+    env.ledger().with_mut(|l| l.timestamp += 60);
+    env.as_contract(&contract_id, || AmongUsContract::gc(env.clone()));
+}
+
+#[test]
+fn delegate_casts_flagged_vote_after_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let verifier_id = env.register_contract(None, MockVerifier);
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.init(&admin, &1);
+    client.set_verifier(&admin, &verifier_id);
+    client.configure_delegation(&admin, &20);
+    let players = join_four_players(&env, &client);
+    let absent = players.get(0).unwrap();
+    let delegate = players.get(1).unwrap();
+    client.delegate_vote(&absent, &delegate);
+    client.start_game(&admin);
+    client.start_meeting(&delegate);
+
+    env.ledger().with_mut(|l| l.timestamp += 20);
+    let target_hash = BytesN::from_array(&env, &[33; 32]);
+    let vote = VoteInput {
+        target_hash: target_hash.clone(),
+        proof: Bytes::from_array(&env, &[8; 32]),
+        nullifier: BytesN::from_array(&env, &[5; 32]),
+    };
+    client.submit_delegated_vote(&delegate, &absent, &vote);
+
+    assert_eq!(client.get_players().get(absent.clone()).unwrap().voted_for_hash, target_hash);
+    let flagged = client.get_delegated_votes(&2);
+    assert_eq!(flagged.len(), 1);
+    assert_eq!(flagged.get(0).unwrap(), absent);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #98)")]
+fn delegate_cannot_vote_before_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let verifier_id = env.register_contract(None, MockVerifier);
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.init(&admin, &1);
+    client.set_verifier(&admin, &verifier_id);
+    client.configure_delegation(&admin, &20);
+    let players = join_four_players(&env, &client);
+    client.delegate_vote(&players.get(0).unwrap(), &players.get(1).unwrap());
+    client.start_game(&admin);
+    client.start_meeting(&players.get(1).unwrap());
+
+    let vote = VoteInput {
+        target_hash: BytesN::from_array(&env, &[33; 32]),
+        proof: Bytes::from_array(&env, &[8; 32]),
+        nullifier: BytesN::from_array(&env, &[5; 32]),
+    };
+    let _ = env.as_contract(&contract_id, || {
+        AmongUsContract::submit_delegated_vote(
+            env.clone(),
+            players.get(1).unwrap(),
+            players.get(0).unwrap(),
+            vote,
+        )
+    });
+}
+
+#[test]
+fn match_pause_freezes_task_duration_clock() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, players) = start_mapped_game(&env);
+    client.configure_task_duration(&admin, &30);
+    let player = players.get(0).unwrap();
+    client.submit_move(&player, &20, &30);
+    client.start_task(&player, &1);
+
+    env.ledger().with_mut(|l| l.timestamp += 10);
+    client.pause_match(&admin);
+    env.ledger().with_mut(|l| l.timestamp += 600);
+    client.resume_match(&admin);
+    env.ledger().with_mut(|l| l.timestamp += 10);
+    assert_eq!(client.get_match_time(), 20);
+
+    env.as_contract(&contract_id, || {
+        let started = AmongUsContract::get_task_start(env.clone(), player.clone()).unwrap();
+        assert_eq!(AmongUsContract::match_time(&env) - started.started_at, 20);
+    });
+    env.ledger().with_mut(|l| l.timestamp += 10);
+    client.submit_task_proof(&player, &proof_input(&env, 1));
+}
+
+#[test]
+fn speaker_order_rotates_and_floor_passes_in_order() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, players) = start_game_with_verifier(&env);
+    client.configure_structured_talk(&admin, &true);
+    let hash_of = |player: &Address| client.get_players().get(player.clone()).unwrap().player_hash;
+    let holder = |hash: &BytesN<32>| players.iter().find(|p| hash_of(p) == *hash).unwrap();
+
+    client.start_meeting(&players.get(0).unwrap());
+    let first = client.get_game_state().round;
+    let order = client.get_speaker_order(&first);
+    assert_eq!(order.len(), 4);
+    for player in players.iter() {
+        assert!(order.contains(hash_of(&player)));
+    }
+    client.yield_floor(&holder(&order.get(0).unwrap()));
+    assert_eq!(client.get_current_speaker(&first), order.get(1));
+
+    // The next meeting keeps the same cycle but opens one seat later.
+    env.as_contract(&contract_id, || AmongUsContract::note_speaker_order(&env, first + 1));
+    let next = client.get_speaker_order(&(first + 1));
+    for i in 0..4 {
+        assert_eq!(next.get(i), order.get((i + 1) % 4));
+    }
+    let out_of_turn = holder(&order.get(0).unwrap());
+    let rejected = catch_unwind(AssertUnwindSafe(|| {
+        env.as_contract(&contract_id, || AmongUsContract::yield_floor(env.clone(), out_of_turn))
+    }));
+    assert!(panicked_with(&rejected.err().unwrap(), ModuleError::NotYourTurn));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #5)")]
+fn paused_match_rejects_player_actions() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, players) = start_game_with_verifier(&env);
+    client.pause_match(&admin);
+    let _ = env.as_contract(&contract_id, || {
+        AmongUsContract::submit_move(env.clone(), players.get(0).unwrap(), 1, 1)
+    });
+}
+
+#[test]
+fn clock_elapsed_saturates_when_ledger_time_goes_backwards() {
+    assert_eq!(clock::elapsed(100, 130), 30);
+    assert_eq!(clock::elapsed(130, 100), 0);
+}
+
+#[test]
+fn clock_deadline_boundaries() {
+    assert_eq!(clock::deadline(u64::MAX - 1, 10), u64::MAX);
+    assert!(clock::has_elapsed(100, 30, 130));
+    assert!(!clock::has_elapsed(100, 30, 129));
+    assert!(clock::has_elapsed(100, 0, 90));
+    assert!(clock::within_window(100, 30, 130));
+    assert!(!clock::within_window(100, 30, 131));
+    assert!(clock::within_window(u64::MAX, 30, u64::MAX));
+}
+
+#[test]
+fn clock_pauses_stop_and_offset_match_time() {
+    assert_eq!(clock::paused_clock(500, None, 0), 500);
+    assert_eq!(clock::paused_clock(500, None, 120), 380);
+    assert_eq!(clock::paused_clock(900, Some(500), 120), 380);
+    assert_eq!(clock::paused_clock(400, Some(500), 120), 280);
+    assert_eq!(clock::paused_clock(100, None, 120), 0);
+}
+
+fn legacy_player(env: &Env, hash: u8) -> PlayerV1 {
+    PlayerV1 {
+        x: hash as u32,
+        y: 0,
+        alive: true,
+        tasks_done: 2,
+        player_hash: BytesN::from_array(env, &[hash; 32]),
+        role_hash: BytesN::from_array(env, &[hash + 1; 32]),
+        voted_for_hash: BytesN::from_array(env, &[0; 32]),
+        color: symbol_short!("Red"),
+        name: symbol_short!("Old"),
+    }
+}
+
+#[test]
+fn migrate_upgrades_v1_fixture() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let player = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().instance();
+        storage.set(&DataKey::Admin, &admin);
+        storage.set(
+            &DataKey::Config,
+            &GameConfigV1 {
+                max_players: 8,
+                tasks_to_win: 12,
+            },
+        );
+        storage.set(
+            &DataKey::GameState,
+            &GameStateV1 {
+                phase: symbol_short!("playing"),
+                round: 3,
+                meeting_active: false,
+                impostor_count: 2,
+                sabotage_active: false,
+                winner: symbol_short!("none"),
+            },
+        );
+        let mut players = Map::new(&env);
+        players.set(player.clone(), legacy_player(&env, 7));
+        storage.set(&DataKey::Players, &players);
+    });
+    assert_eq!(client.get_storage_version(), 1);
+
+    assert_eq!(client.migrate(&admin), 13);
+    let config = client.get_config();
+    assert_eq!((config.max_players, config.tasks_to_win), (8, 12));
+    assert_eq!(config.max_kills_per_round, 3);
+    let state = client.get_game_state();
+    assert_eq!((state.round, state.impostor_count, state.button_used_round), (3, 2, 0));
+    let migrated = client.get_players().get(player).unwrap();
+    assert_eq!((migrated.x, migrated.tasks_done), (7, 2));
+    assert_eq!(migrated.hat, symbol_short!("none"));
+    assert_eq!(migrated.joined_at, 0);
+}
+
+#[test]
+fn migrate_upgrades_v2_fixture_and_is_idempotent() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.init(&admin, &1);
+    let player = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().instance();
+        storage.remove(&DataKey::StorageVersion);
+        let mut players = Map::new(&env);
+        players.set(player.clone(), legacy_player(&env, 9));
+        storage.set(&DataKey::Players, &players);
+    });
+    assert_eq!(client.get_storage_version(), 2);
+
+    assert_eq!(client.migrate(&admin), 13);
+    assert_eq!(client.migrate(&admin), 13);
+    let migrated = client.get_players().get(player).unwrap();
+    assert_eq!(migrated.player_hash, BytesN::from_array(&env, &[9; 32]));
+    assert_eq!(migrated.skin, symbol_short!("none"));
+}
+
+#[test]
+fn migrate_moves_v3_incident_log_into_paged_entries() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.init(&admin, &1);
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().instance();
+        storage.set(&DataKey::StorageVersion, &3u32);
+        let mut legacy = Vec::new(&env);
+        for round in 0..25u32 {
+            legacy.push_back(IncidentV1 {
+                kind: symbol_short!("pause"),
+                round,
+                timestamp: 100 + u64::from(round),
+            });
+        }
+        storage.set(&DataKey::Incidents, &legacy);
+    });
+
+    assert_eq!(client.migrate(&admin), 13);
+    client.set_phase(&admin, &symbol_short!("meeting"), &IncidentReason::Dispute);
+    assert_eq!(client.get_incident_count(), 26);
+    let first = client.get_incidents(&0);
+    assert_eq!(first.len(), 20);
+    assert_eq!(first.get(0).unwrap().reason, IncidentReason::Unspecified);
+    let rest = client.get_incidents(&(first.last().unwrap().seq + 1));
+    assert_eq!(rest.len(), 6);
+    let forced = rest.last().unwrap();
+    assert_eq!((forced.seq, forced.kind), (25, symbol_short!("force")));
+    assert_eq!(forced.reason, IncidentReason::Dispute);
+}
+
+#[test]
+fn migrate_gives_v4_config_an_unbounded_kill_range() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.init(&admin, &1);
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().instance();
+        storage.set(&DataKey::StorageVersion, &4u32);
+        storage.set(
+            &DataKey::Config,
+            &GameConfigV2 {
+                max_players: 10,
+                tasks_to_win: 20,
+                max_tasks_per_round: 4,
+                max_kills_per_round: 2,
+            },
+        );
+    });
+
+    assert_eq!(client.migrate(&admin), 13);
+    let config = client.get_config();
+    assert_eq!((config.max_players, config.max_kills_per_round), (10, 2));
+    assert_eq!(config.kill_range, 0);
+}
+
+#[test]
+fn migrate_turns_the_kill_cooldown_off_for_v5_configs() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.init(&admin, &1);
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().instance();
+        storage.set(&DataKey::StorageVersion, &5u32);
+        storage.set(
+            &DataKey::Config,
+            &GameConfigV3 {
+                max_players: 10,
+                tasks_to_win: 20,
+                max_tasks_per_round: 4,
+                max_kills_per_round: 2,
+                kill_range: 7,
+            },
+        );
+    });
+
+    assert_eq!(client.migrate(&admin), 13);
+    let config = client.get_config();
+    assert_eq!((config.kill_range, config.kill_cooldown_secs), (7, 0));
+}
+
+#[test]
+fn migrate_moves_v7_timelines_into_temporary_storage() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, players) = start_game_with_verifier(&env);
+    client.submit_move(&players.get(0).unwrap(), &3, &4);
+    env.as_contract(&contract_id, || {
+        let key = TimelineKey::Timeline(1);
+        let timeline: Vec<TimelineEntry> = env.storage().temporary().get(&key).unwrap();
+        env.storage().temporary().remove(&key);
+        env.storage().persistent().set(&key, &timeline);
+        env.storage().instance().set(&DataKey::StorageVersion, &7u32);
+    });
+    assert!(client.get_round_timeline(&1).is_empty());
+
+    assert_eq!(client.migrate(&admin), 13);
+    assert_eq!(client.get_round_timeline(&1).get(0).unwrap().kind, symbol_short!("move"));
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().persistent().has(&TimelineKey::Timeline(1)));
+    });
+}
+
+#[test]
+fn migrate_backfills_repairs_into_v8_match_reports() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, _) = start_game_with_verifier(&env);
+    client.end_game_admin(&admin, &symbol_short!("crew"));
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().instance();
+        let mut fields: Map<Symbol, Val> = storage.get(&DataKey::MatchReport).unwrap();
+        fields.remove(Symbol::new(&env, "repairs"));
+        storage.set(&DataKey::MatchReport, &fields);
+        storage.set(&DataKey::StorageVersion, &8u32);
+    });
+
+    assert_eq!(client.migrate(&admin), 13);
+    assert!(client.get_match_report().unwrap().repairs.is_empty());
+}
+
+#[test]
+fn migrate_converts_the_v10_kill_cooldown_to_match_seconds() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, _) = start_game_with_verifier(&env);
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().instance();
+        let mut fields: Map<Symbol, Val> = storage.get(&DataKey::Config).unwrap();
+        fields.remove(Symbol::new(&env, "kill_cooldown_secs"));
+        fields.set(Symbol::new(&env, "kill_cooldown_ledgers"), 3u32.into_val(&env));
+        storage.set(&DataKey::Config, &fields);
+        storage.set(&DataKey::StorageVersion, &10u32);
+    });
+
+    assert_eq!(client.migrate(&admin), 13);
+    assert_eq!(client.get_config().kill_cooldown_secs, 15);
+}
+
+#[test]
+fn migrate_converts_the_v12_meeting_cooldown_to_match_seconds() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, players) = start_game_with_verifier(&env);
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().instance();
+        storage.set(&EmergencyKey::MeetingCooldown, &4u32);
+        storage.set(&EmergencyKey::MeetingClosedAt, &100u32);
+        storage.set(&DataKey::StorageVersion, &12u32);
+    });
+
+    assert_eq!(client.migrate(&admin), 13);
+    assert_eq!(client.get_meeting_cooldown(), 0);
+    client.start_meeting(&players.get(0).unwrap());
+    client.end_meeting(&admin);
+    assert_eq!(client.get_meeting_cooldown(), 20);
+}
+
+#[cfg(feature = "cosmetics")]
+#[test]
+fn cosmetics_and_join_timestamp_are_stored() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.init(&admin, &1);
+    let player = join_four_players(&env, &client).get(0).unwrap();
+    client.set_cosmetics(&player, &symbol_short!("crown"), &symbol_short!("gold"));
+
+    let entry = client.get_players().get(player).unwrap();
+    assert_eq!((entry.hat, entry.skin), (symbol_short!("crown"), symbol_short!("gold")));
+    assert_eq!((entry.joined_at, entry.last_action_at), (1_000, 1_000));
+    assert_eq!(client.get_storage_version(), 13);
+}
+
+#[test]
+fn capabilities_match_compiled_features() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, AmongUsContract);
+    // Read without a client call, so the committed snapshot holds no feature-dependent
+    // return value and `scripts/test_contract_features.sh` leaves it untouched.
+    let capabilities =
+        env.as_contract(&contract_id, || AmongUsContract::get_capabilities(env.clone()));
+    assert_eq!(
+        capabilities.contains(symbol_short!("economy")),
+        cfg!(feature = "economy")
+    );
+    assert_eq!(
+        capabilities.contains(symbol_short!("cosmetics")),
+        cfg!(feature = "cosmetics")
+    );
+    assert_eq!(
+        capabilities.contains(symbol_short!("anon_vote")),
+        cfg!(feature = "anonymous-voting")
+    );
+    assert_eq!(
+        capabilities.contains(symbol_short!("fog")),
+        cfg!(feature = "fog-of-war")
+    );
+}
+
+#[test]
+fn verifier_lock_is_released_after_each_proof() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, _, players) = start_game_with_verifier(&env);
+    let player = players.get(0).unwrap();
+
+    client.submit_task_proof(&player, &proof_input(&env, 9));
+    client.submit_task_proof(&players.get(1).unwrap(), &proof_input(&env, 10));
+
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().instance().has(&DataKey::VerifierLock));
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #31)")]
+fn verifier_lock_rejects_nested_proof() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (_, contract_id, _, players) = start_game_with_verifier(&env);
+    let player = players.get(0).unwrap();
+
+    env.as_contract(&contract_id, || {
+        env.storage().instance().set(&DataKey::VerifierLock, &true);
+        let _ = AmongUsContract::submit_task_proof(env.clone(), player, proof_input(&env, 9));
+    });
+}
+
+
+fn assert_rejected_without_writes(register: impl Fn(&Env) -> Address, expected: GameError) {
+    let env = &Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, players) = start_game_with_verifier(env);
+    client.set_verifier(&admin, &register(env));
+    let player = players.get(0).unwrap();
+    let proof = proof_input(env, 9);
+
+    if expected == GameError::VerifierFailed {
+        // Verifier failures are coded panics, which only unwind through a direct call.
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            env.as_contract(&contract_id, || {
+                AmongUsContract::submit_task_proof(env.clone(), player.clone(), proof.clone())
+            })
+        }));
+        assert!(panicked_with(&result.expect_err("proof accepted"), expected));
+    } else {
+        assert_eq!(client.try_submit_task_proof(&player, &proof), Err(Ok(expected)));
+    }
+
+    // The failed frame is never committed, so the ledger still holds the pre-call state.
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().instance();
+        assert!(!storage.has(&DataKey::UsedNullifier(proof.nullifier.clone())));
+        assert!(!storage.has(&DataKey::VerifierLock));
+        let entry = AmongUsContract::read_players(env).get(player.clone()).unwrap();
+        assert_eq!(entry.tasks_done, 0);
+    });
+}
+
+#[test]
+fn accepting_verifier_admits_any_proof() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, players) = start_game_with_verifier(&env);
+    let verifier_id = env.register_contract(None, AcceptingVerifier);
+    client.set_verifier(&admin, &verifier_id);
+    let player = players.get(0).unwrap();
+
+    let mut proof = proof_input(&env, 9);
+    proof.proof = Bytes::from_array(&env, &[0; 32]);
+    client.submit_task_proof(&player, &proof);
+
+    assert_eq!(client.get_players().get(player).unwrap().tasks_done, 1);
+}
+
+#[test]
+fn misbehaving_verifiers_leave_no_partial_writes() {
+    assert_rejected_without_writes(
+        |env| env.register_contract(None, RejectingVerifier),
+        GameError::InvalidProof,
+    );
+    assert_rejected_without_writes(
+        |env| env.register_contract(None, FailingVerifier),
+        GameError::VerifierFailed,
+    );
+    assert_rejected_without_writes(
+        |env| env.register_contract(None, GarbageVerifier),
+        GameError::VerifierFailed,
+    );
+}
+
+fn queue_player(env: &Env, client: &AmongUsContractClient<'_>, hash: u8) -> Address {
+    let player = Address::generate(env);
+    client.queue_for_slot(
+        &player,
+        &symbol_short!("Pur"),
+        &symbol_short!("Q"),
+        &BytesN::from_array(env, &[hash; 32]),
+        &BytesN::from_array(env, &[hash; 32]),
+    );
+    player
+}
+
+#[test]
+fn queued_players_fill_slots_freed_before_start() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.init(&admin, &1);
+    client.configure_game(&admin, &4, &3);
+    let players = join_four_players(&env, &client);
+
+    let first = queue_player(&env, &client, 70);
+    let second = queue_player(&env, &client, 71);
+    assert_eq!(client.get_queue_position(&second), Some(2));
+
+    client.leave_game(&players.get(0).unwrap(), &None);
+    assert!(client.get_players().contains_key(first.clone()));
+    assert_eq!(client.get_queue_position(&first), None);
+    assert_eq!(client.get_queue_position(&second), Some(1));
+
+    client.kick_player(&admin, &players.get(1).unwrap(), &None);
+    let roster = client.get_players();
+    assert!(roster.contains_key(second.clone()));
+    assert!(!roster.contains_key(players.get(1).unwrap()));
+    assert_eq!(client.get_join_queue().len(), 0);
+}
+
+#[test]
+fn join_rejections_carry_distinct_codes() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.init(&admin, &1);
+    let players = join_four_players(&env, &client);
+    client.configure_game(&admin, &4, &3);
+    let join = |player: &Address, hash: u8| {
+        client.try_join_game(
+            player,
+            &symbol_short!("Pur"),
+            &symbol_short!("P"),
+            &BytesN::from_array(&env, &[hash; 32]),
+            &BytesN::from_array(&env, &[hash; 32]),
+        )
+    };
+
+    assert_eq!(join(&players.get(0).unwrap(), 90), Err(Ok(GameError::AlreadyJoined)));
+    assert_eq!(join(&Address::generate(&env), 91), Err(Ok(GameError::LobbyFull)));
+    assert_eq!(GameError::LobbyFull as u32, 7);
+}
+
+#[test]
+fn queueing_requires_a_full_lobby() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    client.init(&Address::generate(&env), &1);
+    join_four_players(&env, &client);
+    let queued = client.try_queue_for_slot(
+        &Address::generate(&env),
+        &symbol_short!("Pur"),
+        &symbol_short!("Q"),
+        &BytesN::from_array(&env, &[70; 32]),
+        &BytesN::from_array(&env, &[70; 32]),
+    );
+    assert_eq!(queued, Err(Ok(GameError::LobbyHasOpenSlots)));
+}
+
+#[test]
+fn reserved_slots_skip_the_queue_until_their_deadline() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.init(&admin, &1);
+    client.configure_game(&admin, &4, &3);
+    let join = |player: &Address, hash: u8| {
+        client.try_join_game(
+            player,
+            &COLOR_PALETTE[hash as usize % 12],
+            &symbol_short!("P"),
+            &BytesN::from_array(&env, &[hash; 32]),
+            &BytesN::from_array(&env, &[hash; 32]),
+        )
+    };
+    join(&Address::generate(&env), 60).unwrap().unwrap();
+    join(&Address::generate(&env), 61).unwrap().unwrap();
+    let (streamer, organizer) = (Address::generate(&env), Address::generate(&env));
+    client.reserve_slots(&admin, &vec![&env, streamer.clone(), organizer.clone()], &100);
+
+    let fan = Address::generate(&env);
+    assert_eq!(join(&fan, 62), Err(Ok(GameError::LobbyFull)));
+    client.queue_for_slot(
+        &fan,
+        &symbol_short!("Pur"),
+        &symbol_short!("F"),
+        &BytesN::from_array(&env, &[62; 32]),
+        &BytesN::from_array(&env, &[62; 32]),
+    );
+    join(&streamer, 63).unwrap().unwrap();
+    assert_eq!(client.get_reserved_slots(), vec![&env, organizer.clone()]);
+
+    env.ledger().with_mut(|l| l.timestamp = 100);
+    assert_eq!(client.get_reserved_slots().len(), 0);
+    assert_eq!(client.release_reservations(), 1);
+    assert!(client.get_players().contains_key(fan));
+    assert_eq!(join(&organizer, 64), Err(Ok(GameError::LobbyFull)));
+}
+
+#[cfg(feature = "economy")]
+#[test]
+fn queued_stake_is_held_then_pooled_on_admission() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.init(&admin, &1);
+    client.configure_game(&admin, &4, &3);
+    let stake_token = env.register_stellar_asset_contract(Address::generate(&env));
+    client.configure_economy(
+        &admin,
+        &EconomyConfig {
+            stake_token: stake_token.clone(),
+            stake_amount: 30,
+            reward_token: stake_token.clone(),
+            mint_rewards: false,
+            reward_minter: admin.clone(),
+            reward_per_winner: 0,
+        },
+    );
+    let stake_admin = token::StellarAssetClient::new(&env, &stake_token);
+    let stake = token::Client::new(&env, &stake_token);
+    let players = Vec::from_array(
+        &env,
+        [
+            Address::generate(&env),
+            Address::generate(&env),
+            Address::generate(&env),
+            Address::generate(&env),
+        ],
+    );
+    for (i, player) in players.iter().enumerate() {
+        stake_admin.mint(&player, &30);
+        client.join_game(
+            &player,
+            &COLOR_PALETTE[i],
+            &symbol_short!("P"),
+            &BytesN::from_array(&env, &[10 + i as u8; 32]),
+            &BytesN::from_array(&env, &[i as u8 + 1; 32]),
+        );
+    }
+
+    let waiting = Address::generate(&env);
+    let quitter = Address::generate(&env);
+    stake_admin.mint(&waiting, &30);
+    stake_admin.mint(&quitter, &30);
+    for (player, hash) in [(&waiting, 70u8), (&quitter, 71u8)] {
+        client.queue_for_slot(
+            player,
+            &symbol_short!("Pur"),
+            &symbol_short!("Q"),
+            &BytesN::from_array(&env, &[hash; 32]),
+            &BytesN::from_array(&env, &[hash; 32]),
+        );
+    }
+    assert_eq!(client.get_escrow_balance(&stake_token), 180);
+
+    client.leave_queue(&quitter);
+    assert_eq!(stake.balance(&quitter), 30);
+
+    let leaver = players.get(0).unwrap();
+    client.leave_game(&leaver, &None);
+    assert_eq!(stake.balance(&leaver), 30);
+    assert_eq!(stake.balance(&waiting), 0);
+    assert!(client.get_players().contains_key(waiting));
+    assert_eq!(client.get_escrow_balance(&stake_token), 120);
+}
+
+#[contract]
+pub struct MockProfiles;
+
+#[contractimpl]
+impl MockProfiles {
+    pub fn mint(env: Env, profile_id: u32, owner: Address) {
+        env.storage().instance().set(&profile_id, &owner);
+    }
+
+    pub fn owner_of(env: Env, profile_id: u32) -> Address {
+        env.storage().instance().get(&profile_id).unwrap()
+    }
+}
+
+fn profile_lobby<'a>(env: &Env) -> (AmongUsContractClient<'a>, Address, MockProfilesClient<'a>) {
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.init(&admin, &1);
+    let profiles = MockProfilesClient::new(env, &env.register_contract(None, MockProfiles));
+    client.configure_profiles(&admin, &profiles.address);
+    (client, admin, profiles)
+}
+
+#[test]
+fn profile_follows_token_to_a_rotated_wallet() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, profiles) = profile_lobby(&env);
+    let old_wallet = Address::generate(&env);
+    let new_wallet = Address::generate(&env);
+    profiles.mint(&7, &old_wallet);
+
+    client.join_with_profile(
+        &old_wallet,
+        &7,
+        &symbol_short!("Red"),
+        &symbol_short!("P1"),
+        &BytesN::from_array(&env, &[70; 32]),
+        &BytesN::from_array(&env, &[7; 32]),
+    );
+    assert_eq!(client.get_player_profile(&old_wallet), Some(7));
+
+    client.kick_player(&admin, &old_wallet, &None);
+    profiles.mint(&7, &new_wallet);
+    client.join_with_profile(
+        &new_wallet,
+        &7,
+        &symbol_short!("Red"),
+        &symbol_short!("P1"),
+        &BytesN::from_array(&env, &[70; 32]),
+        &BytesN::from_array(&env, &[7; 32]),
+    );
+    assert_eq!(client.get_profile_player(&7), Some(new_wallet));
+    assert_eq!(client.get_player_profile(&old_wallet), None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #78)")]
+fn join_with_unowned_profile_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, profiles) = profile_lobby(&env);
+    let player = Address::generate(&env);
+    profiles.mint(&7, &Address::generate(&env));
+    let _ = env.as_contract(&client.address, || {
+        AmongUsContract::join_with_profile(
+            env.clone(),
+            player,
+            7,
+            symbol_short!("Red"),
+            symbol_short!("P1"),
+            BytesN::from_array(&env, &[70; 32]),
+            BytesN::from_array(&env, &[7; 32]),
+        )
+    });
+}
+
+#[test]
+fn league_manager_controls_allowlisted_entry() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let league = Address::generate(&env);
+    client.init(&admin, &1);
+    client.configure_allowlist(&admin, &true);
+    client.set_allowlist_manager(&admin, &league);
+
+    let rostered = Address::generate(&env);
+    let dropped = Address::generate(&env);
+    client.allowlist_add(&league, &Vec::from_array(&env, [rostered.clone(), dropped.clone()]));
+    client.allowlist_remove(&league, &Vec::from_array(&env, [dropped.clone()]));
+    client.join_game(
+        &rostered,
+        &symbol_short!("Red"),
+        &symbol_short!("P1"),
+        &BytesN::from_array(&env, &[11; 32]),
+        &BytesN::from_array(&env, &[1; 32]),
+    );
+    assert!(client.get_players().contains_key(rostered));
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        env.as_contract(&contract_id, || {
+            AmongUsContract::join_game(
+                env.clone(),
+                dropped.clone(),
+                symbol_short!("Blu"),
+                symbol_short!("P2"),
+                BytesN::from_array(&env, &[22; 32]),
+                BytesN::from_array(&env, &[2; 32]),
+            )
+        })
+    }));
+    assert!(panicked_with(&result.err().unwrap(), ModuleError::NotAllowlisted));
+}
+
+#[contract]
+pub struct MockPersonhood;
+
+#[contractimpl]
+impl MockPersonhood {
+    pub fn register(env: Env, player: Address, human: BytesN<32>) {
+        env.storage().instance().set(&player, &human);
+    }
+
+    pub fn human_id(env: Env, player: Address) -> Option<BytesN<32>> {
+        env.storage().instance().get(&player)
+    }
+}
+
+fn join_as(env: &Env, client: &AmongUsContractClient<'_>, player: &Address, hash: u8) {
+    client.join_game(
+        player,
+        &COLOR_PALETTE[hash as usize % COLOR_PALETTE.len()],
+        &symbol_short!("P"),
+        &BytesN::from_array(env, &[hash; 32]),
+        &BytesN::from_array(env, &[hash; 32]),
+    );
+}
+
+#[test]
+fn registry_personhood_seats_one_wallet_per_human() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.init(&admin, &1);
+    let registry = MockPersonhoodClient::new(&env, &env.register_contract(None, MockPersonhood));
+    client.configure_personhood(&admin, &Some(PersonhoodGate::Registry(registry.address.clone())));
+
+    let (wallet_a, wallet_b) = (Address::generate(&env), Address::generate(&env));
+    let human = BytesN::from_array(&env, &[5; 32]);
+    registry.register(&wallet_a, &human);
+    registry.register(&wallet_b, &human);
+    join_as(&env, &client, &wallet_a, 11);
+    client.leave_game(&wallet_a, &None);
+    join_as(&env, &client, &wallet_b, 22);
+    assert!(client.get_players().contains_key(wallet_b));
+
+    let second = catch_unwind(AssertUnwindSafe(|| {
+        env.as_contract(&contract_id, || {
+            AmongUsContract::enter_lobby(
+                &env,
+                &wallet_a,
+                symbol_short!("Blu"),
+                symbol_short!("P"),
+                BytesN::from_array(&env, &[11; 32]),
+                Some(BytesN::from_array(&env, &[11; 32])),
+            )
+        })
+    }));
+    assert!(panicked_with(&second.err().unwrap(), ModuleError::IdentityInUse));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #76)")]
+fn credential_personhood_blocks_a_second_wallet() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.init(&admin, &1);
+    client.set_verifier(&admin, &env.register_contract(None, MockVerifier));
+    client.configure_personhood(&admin, &Some(PersonhoodGate::Credential));
+
+    let wallet_a = Address::generate(&env);
+    client.attest_personhood(&wallet_a, &proof_input(&env, 60));
+    join_as(&env, &client, &wallet_a, 11);
+    assert!(client.get_players().contains_key(wallet_a));
+
+    let wallet_b = Address::generate(&env);
+    let _ = env.as_contract(&contract_id, || {
+        AmongUsContract::attest_personhood(env.clone(), wallet_b, proof_input(&env, 60))
+    });
+}
+
+#[contract]
+pub struct MockAnalyticsSink;
+
+#[contractimpl]
+impl MockAnalyticsSink {
+    pub fn record_match(env: Env, game: Address, analytics: MatchAnalytics) {
+        env.storage().instance().set(&game, &analytics);
+    }
+
+    pub fn last(env: Env, game: Address) -> Option<MatchAnalytics> {
+        env.storage().instance().get(&game)
+    }
+}
+
+#[test]
+fn match_end_reports_analytics_to_the_sink() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, players) = start_game_with_verifier(&env);
+    let sink_id = env.register_contract(None, MockAnalyticsSink);
+    let sink = MockAnalyticsSinkClient::new(&env, &sink_id);
+    client.set_analytics_sink(&admin, &Some(sink_id));
+    let (killer, victim) = (players.get(0).unwrap(), players.get(1).unwrap());
+    client.submit_kill_proof(&killer, &victim, &proof_input(&env, 1), &0);
+    client.submit_task_proof(&players.get(2).unwrap(), &proof_input(&env, 2));
+    client.start_meeting(&players.get(2).unwrap());
+    client.end_meeting(&admin);
+    client.end_game_admin(&admin, &symbol_short!("impost"));
+
+    let analytics = sink.last(&contract_id).unwrap();
+    assert!(!analytics.crew_won);
+    assert_eq!((analytics.rounds, analytics.players, analytics.impostors), (2, 4, 1));
+    assert_eq!(analytics.kills_per_round, vec![&env, 1, 0]);
+    assert_eq!((analytics.tasks_done, analytics.task_completion_bps), (1, 250));
+}
+
+#[test]
+fn failing_analytics_sink_does_not_block_match_end() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, _) = start_game_with_verifier(&env);
+    let sink = env.register_contract(None, MockProfiles);
+    client.set_analytics_sink(&admin, &Some(sink));
+    client.end_game_admin(&admin, &symbol_short!("crew"));
+    assert_eq!(client.get_game_state().phase, symbol_short!("ended"));
+}
+
+#[test]
+fn kill_flavor_is_allowlisted_and_lands_in_the_report() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, players) = start_game_with_verifier(&env);
+    client.configure_kill_flavors(&admin, &vec![&env, 7, 8]);
+    let (killer, victim) = (players.get(0).unwrap(), players.get(1).unwrap());
+    client.submit_kill_proof(&killer, &victim, &proof_input(&env, 1), &7);
+    let (_, _, data) = env.events().all().last().unwrap();
+    let (killed, flavor): (Address, u32) = data.into_val(&env);
+    assert_eq!((killed, flavor), (victim, 7));
+    client.end_game_admin(&admin, &symbol_short!("impost"));
+    assert_eq!(client.get_match_report().unwrap().kill_flavors, vec![&env, 7]);
+
+    let unlisted = catch_unwind(AssertUnwindSafe(|| {
+        env.as_contract(&contract_id, || {
+            AmongUsContract::note_kill_flavor(&env, 9);
+        })
+    }));
+    assert!(panicked_with(&unlisted.err().unwrap(), ModuleError::FlavorNotAllowed));
+}
+
+#[test]
+fn ghost_messages_from_the_dead_are_logged_for_after_the_match() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, players) = start_game_with_verifier(&env);
+    let ghost = players.get(1).unwrap();
+    client.submit_kill_proof(&players.get(0).unwrap(), &ghost, &proof_input(&env, 1), &0);
+    let first = BytesN::from_array(&env, &[1; 32]);
+    client.ghost_message(&ghost, &first);
+    env.ledger().with_mut(|l| l.timestamp += 5);
+    client.ghost_message(&ghost, &BytesN::from_array(&env, &[2; 32]));
+    client.end_game_admin(&admin, &symbol_short!("impost"));
+
+    let log = client.get_ghost_messages();
+    assert_eq!(log.len(), 2);
+    let opening = log.get(0).unwrap();
+    assert_eq!((opening.player, opening.message_id, opening.round), (ghost, first, 1));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #33)")]
+fn ghost_messages_are_sealed_during_the_match() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, _, players) = start_game_with_verifier(&env);
+    let ghost = players.get(1).unwrap();
+    client.submit_kill_proof(&players.get(0).unwrap(), &ghost, &proof_input(&env, 1), &0);
+    client.ghost_message(&ghost, &BytesN::from_array(&env, &[1; 32]));
+    env.as_contract(&contract_id, || AmongUsContract::get_ghost_messages(env.clone()));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #67)")]
+fn ghost_messages_are_rate_limited() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, _, players) = start_game_with_verifier(&env);
+    let ghost = players.get(1).unwrap();
+    client.submit_kill_proof(&players.get(0).unwrap(), &ghost, &proof_input(&env, 1), &0);
+    client.ghost_message(&ghost, &BytesN::from_array(&env, &[1; 32]));
+    env.ledger().with_mut(|l| l.timestamp += 4);
+    let again = BytesN::from_array(&env, &[2; 32]);
+    let _ = env.as_contract(&contract_id, || {
+        AmongUsContract::ghost_message(env.clone(), ghost.clone(), again)
+    });
+}
+
+#[test]
+fn taken_color_is_rejected_unless_auto_assignment_is_on() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.init(&admin, &1);
+    client.configure_color_assignment(&admin, &true);
+    for hash in 1..=2u8 {
+        client.join_game(
+            &Address::generate(&env),
+            &symbol_short!("Pur"),
+            &symbol_short!("P"),
+            &BytesN::from_array(&env, &[hash; 32]),
+            &BytesN::from_array(&env, &[hash; 32]),
+        );
+    }
+    let (_, _, data) = env
+        .events()
+        .all()
+        .iter()
+        .find(|(_, topics, _)| {
+            Symbol::try_from_val(&env, &topics.get(0).unwrap()).ok() == Some(symbol_short!("color"))
+        })
+        .unwrap();
+    assert_eq!(Symbol::try_from_val(&env, &data).unwrap(), symbol_short!("Brn"));
+    let mut colors = client.get_players().values().iter().map(|p| p.color);
+    assert!(colors.any(|c| c == symbol_short!("Brn")));
+
+    client.configure_color_assignment(&admin, &false);
+    let taken = catch_unwind(AssertUnwindSafe(|| {
+        env.as_contract(&contract_id, || {
+            AmongUsContract::join_game(
+                env.clone(),
+                Address::generate(&env),
+                symbol_short!("Pur"),
+                symbol_short!("P"),
+                BytesN::from_array(&env, &[3; 32]),
+                BytesN::from_array(&env, &[3; 32]),
+            )
+        })
+    }));
+    assert!(panicked_with(&taken.err().unwrap(), GameError::ColorTaken));
+}
+
+#[test]
+fn opened_player_hash_cannot_be_squatted() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.init(&admin, &1);
+    client.configure_preimage_check(&admin, &true);
+
+    let owner = Address::generate(&env);
+    let squatter = Address::generate(&env);
+    let published = client.open_player_hash(&owner, &BytesN::from_array(&env, &[5; 32]));
+    client.open_player_hash(&squatter, &BytesN::from_array(&env, &[5; 32]));
+    let role = BytesN::from_array(&env, &[1; 32]);
+    client.join_game(&owner, &symbol_short!("Red"), &symbol_short!("P1"), &published, &role);
+    assert_eq!(client.get_players().get(owner).unwrap().player_hash, published);
+
+    let squat = catch_unwind(AssertUnwindSafe(|| {
+        env.as_contract(&contract_id, || {
+            AmongUsContract::ensure_hash_opened(&env, &squatter, &published)
+        })
+    }));
+    assert!(panicked_with(&squat.err().unwrap(), ModuleError::HashNotOpened));
+}
+
+#[contract]
+pub struct MockLeagueRegistry;
+
+#[contractimpl]
+impl MockLeagueRegistry {
+    pub fn claim(env: Env, game: Address, player_hash: BytesN<32>) -> bool {
+        game.require_auth();
+        if env.storage().instance().has(&player_hash) {
+            return false;
+        }
+        env.storage().instance().set(&player_hash, &game);
+        true
+    }
+
+    pub fn release(env: Env, game: Address, player_hash: BytesN<32>) {
+        game.require_auth();
+        if env.storage().instance().get(&player_hash) == Some(game) {
+            env.storage().instance().remove(&player_hash);
+        }
+    }
+
+    pub fn holder(env: Env, player_hash: BytesN<32>) -> Option<Address> {
+        env.storage().instance().get(&player_hash)
+    }
+}
+
+fn ranked_game<'a>(env: &Env, registry: &Address) -> (AmongUsContractClient<'a>, Address) {
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.init(&admin, &1);
+    client.configure_league_registry(&admin, &Some(registry.clone()));
+    (client, admin)
+}
+
+#[test]
+fn league_identity_holds_one_ranked_seat_at_a_time() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let registry_id = env.register_contract(None, MockLeagueRegistry);
+    let registry = MockLeagueRegistryClient::new(&env, &registry_id);
+    let (game_a, _) = ranked_game(&env, &registry.address);
+    let (game_b, _) = ranked_game(&env, &registry.address);
+    let (wallet_a, wallet_b) = (Address::generate(&env), Address::generate(&env));
+    let identity = BytesN::from_array(&env, &[11; 32]);
+
+    join_as(&env, &game_a, &wallet_a, 11);
+    assert_eq!(registry.holder(&identity), Some(game_a.address.clone()));
+    game_a.leave_game(&wallet_a, &None);
+    assert_eq!(registry.holder(&identity), None);
+    join_as(&env, &game_b, &wallet_b, 11);
+    assert_eq!(registry.holder(&identity), Some(game_b.address.clone()));
+
+    let second = catch_unwind(AssertUnwindSafe(|| {
+        env.as_contract(&game_a.address, || {
+            AmongUsContract::enter_lobby(
+                &env,
+                &wallet_a,
+                symbol_short!("Blu"),
+                symbol_short!("P"),
+                identity.clone(),
+                Some(identity.clone()),
+            )
+        })
+    }));
+    assert!(panicked_with(&second.err().unwrap(), ModuleError::IdentityInUse));
+}
+
+#[test]
+fn league_identities_are_released_when_the_match_ends() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let registry_id = env.register_contract(None, MockLeagueRegistry);
+    let registry = MockLeagueRegistryClient::new(&env, &registry_id);
+    let (client, admin) = ranked_game(&env, &registry.address);
+    client.set_verifier(&admin, &env.register_contract(None, MockVerifier));
+    join_four_players(&env, &client);
+    client.start_game(&admin);
+    let seated = registry.holder(&BytesN::from_array(&env, &[22; 32]));
+    assert_eq!(seated, Some(client.address.clone()));
+
+    client.end_game_admin(&admin, &symbol_short!("crew"));
+    for hash in [11, 22, 33, 44] {
+        assert_eq!(registry.holder(&BytesN::from_array(&env, &[hash; 32])), None);
+    }
+}
+
+#[test]
+fn missing_verifier_blocks_ranked_matches_and_makes_casual_ones_proofless() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let registry = env.register_contract(None, MockLeagueRegistry);
+    let (ranked, ranked_admin) = ranked_game(&env, &registry);
+    join_four_players(&env, &ranked);
+    assert_eq!(
+        ranked.try_start_game(&ranked_admin),
+        Err(Ok(GameError::VerifierNotConfigured))
+    );
+    assert_eq!(ranked.get_game_state().phase, symbol_short!("lobby"));
+
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let casual = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    casual.init(&admin, &1);
+    let players = join_four_players(&env, &casual);
+    casual.start_game(&admin);
+    let casual_topic: Vec<Val> = (symbol_short!("casual"),).into_val(&env);
+    assert!(env.events().all().iter().any(|(_, topics, _)| topics == casual_topic));
+    assert!(casual.is_casual());
+
+    let mut unchecked = proof_input(&env, 1);
+    unchecked.proof = Bytes::from_array(&env, &[0; 32]);
+    casual.submit_task_proof(&players.get(1).unwrap(), &unchecked);
+    assert_eq!(
+        casual.try_submit_task_proof(&players.get(2).unwrap(), &unchecked),
+        Err(Ok(GameError::NullifierUsed))
+    );
+}
+
+fn casual_game(env: &Env) -> (AmongUsContractClient<'_>, Address, Address) {
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.init(&admin, &1);
+    join_four_players(env, &client);
+    (client, contract_id, admin)
+}
+
+#[test]
+fn casual_mode_needs_nothing_at_stake_and_ends_with_a_verifier() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (staked, _, staked_admin) = casual_game(&env);
+    staked.configure_settlement(&staked_admin, &bond_token(&env, &staked_admin, 500), &100, &60);
+    assert_eq!(
+        staked.try_start_game(&staked_admin),
+        Err(Ok(GameError::VerifierNotConfigured))
+    );
+
+    let (client, _, admin) = casual_game(&env);
+    client.start_game(&admin);
+    assert!(client.is_casual());
+    client.set_verifier(&admin, &env.register_contract(None, MockVerifier));
+    assert!(!client.is_casual());
+}
+
+#[test]
+fn casual_matches_count_ejections_revealed_by_a_signed_finalize() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin) = casual_game(&env);
+    client.start_game(&admin);
+    let seat = |hash: u8| {
+        let players = client.get_players();
+        let hash = BytesN::from_array(&env, &[hash; 32]);
+        players.keys().iter().find(|p| players.get(p.clone()).unwrap().player_hash == hash)
+    };
+    let (killer, victim, last) = (seat(11).unwrap(), seat(22).unwrap(), seat(44).unwrap());
+    // Only a proof vouches for a permissionless reveal, and a casual match checks none.
+    client.start_meeting(&killer);
+    let voters = [killer.clone(), victim.clone(), last.clone()];
+    let first = BytesN::from_array(&env, &[33; 32]);
+    eject_by_vote(&env, &client, &admin, &voters, &first, 10, &None);
+    assert_eq!(
+        client.try_reveal_ejection(&proof_input(&env, 1)),
+        Err(Ok(GameError::CasualMatch))
+    );
+
+    client.submit_kill_proof(&killer, &victim, &proof_input(&env, 2), &0);
+    client.report_body(&killer, &victim);
+    let voters = [killer, last];
+    let target = BytesN::from_array(&env, &[44; 32]);
+    eject_by_vote(&env, &client, &admin, &voters, &target, 20, &Some(proof_input(&env, 3)));
+    assert_eq!(client.get_game_state().winner, symbol_short!("crew"));
+}
+
+#[test]
+fn outsiders_cannot_end_or_settle_a_casual_match() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin) = casual_game(&env);
+    client.start_game(&admin);
+    let outsider = Address::generate(&env);
+    assert_eq!(
+        client.try_submit_impostor_win_proof(&proof_input(&env, 1)),
+        Err(Ok(GameError::CasualMatch))
+    );
+    assert_eq!(client.get_game_state().phase, symbol_short!("playing"));
+
+    assert_eq!(
+        client.try_challenge_result(&outsider, &proof_input(&env, 2)),
+        Err(Ok(GameError::CasualMatch))
+    );
+
+    // The settlement is fixed at the start, so no bond can open one to challenge later.
+    let token_id = bond_token(&env, &admin, 500);
+    let late = catch_unwind(AssertUnwindSafe(|| {
+        env.as_contract(&contract_id, || {
+            AmongUsContract::configure_settlement(env.clone(), admin.clone(), token_id, 100, 60)
+        })
+    }));
+    assert!(panicked_with(&late.err().unwrap(), GameError::WrongPhase));
+}
+
+fn register_vk<'a>(
+    env: &Env,
+    client: &AmongUsContractClient<'a>,
+    admin: &Address,
+    circuit: Symbol,
+    verifier: &Address,
+    tag: u8,
+) -> u32 {
+    client.set_circuit_vk(admin, &circuit, &vk_registration(env, verifier, tag))
+}
+
+fn vk_registration(env: &Env, verifier: &Address, tag: u8) -> VkRegistration {
+    VkRegistration {
+        verifier: verifier.clone(),
+        circuit_hash: BytesN::from_array(env, &[tag; 32]),
+        toolchain: soroban_sdk::String::from_str(env, "nargo-0.36.0"),
+        transcript_hash: BytesN::from_array(env, &[tag + 1; 32]),
+    }
+}
+
+#[test]
+fn circuit_vk_routes_proofs_and_keeps_its_log() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, players) = start_game_with_verifier(&env);
+    let task = symbol_short!("task");
+    assert_eq!(client.get_vk_info(&task), None);
+    let rejecting = env.register_contract(None, RejectingVerifier);
+    let accepting = env.register_contract(None, AcceptingVerifier);
+
+    assert_eq!(register_vk(&env, &client, &admin, task.clone(), &rejecting, 1), 0);
+    assert_eq!(register_vk(&env, &client, &admin, task.clone(), &accepting, 3), 1);
+    let info = client.get_vk_info(&task).unwrap();
+    assert_eq!((info.version, info.verifier), (1, accepting));
+    assert_eq!(info.circuit_hash, BytesN::from_array(&env, &[3; 32]));
+    assert_eq!(client.get_vk_log(&task, &0).unwrap().verifier, rejecting);
+    client.submit_task_proof(&players.get(1).unwrap(), &proof_input(&env, 1));
+
+    // The latest key decides; a key for one circuit never judges another.
+    register_vk(&env, &client, &admin, symbol_short!("kill"), &rejecting, 5);
+    let kill = client.try_submit_kill_proof(
+        &players.get(0).unwrap(),
+        &players.get(2).unwrap(),
+        &proof_input(&env, 2),
+        &0,
+    );
+    assert_eq!(kill, Err(Ok(GameError::InvalidProof)));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #32)")]
+fn circuit_vk_rejects_unknown_circuits() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (_, contract_id, admin, _) = start_game_with_verifier(&env);
+    let verifier = env.register_contract(None, AcceptingVerifier);
+    env.as_contract(&contract_id, || {
+        AmongUsContract::set_circuit_vk(
+            env.clone(),
+            admin,
+            symbol_short!("tsak"),
+            vk_registration(&env, &verifier, 1),
+        )
+    });
+}
+
+fn shadow_verdicts(env: &Env) -> std::vec::Vec<(bool, Option<bool>)> {
+    let shadow = symbol_short!("shadow");
+    env.events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| {
+            Symbol::try_from_val(env, &topics.get(0).unwrap()).ok() == Some(shadow.clone())
+        })
+        .map(|(_, _, data)| <(bool, Option<bool>)>::try_from_val(env, &data).unwrap())
+        .collect()
+}
+
+#[test]
+fn candidate_vk_shadows_without_deciding_until_it_expires() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, players) = start_game_with_verifier(&env);
+    let rejecting = env.register_contract(None, RejectingVerifier);
+    client.set_candidate_vk(
+        &admin,
+        &symbol_short!("task"),
+        &vk_registration(&env, &rejecting, 1),
+        &DAY,
+    );
+    assert_eq!(client.get_candidate_vk(&symbol_short!("task")).unwrap().vk.version, 0);
+
+    client.submit_task_proof(&players.get(1).unwrap(), &proof_input(&env, 1));
+    assert_eq!(shadow_verdicts(&env), std::vec![(true, Some(false))]);
+    assert_eq!(client.get_players().get(players.get(1).unwrap()).unwrap().tasks_done, 1);
+
+    // A broken candidate is logged as a failed call and still never blocks the action.
+    let failing = env.register_contract(None, FailingVerifier);
+    client.set_candidate_vk(
+        &admin,
+        &symbol_short!("task"),
+        &vk_registration(&env, &failing, 3),
+        &DAY,
+    );
+    client.submit_task_proof(&players.get(2).unwrap(), &proof_input(&env, 2));
+    assert_eq!(shadow_verdicts(&env), std::vec![(true, Some(false)), (true, None)]);
+
+    env.ledger().with_mut(|l| l.timestamp += DAY);
+    client.submit_task_proof(&players.get(3).unwrap(), &proof_input(&env, 3));
+    assert_eq!(shadow_verdicts(&env).len(), 2);
+}
+
+const DAY: u64 = 24 * 60 * 60;
+
+#[test]
+fn candidate_vk_promotes_after_shadowing_and_rolls_back() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, players) = start_game_with_verifier(&env);
+    let task = symbol_short!("task");
+    let (accepting, rejecting) = (
+        env.register_contract(None, AcceptingVerifier),
+        env.register_contract(None, RejectingVerifier),
+    );
+    register_vk(&env, &client, &admin, task.clone(), &accepting, 1);
+    client.set_candidate_vk(&admin, &task, &vk_registration(&env, &rejecting, 3), &(2 * DAY));
+
+    env.ledger().with_mut(|l| l.timestamp += DAY);
+    assert_eq!(client.promote_candidate_vk(&admin, &task), 1);
+    assert_eq!(client.get_vk_info(&task).unwrap().verifier, rejecting);
+    assert_eq!(client.get_candidate_vk(&task), None);
+
+    assert_eq!(client.rollback_vk(&admin, &task), 0);
+    assert_eq!(client.get_vk_info(&task).unwrap().verifier, accepting);
+    let promoted = client.get_vk_log(&task, &1).unwrap();
+    assert_eq!(promoted.transcript_hash, BytesN::from_array(&env, &[4; 32]));
+    client.submit_task_proof(&players.get(1).unwrap(), &proof_input(&env, 1));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #32)")]
+fn candidate_vk_cannot_skip_the_shadow_period() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, _) = start_game_with_verifier(&env);
+    let verifier = env.register_contract(None, AcceptingVerifier);
+    // A full day of shadowing is the shortest window a candidate may have.
+    let vk = vk_registration(&env, &verifier, 1);
+    client.set_candidate_vk(&admin, &symbol_short!("vote"), &vk, &DAY);
+    env.as_contract(&contract_id, || {
+        AmongUsContract::set_candidate_vk(env.clone(), admin, symbol_short!("vote"), vk, DAY - 1)
+    });
+}
+
+#[test]
+fn verifier_stats_count_verdicts_per_circuit_and_round() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, players) = start_game_with_verifier(&env);
+    client.submit_task_proof(&players.get(1).unwrap(), &proof_input(&env, 1));
+    client.submit_task_proof(&players.get(2).unwrap(), &proof_input(&env, 2));
+    client.start_meeting(&players.get(2).unwrap());
+    client.end_meeting(&admin);
+
+    // Direct probes return their verdict, so rejections land too.
+    let mut rejected = proof_input(&env, 3);
+    rejected.proof = Bytes::from_array(&env, &[0; 32]);
+    assert!(!client.verify_zk_proof(&rejected.proof, &rejected.public_inputs));
+
+    let stats = client.get_verifier_stats();
+    let task = stats.get(symbol_short!("task")).unwrap();
+    assert_eq!((task.accepted, task.rejected, task.failed), (2, 0, 0));
+    let direct = stats.get(symbol_short!("direct")).unwrap();
+    assert_eq!((direct.accepted, direct.rejected), (0, 1));
+    assert_eq!(client.get_round_verifier_stats(&1).accepted, 2);
+    assert_eq!(client.get_round_verifier_stats(&2).rejected, 1);
+}
+
+#[test]
+fn refused_task_proofs_are_counted_once_reported() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, players) = start_game_with_verifier(&env);
+    let seat = players.get(1).unwrap();
+    let role_hash = client.get_players().get(seat.clone()).unwrap().role_hash;
+    let mut refused = proof_input(&env, 1);
+    refused.proof = Bytes::from_array(&env, &[0; 32]);
+    assert_eq!(
+        client.try_submit_task_proof(&seat, &refused),
+        Err(Ok(GameError::InvalidProof))
+    );
+    // The refused call reverted its own counters.
+    assert!(client.get_verifier_stats().get(symbol_short!("task")).is_none());
+
+    let inputs = vec![&env, role_hash, refused.nullifier.clone()];
+    assert!(!client.report_verification(&symbol_short!("task"), &refused.proof, &inputs));
+    let task = client.get_verifier_stats().get(symbol_short!("task")).unwrap();
+    assert_eq!((task.accepted, task.rejected, task.failed), (0, 1, 0));
+    assert_eq!(client.get_round_verifier_stats(&1).rejected, 1);
+    assert_eq!(client.get_operator_metrics().proof_failures, 1);
+    // Nothing was spent, so the seat can still prove the task.
+    client.submit_task_proof(&seat, &proof_input(&env, 1));
+}
+
+#[test]
+fn cost_report_meters_each_players_writes_and_proofs() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, players) = start_game_with_verifier(&env);
+    let (p1, p2, p3) = (
+        players.get(1).unwrap(),
+        players.get(2).unwrap(),
+        players.get(3).unwrap(),
+    );
+    client.submit_task_proof(&p1, &proof_input(&env, 1));
+    client.submit_task_proof(&p2, &proof_input(&env, 2));
+    client.start_meeting(&p2);
+
+    let report = client.get_cost_report();
+    // The join is metered too, without a proof.
+    assert_eq!(
+        report.get(p1).unwrap(),
+        PlayerCost { actions: 2, writes: 4, proofs: 1, proof_bytes: 32 }
+    );
+    assert_eq!(
+        report.get(p2).unwrap(),
+        PlayerCost { actions: 3, writes: 6, proofs: 1, proof_bytes: 32 }
+    );
+    assert_eq!(report.get(p3).unwrap().proofs, 0);
+}
+
+#[test]
+fn full_proofs_reach_the_verifier_but_only_their_hash_is_logged() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, players) = start_game_with_verifier(&env);
+    let proof = proof_input(&env, 1);
+    client.submit_task_proof(&players.get(1).unwrap(), &proof);
+    let logged = env.events().all().iter().any(|(_, topics, data)| {
+        topics == (symbol_short!("proof"), symbol_short!("task")).into_val(&env)
+            && BytesN::<32>::try_from_val(&env, &data).unwrap()
+                == env.crypto().sha256(&proof.proof)
+    });
+    assert!(logged);
+
+    assert_eq!(client.get_max_proof_len(), 2048);
+    client.configure_max_proof_len(&admin, &16);
+    let oversized = catch_unwind(AssertUnwindSafe(|| {
+        env.as_contract(&contract_id, || {
+            let seat = players.get(2).unwrap();
+            AmongUsContract::submit_task_proof(env.clone(), seat, proof_input(&env, 2))
+        })
+    }));
+    assert!(panicked_with(&oversized.err().unwrap(), ModuleError::ProofTooLarge));
+}
+
+#[test]
+fn operator_metrics_roll_up_proofs_and_todays_joins() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 3 * DAY + 100);
+    let (client, _, _, players) = start_game_with_verifier(&env);
+    client.submit_task_proof(&players.get(1).unwrap(), &proof_input(&env, 1));
+    assert!(!client.verify_zk_proof(&Bytes::from_array(&env, &[0; 32]), &Vec::new(&env)));
+
+    let metrics = client.get_operator_metrics();
+    assert_eq!(metrics.phase, symbol_short!("playing"));
+    assert_eq!((metrics.players, metrics.players_today), (4, 4));
+    assert_eq!((metrics.proofs_verified, metrics.proof_failures), (1, 1));
+
+    env.ledger().with_mut(|l| l.timestamp = 4 * DAY);
+    assert_eq!(client.get_operator_metrics().players_today, 0);
+}
+
+#[test]
+fn derived_nullifier_check_binds_nullifiers_to_their_commitment() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, players) = start_game_with_verifier(&env);
+    client.configure_nullifier_check(&admin, &true);
+    let task = symbol_short!("task");
+    // The submitter's stored role commitment, not an input the prover picks.
+    let commitment = BytesN::from_array(&env, &[2; 32]);
+    let round = client.get_game_state().round;
+    let nullifier = client.derive_nullifier(&commitment, &task, &round, &0);
+    // The layout the off-chain helper hashes: the action is its ScVal::Symbol XDR.
+    let mut preimage = soroban_sdk::Bytes::from(client.get_game_scope());
+    preimage.append(&commitment.clone().into());
+    preimage.extend_from_array(&[0, 0, 0, 15, 0, 0, 0, 4, b't', b'a', b's', b'k']);
+    preimage.extend_from_array(&round.to_be_bytes());
+    preimage.extend_from_array(&0u32.to_be_bytes());
+    assert_eq!(nullifier, env.crypto().sha256(&preimage));
+    assert_ne!(nullifier, client.derive_nullifier(&commitment, &task, &(round + 1), &0));
+    assert_ne!(nullifier, client.derive_nullifier(&commitment, &task, &round, &1));
+    assert_ne!(nullifier, client.derive_nullifier(&commitment, &symbol_short!("kill"), &round, &0));
+
+    let player = players.get(1).unwrap();
+    let mut proof = proof_input(&env, 0);
+    proof.nullifier = nullifier;
+    client.submit_task_proof(&player, &proof);
+    // The round's second task takes the next sequence number.
+    let mut second = proof_input(&env, 1);
+    second.nullifier = client.derive_nullifier(&commitment, &task, &round, &1);
+    client.submit_task_proof(&player, &second);
+
+    // Another player cannot spend a nullifier derived from this seat's commitment.
+    let mut borrowed = proof_input(&env, 7);
+    borrowed.nullifier = client.derive_nullifier(&commitment, &task, &round, &2);
+    let borrowed = catch_unwind(AssertUnwindSafe(|| {
+        env.as_contract(&contract_id, || {
+            AmongUsContract::submit_task_proof(env.clone(), players.get(2).unwrap(), borrowed)
+        })
+    }));
+    assert!(panicked_with(&borrowed.err().unwrap(), ModuleError::NullifierMismatch));
+}
+
+#[test]
+fn derived_vote_nullifiers_let_voters_back_the_same_target() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, admin, players) = start_game_with_verifier(&env);
+    client.configure_nullifier_check(&admin, &true);
+    client.start_meeting(&players.get(0).unwrap());
+    let round = client.get_game_state().round;
+    let target = BytesN::from_array(&env, &[44; 32]);
+
+    for (seat, role) in [(0, 1u8), (1, 2)] {
+        let commitment = BytesN::from_array(&env, &[role; 32]);
+        let vote = VoteInput {
+            target_hash: target.clone(),
+            proof: Bytes::from_array(&env, &[8; 32]),
+            nullifier: client.derive_nullifier(&commitment, &symbol_short!("vote"), &round, &0),
+        };
+        client.submit_vote(&players.get(seat).unwrap(), &vote);
+    }
+    let stored = client.get_players();
+    assert_eq!(stored.get(players.get(0).unwrap()).unwrap().voted_for_hash, target);
+    assert_eq!(stored.get(players.get(1).unwrap()).unwrap().voted_for_hash, target);
+}
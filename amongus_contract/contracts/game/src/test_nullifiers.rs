@@ -0,0 +1,167 @@
+#![cfg(test)]
+extern crate std;
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use super::test::{panicked_with, proof_input, start_game_with_verifier};
+use super::test_verifiers::{
+    ReentrantVerifier, ReentrantVerifierClient, ScopedVerifier, ScopedVerifierClient,
+};
+use super::*;
+use soroban_sdk::{Address, BytesN, Env, Vec};
+
+const NULLIFIER: u8 = 77;
+
+#[derive(Clone, Copy, Debug)]
+enum Submission {
+    Vote,
+    Task,
+    Kill,
+    ImpostorWin,
+}
+
+const ALL_SUBMISSIONS: [Submission; 4] = [
+    Submission::Vote,
+    Submission::Task,
+    Submission::Kill,
+    Submission::ImpostorWin,
+];
+
+fn nullifier_used(env: &Env, contract_id: &Address) -> bool {
+    env.as_contract(contract_id, || {
+        env.storage()
+            .instance()
+            .has(&DataKey::UsedNullifier(BytesN::from_array(
+                env,
+                &[NULLIFIER; 32],
+            )))
+    })
+}
+
+// Moves the game into the phase the submission needs, then submits it as a direct call.
+fn submit(
+    env: &Env,
+    contract_id: &Address,
+    admin: &Address,
+    players: &Vec<Address>,
+    kind: Submission,
+) -> Result<(), GameError> {
+    let client = AmongUsContractClient::new(env, contract_id);
+    if client.get_game_state().phase != PHASE_ENDED {
+        let phase = match kind {
+            Submission::Vote => PHASE_MEETING,
+            _ => PHASE_PLAYING,
+        };
+        client.set_phase(admin, &phase, &IncidentReason::Unspecified);
+    }
+    let proof = proof_input(env, NULLIFIER);
+    env.as_contract(contract_id, || match kind {
+        Submission::Vote => AmongUsContract::submit_vote(
+            env.clone(),
+            players.get(0).unwrap(),
+            VoteInput {
+                target_hash: BytesN::from_array(env, &[44; 32]),
+                proof: proof.proof,
+                nullifier: proof.nullifier,
+            },
+        )
+        .map(|_| ()),
+        Submission::Task => {
+            AmongUsContract::submit_task_proof(env.clone(), players.get(1).unwrap(), proof)
+                .map(|_| ())
+        }
+        Submission::Kill => AmongUsContract::submit_kill_proof(
+            env.clone(),
+            players.get(2).unwrap(),
+            players.get(3).unwrap(),
+            proof,
+            0,
+        )
+        .map(|_| ()),
+        Submission::ImpostorWin => {
+            AmongUsContract::submit_impostor_win_proof(env.clone(), proof)
+        }
+    })
+}
+
+#[test]
+fn nullifier_is_spent_once_across_every_submission_pair() {
+    for first in ALL_SUBMISSIONS {
+        for second in ALL_SUBMISSIONS {
+            // A rejected call leaves the host frame poisoned, so every pair gets a fresh env.
+            let env = Env::default();
+            env.mock_all_auths();
+            let (client, contract_id, admin, players) = start_game_with_verifier(&env);
+
+            assert!(!nullifier_used(&env, &contract_id));
+            submit(&env, &contract_id, &admin, &players, first).unwrap();
+            assert!(
+                nullifier_used(&env, &contract_id),
+                "{first:?} did not spend"
+            );
+
+            let ended = client.get_game_state().phase == PHASE_ENDED;
+            let replay = catch_unwind(AssertUnwindSafe(|| {
+                submit(&env, &contract_id, &admin, &players, second)
+            }));
+            if ended {
+                let payload = replay.expect_err("replay accepted");
+                assert!(
+                    panicked_with(&payload, GameError::GameEnded),
+                    "{first:?} then {second:?}"
+                );
+            } else {
+                assert_eq!(
+                    replay.expect("replay panicked"),
+                    Err(GameError::NullifierUsed),
+                    "{first:?} then {second:?}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn reentrant_verifier_cannot_double_spend() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, contract_id, admin, players) = start_game_with_verifier(&env);
+    let player = players.get(1).unwrap();
+    let verifier_id = env.register_contract(None, ReentrantVerifier);
+    let verifier = ReentrantVerifierClient::new(&env, &verifier_id);
+    verifier.arm(&contract_id, &player);
+    client.set_verifier(&admin, &verifier_id);
+
+    client.submit_task_proof(&player, &proof_input(&env, NULLIFIER));
+
+    assert!(!verifier.reentered());
+    assert!(nullifier_used(&env, &contract_id));
+    assert_eq!(client.get_players().get(player).unwrap().tasks_done, 1);
+}
+
+#[test]
+fn nullifiers_and_proofs_stay_inside_their_deployment() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (game_a, id_a, admin_a, players_a) = start_game_with_verifier(&env);
+    let (game_b, id_b, admin_b, players_b) = start_game_with_verifier(&env);
+    assert_ne!(game_a.get_game_scope(), game_b.get_game_scope());
+    let verifier_id = env.register_contract(None, ScopedVerifier);
+    ScopedVerifierClient::new(&env, &verifier_id).bind(&game_a.get_game_scope());
+    game_a.set_verifier(&admin_a, &verifier_id);
+    game_b.set_verifier(&admin_b, &verifier_id);
+
+    game_a.submit_task_proof(&players_a.get(1).unwrap(), &proof_input(&env, NULLIFIER));
+    assert!(nullifier_used(&env, &id_a));
+    assert!(!nullifier_used(&env, &id_b));
+
+    // The same proof replayed into game B carries B's scope, which A's proof never bound.
+    let replay = env.as_contract(&id_b, || {
+        AmongUsContract::submit_task_proof(
+            env.clone(),
+            players_b.get(1).unwrap(),
+            proof_input(&env, NULLIFIER),
+        )
+    });
+    assert_eq!(replay, Err(GameError::InvalidProof));
+}
@@ -0,0 +1,174 @@
+#![cfg(test)]
+//! Verifier test doubles. Each lives in its own module because every one
+//! exports the same `verify` entrypoint.
+
+pub(crate) use accepting::AcceptingVerifier;
+pub(crate) use crew::{CrewVerifier, CrewVerifierClient};
+pub(crate) use failing::FailingVerifier;
+pub(crate) use garbage::GarbageVerifier;
+pub(crate) use reentrant::{ReentrantVerifier, ReentrantVerifierClient};
+pub(crate) use rejecting::RejectingVerifier;
+pub(crate) use scoped::{ScopedVerifier, ScopedVerifierClient};
+
+mod accepting {
+    use soroban_sdk::{contract, contractimpl, Bytes, BytesN, Env, Vec};
+
+    #[contract]
+    pub struct AcceptingVerifier;
+
+    #[contractimpl]
+    impl AcceptingVerifier {
+        pub fn verify(_env: Env, _proof: Bytes, _public_inputs: Vec<BytesN<32>>) -> bool {
+            true
+        }
+    }
+}
+
+mod rejecting {
+    use soroban_sdk::{contract, contractimpl, Bytes, BytesN, Env, Vec};
+
+    #[contract]
+    pub struct RejectingVerifier;
+
+    #[contractimpl]
+    impl RejectingVerifier {
+        pub fn verify(_env: Env, _proof: Bytes, _public_inputs: Vec<BytesN<32>>) -> bool {
+            false
+        }
+    }
+}
+
+mod failing {
+    use soroban_sdk::{contract, contracterror, contractimpl, Bytes, BytesN, Env, Vec};
+
+    #[contracterror]
+    #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+    #[repr(u32)]
+    pub enum VerifierFault {
+        Broken = 1,
+    }
+
+    // Native test contracts cannot unwind through the host, so a trapping verifier
+    // is modelled as one that fails the call with a contract error.
+    #[contract]
+    pub struct FailingVerifier;
+
+    #[contractimpl]
+    impl FailingVerifier {
+        pub fn verify(
+            _env: Env,
+            _proof: Bytes,
+            _public_inputs: Vec<BytesN<32>>,
+        ) -> Result<bool, VerifierFault> {
+            Err(VerifierFault::Broken)
+        }
+    }
+}
+
+mod garbage {
+    use soroban_sdk::{contract, contractimpl, symbol_short, Bytes, BytesN, Env, Symbol, Vec};
+
+    #[contract]
+    pub struct GarbageVerifier;
+
+    #[contractimpl]
+    impl GarbageVerifier {
+        pub fn verify(_env: Env, _proof: Bytes, _public_inputs: Vec<BytesN<32>>) -> Symbol {
+            symbol_short!("yes")
+        }
+    }
+}
+
+mod reentrant {
+    use crate::ProofInput;
+    use soroban_sdk::{
+        contract, contractimpl, symbol_short, vec, Address, Bytes, BytesN, Env, IntoVal, Symbol,
+        Vec,
+    };
+
+    // Accepts every proof, but first tries to spend the same nullifier again
+    // through the game contract that is calling it, recording whether that worked.
+    #[contract]
+    pub struct ReentrantVerifier;
+
+    #[contractimpl]
+    impl ReentrantVerifier {
+        pub fn arm(env: Env, game: Address, player: Address) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("target"), &(game, player));
+        }
+
+        pub fn reentered(env: Env) -> bool {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("reentered"))
+                .unwrap_or(false)
+        }
+
+        pub fn verify(env: Env, proof: Bytes, public_inputs: Vec<BytesN<32>>) -> bool {
+            let (game, player): (Address, Address) = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("target"))
+                .unwrap();
+            let proof = ProofInput {
+                proof,
+                nullifier: public_inputs.last().unwrap(),
+                public_inputs: Vec::new(&env),
+            };
+            let reentry = env.try_invoke_contract::<crate::Receipt, soroban_sdk::Error>(
+                &game,
+                &Symbol::new(&env, "submit_task_proof"),
+                vec![&env, player.into_val(&env), proof.into_val(&env)],
+            );
+            env.storage()
+                .instance()
+                .set(&symbol_short!("reentered"), &reentry.is_ok());
+            true
+        }
+    }
+}
+
+mod scoped {
+    use soroban_sdk::{contract, contractimpl, symbol_short, Bytes, BytesN, Env, Vec};
+
+    // Accepts a proof only when its leading public input is the game scope it was bound to.
+    #[contract]
+    pub struct ScopedVerifier;
+
+    #[contractimpl]
+    impl ScopedVerifier {
+        pub fn bind(env: Env, scope: BytesN<32>) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("scope"), &scope);
+        }
+
+        pub fn verify(env: Env, _proof: Bytes, public_inputs: Vec<BytesN<32>>) -> bool {
+            public_inputs.get(0) == env.storage().instance().get(&symbol_short!("scope"))
+        }
+    }
+}
+
+mod crew {
+    use soroban_sdk::{contract, contractimpl, symbol_short, Bytes, BytesN, Env, Vec};
+
+    // Stands in for the task circuit's role check: any commitment but the bound impostor's
+    // opens as crew.
+    #[contract]
+    pub struct CrewVerifier;
+
+    #[contractimpl]
+    impl CrewVerifier {
+        pub fn bind(env: Env, impostor: BytesN<32>) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("impostor"), &impostor);
+        }
+
+        pub fn verify(env: Env, _proof: Bytes, public_inputs: Vec<BytesN<32>>) -> bool {
+            public_inputs.get(1) != env.storage().instance().get(&symbol_short!("impostor"))
+        }
+    }
+}
@@ -0,0 +1,128 @@
+#![cfg(all(test, feature = "anonymous-voting"))]
+//! Differential harness: the same vote sequence played through attributed and
+//! anonymous voting must produce the same tally, ejection and outcome.
+
+use super::test::{join_four_players, MockVerifier};
+use super::*;
+extern crate std;
+
+use soroban_sdk::{testutils::Address as _, Address, Bytes, BytesN, Env, Map};
+use std::vec::Vec;
+
+#[derive(Clone, Copy)]
+enum Mode {
+    Attributed,
+    Anonymous,
+}
+
+// Plain values only: host objects from two envs cannot be compared.
+#[derive(Debug, PartialEq)]
+struct Outcome {
+    tally: Vec<([u8; 32], u32)>,
+    alive: Vec<([u8; 32], bool)>,
+    phase: u8,
+    winner: u8,
+}
+
+fn code(symbol: Symbol, known: [Symbol; 3]) -> u8 {
+    known.iter().position(|s| *s == symbol).unwrap() as u8
+}
+
+fn hash(env: &Env, index: u32) -> BytesN<32> {
+    BytesN::from_array(env, &[(index as u8 + 1) * 11; 32])
+}
+
+// `votes` are (voter, target) player indexes; `ejected` is the target put to finalize.
+fn play(mode: Mode, votes: &[(u32, u32)], ejected: u32) -> Outcome {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.init(&admin, &1);
+    client.set_verifier(&admin, &env.register_contract(None, MockVerifier));
+    if let Mode::Anonymous = mode {
+        client.configure_anonymous_voting(&admin, &true);
+    }
+    let players = join_four_players(&env, &client);
+    client.start_game(&admin);
+    client.start_meeting(&players.get(0).unwrap());
+
+    let round = client.get_game_state().round;
+    for (i, (voter, target)) in votes.iter().enumerate() {
+        let vote = VoteInput {
+            target_hash: hash(&env, *target),
+            proof: Bytes::from_array(&env, &[8; 32]),
+            nullifier: BytesN::from_array(&env, &[100 + i as u8; 32]),
+        };
+        match mode {
+            Mode::Attributed => client.submit_vote(&players.get(*voter).unwrap(), &vote),
+            Mode::Anonymous => client.submit_vote_anon(&vote),
+        };
+    }
+
+    let tally: Map<BytesN<32>, u32> = match mode {
+        Mode::Attributed => {
+            let mut tally = Map::new(&env);
+            for (_, p) in client.get_players().iter() {
+                if p.voted_for_hash != BytesN::from_array(&env, &[0; 32]) {
+                    let votes = tally.get(p.voted_for_hash.clone()).unwrap_or(0);
+                    tally.set(p.voted_for_hash, votes + 1);
+                }
+            }
+            tally
+        }
+        Mode::Anonymous => client.get_anon_tally(&round),
+    };
+
+    client.finalize_meeting(&admin, &hash(&env, ejected), &None);
+    let mut alive: Map<BytesN<32>, bool> = Map::new(&env);
+    for (_, p) in client.get_players().iter() {
+        alive.set(p.player_hash, p.alive);
+    }
+    let state = client.get_game_state();
+    Outcome {
+        tally: tally.iter().map(|(h, v)| (h.to_array(), v)).collect(),
+        alive: alive.iter().map(|(h, a)| (h.to_array(), a)).collect(),
+        phase: code(state.phase, [PHASE_PLAYING, PHASE_MEETING, PHASE_ENDED]),
+        winner: code(state.winner, [WINNER_NONE, WINNER_CREW, WINNER_IMPOSTOR]),
+    }
+}
+
+#[test]
+fn anonymous_and_attributed_votes_agree() {
+    let sequences: [(&[(u32, u32)], u32); 4] = [
+        (&[(0, 3), (1, 3), (2, 3)], 3),
+        (&[(0, 3), (1, 2), (2, 3)], 3),
+        (&[(0, 1), (1, 0), (2, 1), (3, 1)], 1),
+        (&[], 2),
+    ];
+    for (votes, ejected) in sequences {
+        let attributed = play(Mode::Attributed, votes, ejected);
+        let anonymous = play(Mode::Anonymous, votes, ejected);
+        assert_eq!(attributed, anonymous);
+    }
+}
+
+#[test]
+fn attributed_vote_rejected_in_anonymous_match() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, AmongUsContract);
+    let client = AmongUsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.init(&admin, &1);
+    client.set_verifier(&admin, &env.register_contract(None, MockVerifier));
+    client.configure_anonymous_voting(&admin, &true);
+    let players = join_four_players(&env, &client);
+    client.start_game(&admin);
+    let voter = players.get(0).unwrap();
+    client.start_meeting(&voter);
+
+    let vote = VoteInput {
+        target_hash: hash(&env, 3),
+        proof: Bytes::from_array(&env, &[8; 32]),
+        nullifier: BytesN::from_array(&env, &[100; 32]),
+    };
+    assert_eq!(client.try_submit_vote(&voter, &vote), Err(Ok(GameError::VotesAnonymous)));
+}
@@ -0,0 +1,177 @@
+use soroban_sdk::{
+    contractimpl, contracttype, xdr::ToXdr, Address, Bytes, BytesN, Env, Map, Symbol, Vec,
+};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::{fail, PHASE_ENDED};
+use crate::{AmongUsContract, AmongUsContractClient, GameError, IGameResult};
+
+const DEFAULT_TIMELINE_DEPTH: u32 = 64;
+// Every entry is rewritten with its round's vector, so the depth is capped.
+const MAX_TIMELINE_DEPTH: u32 = 256;
+// Round timelines sit in temporary storage and lapse about a day after their last write.
+const TIMELINE_TTL_LEDGERS: u32 = 17_280;
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum TimelineKey {
+    ActionSeq,
+    Timeline(u32),
+    CallStats,
+    TimelineDepth,
+    EventsRoot,
+}
+
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+pub struct TimelineEntry {
+    pub action_seq: u32,
+    pub kind: Symbol,
+    pub subject_hash: BytesN<32>,
+    pub timestamp: u64,
+}
+
+// Returned by actions that land on the timeline, so a submitter can match its entry and events.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Receipt {
+    pub action_seq: u32,
+    pub round: u32,
+    pub timestamp: u64,
+}
+
+impl AmongUsContract {
+    fn timeline_depth(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&TimelineKey::TimelineDepth)
+            .unwrap_or(DEFAULT_TIMELINE_DEPTH)
+    }
+
+    pub(crate) fn write_timeline(env: &Env, round: u32, timeline: &Vec<TimelineEntry>) {
+        let key = TimelineKey::Timeline(round);
+        env.storage().temporary().set(&key, timeline);
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, TIMELINE_TTL_LEDGERS, TIMELINE_TTL_LEDGERS);
+    }
+
+    fn round_timeline(env: &Env, round: u32) -> Vec<TimelineEntry> {
+        env.storage()
+            .temporary()
+            .get(&TimelineKey::Timeline(round))
+            .unwrap_or(Vec::new(env))
+    }
+
+    fn call_stats(env: &Env) -> Map<Symbol, u32> {
+        env.storage()
+            .instance()
+            .get(&TimelineKey::CallStats)
+            .unwrap_or(Map::new(env))
+    }
+
+    // A call that fails reverts its count with everything else, so these are successful
+    // calls only.
+    pub(crate) fn note_call(env: &Env, kind: &Symbol) {
+        let mut stats = Self::call_stats(env);
+        stats.set(kind.clone(), stats.get(kind.clone()).unwrap_or(0) + 1);
+        env.storage()
+            .instance()
+            .set(&TimelineKey::CallStats, &stats);
+    }
+
+    fn events_root(env: &Env) -> BytesN<32> {
+        env.storage()
+            .instance()
+            .get(&TimelineKey::EventsRoot)
+            .unwrap_or(BytesN::from_array(env, &[0; 32]))
+    }
+
+    // Chains every entry into one commitment, `root = sha256(root || xdr((round, entry)))`
+    // from a zero root, so it outlives the lapsing round timelines.
+    fn fold_events_root(env: &Env, round: u32, entry: &TimelineEntry) {
+        let mut preimage = Bytes::from_array(env, &Self::events_root(env).to_array());
+        preimage.append(&(round, entry.clone()).to_xdr(env));
+        env.storage()
+            .instance()
+            .set(&TimelineKey::EventsRoot, &env.crypto().sha256(&preimage));
+    }
+
+    // Oldest entries fall off once a round is full; action_seq keeps gaps visible.
+    pub(crate) fn record_action(env: &Env, kind: Symbol, subject_hash: BytesN<32>) -> Receipt {
+        let action_seq: u32 = env
+            .storage()
+            .instance()
+            .get(&TimelineKey::ActionSeq)
+            .unwrap_or(0)
+            + 1;
+        env.storage()
+            .instance()
+            .set(&TimelineKey::ActionSeq, &action_seq);
+
+        Self::note_call(env, &kind);
+        let round = Self::read_state(env).round;
+        let timestamp = Self::ledger_now(env);
+        let mut timeline = Self::round_timeline(env, round);
+        // A lowered depth trims an existing round down on its next write.
+        while timeline.len() >= Self::timeline_depth(env) {
+            timeline.pop_front();
+        }
+        let entry = TimelineEntry {
+            action_seq,
+            kind,
+            subject_hash,
+            timestamp,
+        };
+        Self::fold_events_root(env, round, &entry);
+        timeline.push_back(entry);
+        Self::write_timeline(env, round, &timeline);
+        Receipt {
+            action_seq,
+            round,
+            timestamp,
+        }
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    pub fn configure_timeline_depth(env: Env, caller: Address, depth: u32) {
+        Self::require_operator(&env, &caller);
+        if depth == 0 || depth > MAX_TIMELINE_DEPTH {
+            fail(&env, GameError::InvalidConfig);
+        }
+        env.storage()
+            .instance()
+            .set(&TimelineKey::TimelineDepth, &depth);
+    }
+
+    // Empty once the round's entries have lapsed from temporary storage.
+    pub fn get_round_timeline(env: Env, round: u32) -> Vec<TimelineEntry> {
+        Self::round_timeline(&env, round)
+    }
+
+    pub fn get_events_root(env: Env) -> BytesN<32> {
+        Self::events_root(&env)
+    }
+
+    // Checks an archived replay without trusting whoever served it: folding the archive's
+    // entries must give the chain's events root, and replaying them must end in the final
+    // state the chain committed to, the hash of the match report.
+    pub fn verify_replay(
+        env: Env,
+        events_root: BytesN<32>,
+        claimed_final_state_hash: BytesN<32>,
+    ) -> bool {
+        Self::read_state(&env).phase == PHASE_ENDED
+            && events_root == Self::events_root(&env)
+            && Self::final_report_hash(env) == Some(claimed_final_state_hash)
+    }
+
+    // Successful calls this match by action kind: `join` plus every timeline kind (`move`,
+    // `task`, `kill`, `vote`, `meeting`, `eject`, ...).
+    pub fn get_call_stats(env: Env) -> Map<Symbol, u32> {
+        Self::call_stats(&env)
+    }
+}
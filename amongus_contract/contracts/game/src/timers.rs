@@ -0,0 +1,73 @@
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, BytesN, Env};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::fail;
+use crate::{AmongUsContract, AmongUsContractClient, GameError};
+
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+pub struct PlayerTimers {
+    pub kills_left: Option<u32>,
+    pub tasks_left: u32,
+    pub emergency_meetings_left: u32,
+    pub task_ready_in_secs: u64,
+    pub kill_ready_in_secs: Option<u64>,
+    pub sabotage_ready_in_secs: Option<u64>,
+    pub afk_deadline_in_secs: Option<u64>,
+    pub shield_expires_in_secs: Option<u64>,
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    // Everything a client needs for countdowns in one read. Kill usage would reveal roles,
+    // so reading another player's timers takes an impostor read grant or admin, and a
+    // player's own read leaves the kill and sabotage fields empty without one.
+    pub fn get_player_timers(
+        env: Env,
+        caller: Address,
+        player: Address,
+        grant_salt: Option<BytesN<32>>,
+    ) -> PlayerTimers {
+        let impostor_view = if caller == player {
+            Self::has_read_grant(&env, &caller, grant_salt, symbol_short!("impostor"))
+        } else {
+            Self::ensure_read_grant(&env, &caller, grant_salt, symbol_short!("impostor"));
+            true
+        };
+        if !Self::read_players(&env).contains_key(player.clone()) {
+            fail(&env, GameError::PlayerNotFound);
+        }
+        let state = Self::read_state(&env);
+        let config = Self::read_config(&env);
+        let usage = Self::read_quota(&env, &player, state.round);
+        PlayerTimers {
+            kills_left: impostor_view
+                .then(|| config.max_kills_per_round.saturating_sub(usage.kills)),
+            tasks_left: config.max_tasks_per_round.saturating_sub(usage.tasks),
+            emergency_meetings_left: Self::emergency_meetings_left(
+                &env,
+                &player,
+                state.button_used_round != state.round,
+            ),
+            task_ready_in_secs: Self::task_ready_in(&env, &player),
+            kill_ready_in_secs: impostor_view
+                .then(|| Self::kill_cooldown_left(&env, &config, &player)),
+            sabotage_ready_in_secs: impostor_view.then(|| Self::sabotage_ready_in(&env)),
+            afk_deadline_in_secs: Self::afk_deadline_in(&env, &player),
+            shield_expires_in_secs: Self::shield_expires_in(&env),
+        }
+    }
+
+    // Match seconds until `player` may kill again. A nonzero wait marks a recent killer, so
+    // it takes an impostor read grant or admin even on the caller's own seat.
+    pub fn get_kill_cooldown(
+        env: Env,
+        caller: Address,
+        player: Address,
+        grant_salt: Option<BytesN<32>>,
+    ) -> u64 {
+        Self::ensure_read_grant(&env, &caller, grant_salt, symbol_short!("impostor"));
+        Self::kill_cooldown_left(&env, &Self::read_config(&env), &player)
+    }
+}
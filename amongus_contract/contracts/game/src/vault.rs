@@ -0,0 +1,47 @@
+use soroban_sdk::{contractimpl, contracttype, token, Address, Env};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::constants::fail;
+use crate::{AmongUsContract, AmongUsContractClient, GameError};
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum VaultKey {
+    EscrowBalance(Address),
+}
+
+impl AmongUsContract {
+    pub(crate) fn escrow_balance(env: &Env, token: &Address) -> i128 {
+        env.storage()
+            .instance()
+            .get(&VaultKey::EscrowBalance(token.clone()))
+            .unwrap_or(0)
+    }
+
+    pub(crate) fn escrow_deposit(env: &Env, from: &Address, token: &Address, amount: i128) {
+        token::Client::new(env, token).transfer(from, &env.current_contract_address(), &amount);
+        let balance = Self::escrow_balance(env, token) + amount;
+        env.storage()
+            .instance()
+            .set(&VaultKey::EscrowBalance(token.clone()), &balance);
+    }
+
+    pub(crate) fn escrow_release(env: &Env, to: &Address, token: &Address, amount: i128) {
+        let balance = Self::escrow_balance(env, token);
+        if amount > balance {
+            fail(env, GameError::InsufficientEscrow);
+        }
+        env.storage()
+            .instance()
+            .set(&VaultKey::EscrowBalance(token.clone()), &(balance - amount));
+        token::Client::new(env, token).transfer(&env.current_contract_address(), to, &amount);
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    pub fn get_escrow_balance(env: Env, token: Address) -> i128 {
+        Self::escrow_balance(&env, &token)
+    }
+}
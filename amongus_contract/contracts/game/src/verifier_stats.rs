@@ -0,0 +1,99 @@
+use soroban_sdk::{contractimpl, contracttype, Bytes, BytesN, Env, Map, Symbol, Vec};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::{AmongUsContract, AmongUsContractClient};
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum VerifierStatsKey {
+    CircuitStats,
+    RoundStats(u32),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct VerifierTally {
+    pub accepted: u32,
+    pub rejected: u32,
+    pub failed: u32,
+}
+
+impl VerifierTally {
+    fn note(&mut self, outcome: Option<bool>) {
+        match outcome {
+            Some(true) => self.accepted += 1,
+            Some(false) => self.rejected += 1,
+            None => self.failed += 1,
+        }
+    }
+}
+
+impl AmongUsContract {
+    pub(crate) fn circuit_stats(env: &Env) -> Map<Symbol, VerifierTally> {
+        env.storage()
+            .instance()
+            .get(&VerifierStatsKey::CircuitStats)
+            .unwrap_or(Map::new(env))
+    }
+
+    fn round_stats(env: &Env, round: u32) -> VerifierTally {
+        env.storage()
+            .instance()
+            .get(&VerifierStatsKey::RoundStats(round))
+            .unwrap_or_default()
+    }
+
+    // A rejected or failed proof-gated action reverts together with its counters, so
+    // those land through `report_verification`, which returns the verdict instead.
+    pub(crate) fn note_verification(env: &Env, circuit_id: &Symbol, outcome: Option<bool>) {
+        let mut stats = Self::circuit_stats(env);
+        let mut tally = stats.get(circuit_id.clone()).unwrap_or_default();
+        tally.note(outcome);
+        stats.set(circuit_id.clone(), tally);
+        env.storage()
+            .instance()
+            .set(&VerifierStatsKey::CircuitStats, &stats);
+
+        let round = Self::read_state(env).round;
+        let mut tally = Self::round_stats(env, round);
+        tally.note(outcome);
+        env.storage()
+            .instance()
+            .set(&VerifierStatsKey::RoundStats(round), &tally);
+    }
+
+    pub(crate) fn clear_round_stats(env: &Env, round: u32) {
+        env.storage()
+            .instance()
+            .remove(&VerifierStatsKey::RoundStats(round));
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    pub fn get_verifier_stats(env: Env) -> Map<Symbol, VerifierTally> {
+        Self::circuit_stats(&env)
+    }
+
+    pub fn get_round_verifier_stats(env: Env, round: u32) -> VerifierTally {
+        Self::round_stats(&env, round)
+    }
+
+    /// Replays a proof against the circuit's active verifier and counts the verdict without
+    /// spending anything, so a relayer can record the proof an action just refused.
+    /// `public_inputs` are the ones the action assembled, without the game scope.
+    pub fn report_verification(
+        env: Env,
+        circuit_id: Symbol,
+        proof: Bytes,
+        public_inputs: Vec<BytesN<32>>,
+    ) -> bool {
+        Self::ensure_verifier_enabled(&env);
+        let verifier = Self::circuit_verifier(&env, &circuit_id);
+        let args = Self::verifier_args(&env, &circuit_id, proof, public_inputs);
+        let outcome = Self::invoke_verifier(&env, &verifier, args);
+        Self::note_verification(&env, &circuit_id, outcome);
+        outcome == Some(true)
+    }
+}
@@ -0,0 +1,297 @@
+use soroban_sdk::{
+    contractimpl, contracttype, symbol_short, vec, Address, BytesN, Env, IntoVal, Symbol, Val, Vec,
+};
+
+#[cfg(test)]
+use crate::__AmongUsContract_fn_set_registry;
+use crate::clock;
+use crate::constants::{fail, CIRCUIT_REVEAL, PHASE_PLAYING, WINNER_CREW, WINNER_IMPOSTOR};
+use crate::{
+    AmongUsContract, AmongUsContractClient, DataKey, GameError, GameState, ModuleError, ProofInput,
+};
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum WinKey {
+    GameMode,
+    SurvivalLimit,
+    MatchStart,
+    PendingEjection,
+}
+
+// How long, in match seconds, an unrevealed ejection waits for its reveal before it counts
+// as a crew loss.
+const EJECTION_REVEAL_SECS: u64 = 60;
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub struct PendingEjection {
+    pub role_hash: BytesN<32>,
+    pub round: u32,
+    pub ejected_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum GameMode {
+    Classic = 0,
+    Survival = 1,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum WinCondition {
+    ImpostorsEliminated = 0,
+    TaskThreshold = 1,
+    ImpostorParity = 2,
+    SabotageTimeout = 3,
+    SurvivalTimeout = 4,
+}
+
+// Checked in order and the first that holds decides the match, so a new mode is one more
+// row here rather than another check in every entry point.
+fn mode_conditions(mode: GameMode) -> &'static [WinCondition] {
+    match mode {
+        GameMode::Classic => &[
+            WinCondition::ImpostorsEliminated,
+            WinCondition::TaskThreshold,
+            WinCondition::ImpostorParity,
+            WinCondition::SabotageTimeout,
+        ],
+        GameMode::Survival => &[
+            WinCondition::ImpostorsEliminated,
+            WinCondition::ImpostorParity,
+            WinCondition::SurvivalTimeout,
+        ],
+    }
+}
+
+impl AmongUsContract {
+    fn game_mode(env: &Env) -> GameMode {
+        env.storage()
+            .instance()
+            .get(&WinKey::GameMode)
+            .unwrap_or(GameMode::Classic)
+    }
+
+    // Starts at the configured count; only reveal proofs move it, since roles stay hidden.
+    pub(crate) fn impostors_alive(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::ImpostorsAlive)
+            .unwrap_or_else(|| Self::read_state(env).impostor_count)
+    }
+
+    pub(crate) fn crew_alive(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::CrewAlive)
+            .unwrap_or_else(|| {
+                Self::count_alive(&Self::read_players(env))
+                    .saturating_sub(Self::impostors_alive(env))
+            })
+    }
+
+    pub(crate) fn seed_win_state(env: &Env, state: &GameState, players: u32) {
+        let storage = env.storage().instance();
+        storage.set(&DataKey::ImpostorsAlive, &state.impostor_count);
+        storage.set(
+            &DataKey::CrewAlive,
+            &players.saturating_sub(state.impostor_count),
+        );
+        storage.set(&WinKey::MatchStart, &Self::match_time(env));
+    }
+
+    // Kills and unrevealed ejections both cost the crew a player: a reveal is the only way
+    // an ejected player counts as an impostor.
+    pub(crate) fn note_crew_lost(env: &Env) {
+        let left = Self::crew_alive(env).saturating_sub(1);
+        env.storage().instance().set(&DataKey::CrewAlive, &left);
+    }
+
+    // An ejection without a reveal moves neither count until `reveal_ejection` lands or the
+    // window runs out, so parity is never decided on a seat that may yet prove an impostor.
+    // A newer ejection closes the window on the one before it.
+    pub(crate) fn hold_ejection(env: &Env, role_hash: BytesN<32>, round: u32) {
+        Self::expire_pending_ejection(env, true);
+        env.storage().instance().set(
+            &WinKey::PendingEjection,
+            &PendingEjection {
+                role_hash,
+                round,
+                ejected_at: Self::match_time(env),
+            },
+        );
+    }
+
+    pub(crate) fn ejection_pending(env: &Env) -> bool {
+        env.storage().instance().has(&WinKey::PendingEjection)
+    }
+
+    fn expire_pending_ejection(env: &Env, force: bool) {
+        let Some(pending) = env
+            .storage()
+            .instance()
+            .get::<_, PendingEjection>(&WinKey::PendingEjection)
+        else {
+            return;
+        };
+        let now = Self::match_time(env);
+        if !force && clock::within_window(pending.ejected_at, EJECTION_REVEAL_SECS, now) {
+            return;
+        }
+        env.storage().instance().remove(&WinKey::PendingEjection);
+        Self::note_crew_lost(env);
+    }
+
+    // The contract builds the public inputs from the ejected seat and the meeting round, so
+    // a reveal cannot vouch for any other commitment.
+    pub(crate) fn reveal_ejected_impostor(
+        env: &Env,
+        role_hash: BytesN<32>,
+        round: u32,
+        proof: ProofInput,
+    ) -> Result<(), GameError> {
+        if env
+            .storage()
+            .instance()
+            .has(&DataKey::UsedNullifier(proof.nullifier.clone()))
+        {
+            return Err(GameError::NullifierUsed);
+        }
+        Self::ensure_derived_nullifier(
+            env,
+            CIRCUIT_REVEAL,
+            Some(role_hash.clone()),
+            0,
+            &proof.nullifier,
+        );
+        Self::consume_nullifier(env, proof.nullifier.clone());
+        let public_inputs = vec![
+            env,
+            role_hash,
+            Self::field_input(env, round),
+            proof.nullifier,
+        ];
+        if !Self::verify_circuit(env, CIRCUIT_REVEAL, proof.proof, public_inputs) {
+            return Err(GameError::InvalidProof);
+        }
+        let left = Self::impostors_alive(env).saturating_sub(1);
+        env.storage()
+            .instance()
+            .set(&DataKey::ImpostorsAlive, &left);
+        env.events()
+            .publish((symbol_short!("revealed"), round), left);
+        Ok(())
+    }
+
+    fn evaluate_condition(env: &Env, condition: WinCondition) -> Option<Symbol> {
+        let storage = env.storage().instance();
+        let holds = match condition {
+            WinCondition::ImpostorsEliminated => Self::impostors_alive(env) == 0,
+            WinCondition::TaskThreshold => {
+                Self::total_tasks(&Self::read_players(env)) >= Self::read_config(env).tasks_to_win
+            }
+            WinCondition::ImpostorParity => {
+                !Self::ejection_pending(env) && Self::impostors_alive(env) >= Self::crew_alive(env)
+            }
+            WinCondition::SabotageTimeout => Self::sabotage_overdue(env),
+            WinCondition::SurvivalTimeout => {
+                let limit: u64 = storage.get(&WinKey::SurvivalLimit).unwrap_or(0);
+                let start: u64 = storage.get(&WinKey::MatchStart).unwrap_or(0);
+                limit > 0 && clock::has_elapsed(start, limit, Self::match_time(env))
+            }
+        };
+        if !holds {
+            return None;
+        }
+        match condition {
+            WinCondition::ImpostorParity | WinCondition::SabotageTimeout => Some(WINNER_IMPOSTOR),
+            _ => Some(WINNER_CREW),
+        }
+    }
+
+    // Ends a running match if the mode's conditions say it is decided; `by` tops the
+    // `winner` event.
+    pub(crate) fn settle_win(env: &Env, by: Val) -> bool {
+        if Self::read_state(env).phase != PHASE_PLAYING {
+            return false;
+        }
+        Self::expire_pending_ejection(env, false);
+        for condition in mode_conditions(Self::game_mode(env)) {
+            if let Some(winner) = Self::evaluate_condition(env, *condition) {
+                Self::set_winner(env, winner.clone());
+                env.events().publish((symbol_short!("winner"), by), winner);
+                return true;
+            }
+        }
+        false
+    }
+}
+
+#[contractimpl]
+impl AmongUsContract {
+    // Survival matches are won by the crew once `survival_secs` of match time pass.
+    pub fn configure_game_mode(env: Env, caller: Address, mode: GameMode, survival_secs: u64) {
+        Self::require_operator(&env, &caller);
+        Self::ensure_lobby(&env, false);
+        if mode == GameMode::Survival && survival_secs == 0 {
+            fail(&env, GameError::InvalidConfig);
+        }
+        env.storage().instance().set(&WinKey::GameMode, &mode);
+        env.storage()
+            .instance()
+            .set(&WinKey::SurvivalLimit, &survival_secs);
+    }
+
+    // Settles the last unrevealed ejection as an impostor's. The proof is the authority, so
+    // anyone may submit it while the reveal window is open. A casual match takes its reveals
+    // only through the signed finalize, leave and kick calls.
+    pub fn reveal_ejection(env: Env, proof: ProofInput) -> Result<(), GameError> {
+        Self::ensure_not_ended(&env);
+        Self::ensure_not_paused(&env);
+        Self::ensure_not_casual(&env)?;
+        Self::expire_pending_ejection(&env, false);
+        let pending: PendingEjection = env
+            .storage()
+            .instance()
+            .get(&WinKey::PendingEjection)
+            .unwrap_or_else(|| fail(&env, ModuleError::NothingToReveal));
+        Self::reveal_ejected_impostor(&env, pending.role_hash, pending.round, proof)?;
+        env.storage().instance().remove(&WinKey::PendingEjection);
+        Self::settle_win(&env, symbol_short!("reveal").into_val(&env));
+        Ok(())
+    }
+
+    // Timeouts come due between calls, so anyone may settle them.
+    pub fn check_win_conditions(env: Env) -> bool {
+        Self::ensure_not_paused(&env);
+        Self::settle_win(&env, symbol_short!("check").into_val(&env))
+    }
+
+    pub fn get_game_mode(env: Env) -> GameMode {
+        Self::game_mode(&env)
+    }
+
+    // How close each side is to parity gives away what the impostors know, so the counts
+    // take an impostor read grant or admin.
+    pub fn get_impostors_alive(env: Env, caller: Address, grant_salt: Option<BytesN<32>>) -> u32 {
+        Self::ensure_read_grant(&env, &caller, grant_salt, symbol_short!("impostor"));
+        Self::impostors_alive(&env)
+    }
+
+    pub fn get_crew_alive(env: Env, caller: Address, grant_salt: Option<BytesN<32>>) -> u32 {
+        Self::ensure_read_grant(&env, &caller, grant_salt, symbol_short!("impostor"));
+        Self::crew_alive(&env)
+    }
+
+    pub fn get_win_conditions(env: Env) -> Vec<WinCondition> {
+        let mut conditions = Vec::new(&env);
+        for condition in mode_conditions(Self::game_mode(&env)) {
+            conditions.push_back(*condition);
+        }
+        conditions
+    }
+}
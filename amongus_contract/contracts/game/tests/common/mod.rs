@@ -0,0 +1,43 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// Builds the release WASM with default features and returns its path. A separate
+// target dir keeps the nested build off the outer test's build lock.
+pub fn build_release_wasm() -> PathBuf {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = manifest_dir.join("../../target/wasm-size");
+
+    let status = Command::new(env!("CARGO"))
+        .current_dir(&manifest_dir)
+        .args([
+            "build",
+            "--release",
+            "--target",
+            "wasm32-unknown-unknown",
+            "-p",
+            "game",
+            "--target-dir",
+        ])
+        .arg(&target_dir)
+        .status()
+        .expect("failed to run cargo");
+    assert!(status.success(), "release wasm build failed");
+
+    target_dir.join("wasm32-unknown-unknown/release/game.wasm")
+}
+
+// Runs `wasm-opt -Oz` over `wasm`, the same pass `stellar contract optimize` applies before
+// upload. Needs `wasm-opt` on PATH (binaryen, or `cargo install wasm-opt`).
+#[allow(dead_code)]
+pub fn optimize_wasm(wasm: &Path) -> PathBuf {
+    let optimized = wasm.with_extension("optimized.wasm");
+    let status = Command::new("wasm-opt")
+        .args(["-Oz", "--enable-bulk-memory", "--enable-sign-ext"])
+        .arg(wasm)
+        .arg("-o")
+        .arg(&optimized)
+        .status()
+        .expect("failed to run wasm-opt; install binaryen or `cargo install wasm-opt`");
+    assert!(status.success(), "wasm-opt failed");
+    optimized
+}
@@ -0,0 +1,164 @@
+//! Renders the contract spec embedded in the release WASM and compares it with
+//! `tests/snapshots/contract_spec.txt`. After an intentional ABI change, rerun
+//! with `UPDATE_SPEC_SNAPSHOT=1` and commit the new snapshot.
+
+mod common;
+
+use soroban_sdk::xdr::{
+    Limited, Limits, ReadXdr, ScSpecEntry, ScSpecTypeDef, ScSpecUdtUnionCaseV0,
+};
+use std::path::PathBuf;
+
+fn read_leb128(bytes: &[u8], pos: &mut usize) -> usize {
+    let mut value = 0usize;
+    let mut shift = 0;
+    loop {
+        let byte = bytes[*pos];
+        *pos += 1;
+        value |= ((byte & 0x7f) as usize) << shift;
+        if byte & 0x80 == 0 {
+            return value;
+        }
+        shift += 7;
+    }
+}
+
+fn custom_section<'a>(wasm: &'a [u8], name: &str) -> &'a [u8] {
+    let mut pos = 8;
+    while pos < wasm.len() {
+        let id = wasm[pos];
+        pos += 1;
+        let size = read_leb128(wasm, &mut pos);
+        let end = pos + size;
+        if id == 0 {
+            let mut name_pos = pos;
+            let name_len = read_leb128(wasm, &mut name_pos);
+            if &wasm[name_pos..name_pos + name_len] == name.as_bytes() {
+                return &wasm[name_pos + name_len..end];
+            }
+        }
+        pos = end;
+    }
+    panic!("custom section {name} not found");
+}
+
+fn type_name(ty: &ScSpecTypeDef) -> String {
+    match ty {
+        ScSpecTypeDef::Option(t) => format!("Option<{}>", type_name(&t.value_type)),
+        ScSpecTypeDef::Result(t) => format!(
+            "Result<{}, {}>",
+            type_name(&t.ok_type),
+            type_name(&t.error_type)
+        ),
+        ScSpecTypeDef::Vec(t) => format!("Vec<{}>", type_name(&t.element_type)),
+        ScSpecTypeDef::Map(t) => format!(
+            "Map<{}, {}>",
+            type_name(&t.key_type),
+            type_name(&t.value_type)
+        ),
+        ScSpecTypeDef::Tuple(t) => format!(
+            "({})",
+            t.value_types
+                .iter()
+                .map(type_name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        ScSpecTypeDef::BytesN(t) => format!("BytesN<{}>", t.n),
+        ScSpecTypeDef::Udt(t) => t.name.to_utf8_string_lossy(),
+        ScSpecTypeDef::Bool
+        | ScSpecTypeDef::U32
+        | ScSpecTypeDef::I32
+        | ScSpecTypeDef::U64
+        | ScSpecTypeDef::I64
+        | ScSpecTypeDef::U128
+        | ScSpecTypeDef::I128
+        | ScSpecTypeDef::U256
+        | ScSpecTypeDef::I256 => ty.name().to_lowercase(),
+        other => other.name().to_string(),
+    }
+}
+
+fn render(entry: &ScSpecEntry) -> String {
+    match entry {
+        ScSpecEntry::FunctionV0(f) => {
+            let inputs = f
+                .inputs
+                .iter()
+                .map(|i| format!("{}: {}", i.name.to_utf8_string_lossy(), type_name(&i.type_)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let output = f.outputs.first().map(type_name).unwrap_or("()".into());
+            format!(
+                "fn {}({inputs}) -> {output}",
+                f.name.0.to_utf8_string_lossy()
+            )
+        }
+        ScSpecEntry::UdtStructV0(s) => {
+            let fields = s
+                .fields
+                .iter()
+                .map(|f| format!("{}: {}", f.name.to_utf8_string_lossy(), type_name(&f.type_)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("struct {} {{ {fields} }}", s.name.to_utf8_string_lossy())
+        }
+        ScSpecEntry::UdtUnionV0(u) => {
+            let cases = u
+                .cases
+                .iter()
+                .map(|c| match c {
+                    ScSpecUdtUnionCaseV0::VoidV0(v) => v.name.to_utf8_string_lossy(),
+                    ScSpecUdtUnionCaseV0::TupleV0(t) => format!(
+                        "{}({})",
+                        t.name.to_utf8_string_lossy(),
+                        t.type_.iter().map(type_name).collect::<Vec<_>>().join(", ")
+                    ),
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("union {} {{ {cases} }}", u.name.to_utf8_string_lossy())
+        }
+        ScSpecEntry::UdtEnumV0(e) => {
+            let cases = e
+                .cases
+                .iter()
+                .map(|c| format!("{} = {}", c.name.to_utf8_string_lossy(), c.value))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("enum {} {{ {cases} }}", e.name.to_utf8_string_lossy())
+        }
+        ScSpecEntry::UdtErrorEnumV0(e) => {
+            let cases = e
+                .cases
+                .iter()
+                .map(|c| format!("{} = {}", c.name.to_utf8_string_lossy(), c.value))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("error {} {{ {cases} }}", e.name.to_utf8_string_lossy())
+        }
+    }
+}
+
+#[test]
+fn contract_spec_matches_snapshot() {
+    let wasm = std::fs::read(common::build_release_wasm()).unwrap();
+    let section = custom_section(&wasm, "contractspecv0");
+    let mut lines = ScSpecEntry::read_xdr_iter(&mut Limited::new(section, Limits::none()))
+        .map(|entry| render(&entry.expect("malformed spec entry")))
+        .collect::<Vec<_>>();
+    lines.sort();
+    let rendered = lines.join("\n") + "\n";
+
+    let snapshot =
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots/contract_spec.txt");
+    if std::env::var_os("UPDATE_SPEC_SNAPSHOT").is_some() {
+        std::fs::write(&snapshot, &rendered).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&snapshot).unwrap_or_default();
+    assert!(
+        rendered == expected,
+        "contract spec changed; review the ABI diff and rerun with UPDATE_SPEC_SNAPSHOT=1\n--- expected\n{expected}\n--- actual\n{rendered}"
+    );
+}
@@ -0,0 +1,51 @@
+//! Runs the release WASM and swaps it in place with `upgrade`, which the native
+//! test contract cannot do.
+
+mod common;
+
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{vec, Address, Env, IntoVal, Symbol};
+
+#[test]
+fn upgrade_is_admin_only_and_keeps_storage() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+    let wasm = std::fs::read(common::build_release_wasm()).unwrap();
+    let game = env.register_contract_wasm(None, wasm.as_slice());
+    let hash = env.deployer().upload_contract_wasm(wasm.as_slice());
+    let admin = Address::generate(&env);
+    env.invoke_contract::<()>(
+        &game,
+        &Symbol::new(&env, "init"),
+        vec![&env, admin.into_val(&env), 2u32.into_val(&env)],
+    );
+
+    let upgrade = Symbol::new(&env, "upgrade");
+    let stranger = Address::generate(&env);
+    let rejected = env.try_invoke_contract::<(), soroban_sdk::Error>(
+        &game,
+        &upgrade,
+        vec![&env, stranger.into_val(&env), hash.clone().into_val(&env)],
+    );
+    // GameError::NotAdmin
+    assert_eq!(
+        rejected,
+        Err(Ok(soroban_sdk::Error::from_contract_error(3)))
+    );
+
+    env.invoke_contract::<()>(
+        &game,
+        &upgrade,
+        vec![&env, admin.into_val(&env), hash.into_val(&env)],
+    );
+    let stored: Option<Address> =
+        env.invoke_contract(&game, &Symbol::new(&env, "get_admin"), vec![&env]);
+    assert_eq!(stored, Some(admin.clone()));
+    let migrated: u32 = env.invoke_contract(
+        &game,
+        &Symbol::new(&env, "migrate"),
+        vec![&env, admin.into_val(&env)],
+    );
+    assert_eq!(migrated, 10);
+}
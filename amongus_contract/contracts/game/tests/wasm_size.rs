@@ -0,0 +1,27 @@
+mod common;
+
+// Byte budget for the optimized release WASM, pinned to the network's 128 KiB contract
+// size limit, which applies to the uploaded artifact; override with GAME_WASM_BUDGET to
+// hold a build to a tighter target.
+const DEFAULT_WASM_BUDGET: u64 = 128 * 1024;
+
+#[test]
+fn release_wasm_fits_budget() {
+    let budget = std::env::var("GAME_WASM_BUDGET")
+        .ok()
+        .map(|v| {
+            v.parse::<u64>()
+                .expect("GAME_WASM_BUDGET must be a byte count")
+        })
+        .unwrap_or(DEFAULT_WASM_BUDGET);
+
+    let wasm = common::optimize_wasm(&common::build_release_wasm());
+    let size = std::fs::metadata(&wasm)
+        .unwrap_or_else(|_| panic!("missing {}", wasm.display()))
+        .len();
+    println!("game.wasm: {size} bytes (budget {budget})");
+    assert!(
+        size <= budget,
+        "game.wasm is {size} bytes, over the {budget} byte budget"
+    );
+}
@@ -0,0 +1,152 @@
+#![no_std]
+
+use soroban_sdk::{
+    contract, contractimpl, contracttype,
+    crypto::bls12_381::{Fr, G1Affine, G2Affine, G1_SERIALIZED_SIZE, G2_SERIALIZED_SIZE},
+    vec, Address, Bytes, BytesN, Env, Symbol, Vec,
+};
+
+mod test;
+
+const G1: u32 = G1_SERIALIZED_SIZE as u32;
+const G2: u32 = G2_SERIALIZED_SIZE as u32;
+// A Groth16 proof is `A || B || C`: two uncompressed G1 points around one G2 point.
+const PROOF_LEN: u32 = 2 * G1 + G2;
+// `alpha || beta || gamma || delta` ahead of the `ic` points.
+const VK_HEADER_LEN: u32 = G1 + 3 * G2;
+
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+pub enum VerifierKey {
+    Admin,
+    Vk,
+    VkVersion,
+}
+
+#[contract]
+pub struct Groth16Verifier;
+
+fn g1_at(bytes: &Bytes, offset: u32) -> G1Affine {
+    let mut point = [0u8; G1_SERIALIZED_SIZE];
+    bytes.slice(offset..offset + G1).copy_into_slice(&mut point);
+    G1Affine::from_array(bytes.env(), &point)
+}
+
+fn g2_at(bytes: &Bytes, offset: u32) -> G2Affine {
+    let mut point = [0u8; G2_SERIALIZED_SIZE];
+    bytes.slice(offset..offset + G2).copy_into_slice(&mut point);
+    G2Affine::from_array(bytes.env(), &point)
+}
+
+// Number of `ic` points, or None when the key is not a header plus at least one point.
+fn ic_count(vk: &Bytes) -> Option<u32> {
+    let points = vk.len().checked_sub(VK_HEADER_LEN)?;
+    (points > 0 && points % G1 == 0).then_some(points / G1)
+}
+
+impl Groth16Verifier {
+    fn store_vk(env: &Env, vk: &Bytes) -> u32 {
+        if ic_count(vk).is_none() {
+            panic!("malformed verification key");
+        }
+        let version = Self::get_vk_version(env.clone()) + 1;
+        let storage = env.storage().instance();
+        storage.set(&VerifierKey::Vk, vk);
+        storage.set(&VerifierKey::VkVersion, &version);
+        version
+    }
+}
+
+#[contractimpl]
+impl Groth16Verifier {
+    // The key is serialized uncompressed as `alpha || beta || gamma || delta || ic[0..]`,
+    // with one `ic` point per public input plus the constant term in front.
+    pub fn init(env: Env, admin: Address, vk: Bytes) {
+        if env.storage().instance().has(&VerifierKey::Admin) {
+            panic!("already initialized");
+        }
+        env.storage().instance().set(&VerifierKey::Admin, &admin);
+        Self::store_vk(&env, &vk);
+    }
+
+    // Upgrades the circuit in place. Proofs for the old key stop verifying at once, so the
+    // event carries both key hashes for anyone auditing which key judged a proof. Every game
+    // routed here sees the swap mid-match, so this is for deployments no live game points at;
+    // a live game takes a new key as a fresh verifier through its candidate and promote flow.
+    pub fn set_verification_key(env: Env, caller: Address, vk: Bytes) -> u32 {
+        caller.require_auth();
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&VerifierKey::Admin)
+            .unwrap_or_else(|| panic!("not initialized"));
+        if caller != admin {
+            panic!("caller is not admin");
+        }
+        let old = env
+            .crypto()
+            .sha256(&Self::get_verification_key(env.clone()))
+            .to_bytes();
+        let version = Self::store_vk(&env, &vk);
+        env.events().publish(
+            (Symbol::new(&env, "vk_rotated"), version),
+            (old, env.crypto().sha256(&vk).to_bytes()),
+        );
+        version
+    }
+
+    pub fn get_verification_key(env: Env) -> Bytes {
+        env.storage()
+            .instance()
+            .get(&VerifierKey::Vk)
+            .unwrap_or_else(|| panic!("not initialized"))
+    }
+
+    // 1 for the key set at init, bumped by every rotation.
+    pub fn get_vk_version(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&VerifierKey::VkVersion)
+            .unwrap_or(0)
+    }
+
+    // Checks e(-A, B) · e(alpha, beta) · e(vk_x, gamma) · e(C, delta) = 1, where vk_x folds
+    // the public inputs into `ic`. Inputs are big-endian and taken modulo the group order.
+    // A proof of the wrong size or input count is a rejection, not a trap.
+    pub fn verify(env: Env, proof: Bytes, public_inputs: Vec<BytesN<32>>) -> bool {
+        let vk = Self::get_verification_key(env.clone());
+        let ic = ic_count(&vk).unwrap();
+        if proof.len() != PROOF_LEN || public_inputs.len() + 1 != ic {
+            return false;
+        }
+        let bls = env.crypto().bls12_381();
+
+        let mut vk_x = g1_at(&vk, VK_HEADER_LEN);
+        if !public_inputs.is_empty() {
+            let mut points = Vec::new(&env);
+            let mut scalars = Vec::new(&env);
+            for (i, input) in public_inputs.iter().enumerate() {
+                points.push_back(g1_at(&vk, VK_HEADER_LEN + (i as u32 + 1) * G1));
+                scalars.push_back(Fr::from_bytes(input));
+            }
+            vk_x = bls.g1_add(&vk_x, &bls.g1_msm(points, scalars));
+        }
+
+        bls.pairing_check(
+            vec![
+                &env,
+                -g1_at(&proof, 0),
+                g1_at(&vk, 0),
+                vk_x,
+                g1_at(&proof, G1 + G2),
+            ],
+            vec![
+                &env,
+                g2_at(&proof, G1),
+                g2_at(&vk, G1),
+                g2_at(&vk, G1 + G2),
+                g2_at(&vk, G1 + 2 * G2),
+            ],
+        )
+    }
+}
@@ -0,0 +1,194 @@
+#![cfg(test)]
+extern crate std;
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Events as _},
+    Address, IntoVal, Symbol, U256,
+};
+use std::path::PathBuf;
+use std::process::Command;
+
+const DST: &[u8] = b"PROOF-OF-SUS-TEST_XMD:SHA-256_SSWU_RO_";
+
+fn scalar(env: &Env, value: u32) -> Fr {
+    Fr::from_u256(U256::from_u32(env, value))
+}
+
+// Builds a key and a proof for `inputs` from known discrete logs, so the pairing equation
+// holds by construction without a trusted setup.
+fn prove(env: &Env, inputs: &Vec<BytesN<32>>) -> (Bytes, Bytes) {
+    let bls = env.crypto().bls12_381();
+    let dst = Bytes::from_slice(env, DST);
+    let p = bls.hash_to_g1(&Bytes::from_slice(env, b"g1"), &dst);
+    let q = bls.hash_to_g2(&Bytes::from_slice(env, b"g2"), &dst);
+    let (a, b, g, d, c) = (
+        scalar(env, 3),
+        scalar(env, 5),
+        scalar(env, 7),
+        scalar(env, 11),
+        scalar(env, 13),
+    );
+
+    let mut vk = Bytes::from((p.clone() * a.clone()).to_bytes());
+    vk.append(&Bytes::from((q.clone() * b.clone()).to_bytes()));
+    vk.append(&Bytes::from((q.clone() * g.clone()).to_bytes()));
+    vk.append(&Bytes::from((q.clone() * d.clone()).to_bytes()));
+    vk.append(&Bytes::from((p.clone() * scalar(env, 17)).to_bytes()));
+    let mut folded = scalar(env, 17);
+    for (i, input) in inputs.iter().enumerate() {
+        let s = scalar(env, 19 + i as u32);
+        vk.append(&Bytes::from((p.clone() * s.clone()).to_bytes()));
+        folded = folded + s * Fr::from_bytes(input);
+    }
+    // e(A, Q) = e(alpha, beta) · e(vk_x, gamma) · e(C, delta) with B = Q.
+    let a_log = a * b + g * folded + d * c.clone();
+
+    let mut proof = Bytes::from((p.clone() * a_log).to_bytes());
+    proof.append(&Bytes::from(q.to_bytes()));
+    proof.append(&Bytes::from((p * c).to_bytes()));
+    (vk, proof)
+}
+
+fn deploy(env: &Env, vk: &Bytes) -> (Groth16VerifierClient<'static>, Address) {
+    let client = Groth16VerifierClient::new(env, &env.register(Groth16Verifier, ()));
+    let admin = Address::generate(env);
+    client.init(&admin, vk);
+    (client, admin)
+}
+
+// Builds the game's release WASM into the side target dir its size test uses.
+fn game_wasm() -> std::vec::Vec<u8> {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = manifest_dir.join("../../target/wasm-size");
+    let status = Command::new(env!("CARGO"))
+        .current_dir(manifest_dir.join("../game"))
+        .args([
+            "build",
+            "--release",
+            "--target",
+            "wasm32-unknown-unknown",
+            "-p",
+            "game",
+            "--target-dir",
+        ])
+        .arg(&target_dir)
+        .status()
+        .expect("failed to run cargo");
+    assert!(status.success(), "game wasm build failed");
+    std::fs::read(target_dir.join("wasm32-unknown-unknown/release/game.wasm")).unwrap()
+}
+
+#[test]
+fn pairing_check_accepts_only_the_proven_inputs() {
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    // The second input sits above the group order, so it only verifies once reduced.
+    let inputs = vec![
+        &env,
+        BytesN::from_array(&env, &[0x2a; 32]),
+        BytesN::from_array(&env, &[0xff; 32]),
+    ];
+    let (vk, proof) = prove(&env, &inputs);
+    let (client, _) = deploy(&env, &vk);
+    assert!(client.verify(&proof, &inputs));
+
+    let mut tampered = inputs.clone();
+    tampered.set(0, BytesN::from_array(&env, &[0x2b; 32]));
+    assert!(!client.verify(&proof, &tampered));
+    assert!(!client.verify(&proof.slice(..proof.len() - 1), &inputs));
+    tampered.pop_back();
+    assert!(!client.verify(&proof, &tampered));
+}
+
+#[test]
+#[should_panic(expected = "already initialized")]
+fn verification_key_is_set_once() {
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    let (vk, _) = prove(&env, &Vec::new(&env));
+    let (client, admin) = deploy(&env, &vk);
+    client.init(&admin, &vk);
+}
+
+#[test]
+fn rotated_key_takes_over_and_logs_both_hashes() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.cost_estimate().budget().reset_unlimited();
+    let old_inputs = vec![&env, BytesN::from_array(&env, &[1; 32])];
+    let new_inputs = vec![
+        &env,
+        BytesN::from_array(&env, &[1; 32]),
+        BytesN::from_array(&env, &[2; 32]),
+    ];
+    let (old_vk, old_proof) = prove(&env, &old_inputs);
+    let (new_vk, new_proof) = prove(&env, &new_inputs);
+    let (client, admin) = deploy(&env, &old_vk);
+    assert_eq!(client.get_vk_version(), 1);
+
+    assert_eq!(client.set_verification_key(&admin, &new_vk), 2);
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        (Symbol::new(&env, "vk_rotated"), 2u32).into_val(&env)
+    );
+    let hashes: (BytesN<32>, BytesN<32>) = data.into_val(&env);
+    assert_eq!(
+        hashes,
+        (
+            env.crypto().sha256(&old_vk).into(),
+            env.crypto().sha256(&new_vk).into()
+        )
+    );
+    assert_eq!(client.get_verification_key(), new_vk);
+    assert!(!client.verify(&old_proof, &old_inputs));
+    assert!(client.verify(&new_proof, &new_inputs));
+
+    let outsider = Address::generate(&env);
+    assert!(client.try_set_verification_key(&outsider, &old_vk).is_err());
+    assert!(client
+        .try_set_verification_key(&admin, &old_vk.slice(..100))
+        .is_err());
+}
+
+#[test]
+fn game_routes_proofs_through_the_pairing_check() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.cost_estimate().budget().reset_unlimited();
+    let game = env.register(game_wasm().as_slice(), ());
+    let admin = Address::generate(&env);
+    let call = |name: &str, args| -> soroban_sdk::Val {
+        env.invoke_contract(&game, &Symbol::new(&env, name), args)
+    };
+    call(
+        "init",
+        vec![&env, admin.into_val(&env), 1u32.into_val(&env)],
+    );
+
+    // The game binds every proof to its own scope by prepending it to the inputs.
+    let scope: BytesN<32> =
+        env.invoke_contract(&game, &Symbol::new(&env, "get_game_scope"), vec![&env]);
+    let claimed = BytesN::from_array(&env, &[0x2a; 32]);
+    let (vk, proof) = prove(&env, &vec![&env, scope, claimed.clone()]);
+    let (verifier, _) = deploy(&env, &vk);
+    call(
+        "set_verifier",
+        vec![&env, admin.into_val(&env), verifier.address.into_val(&env)],
+    );
+
+    let verify = |input: &BytesN<32>| -> bool {
+        env.invoke_contract(
+            &game,
+            &Symbol::new(&env, "verify_zk_proof"),
+            vec![
+                &env,
+                proof.into_val(&env),
+                vec![&env, input.clone()].into_val(&env),
+            ],
+        )
+    };
+    assert!(verify(&claimed));
+    assert!(!verify(&BytesN::from_array(&env, &[0x2b; 32])));
+}
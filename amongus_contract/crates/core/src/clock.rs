@@ -0,0 +1,89 @@
+// Ledger timestamps are only roughly monotonic, so every subtraction saturates.
+pub fn elapsed(since: u64, now: u64) -> u64 {
+    now.saturating_sub(since)
+}
+
+pub fn deadline(start: u64, window: u64) -> u64 {
+    start.saturating_add(window)
+}
+
+pub fn has_elapsed(start: u64, duration: u64, now: u64) -> bool {
+    elapsed(start, now) >= duration
+}
+
+pub fn within_window(start: u64, window: u64, now: u64) -> bool {
+    now <= deadline(start, window)
+}
+
+pub fn paused_clock(now: u64, paused_at: Option<u64>, paused_total: u64) -> u64 {
+    paused_at
+        .unwrap_or(now)
+        .min(now)
+        .saturating_sub(paused_total)
+}
+
+// Every time read goes through a source, so a simulator can fast-forward cooldowns,
+// deadlines and AFK windows on a virtual clock while the contract reads the ledger.
+pub trait TimeSource {
+    fn now(&self) -> u64;
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct VirtualClock {
+    pub now: u64,
+}
+
+impl VirtualClock {
+    pub fn advance(&mut self, secs: u64) {
+        self.now = self.now.saturating_add(secs);
+    }
+}
+
+impl TimeSource for VirtualClock {
+    fn now(&self) -> u64 {
+        self.now
+    }
+}
+
+// The contract's pause bookkeeping over any source: match time stops while paused, and
+// a resume adds the paused stretch to the running total.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct MatchClock<S> {
+    pub source: S,
+    pub paused_at: Option<u64>,
+    pub paused_total: u64,
+}
+
+impl<S: TimeSource> MatchClock<S> {
+    pub fn new(source: S) -> Self {
+        MatchClock {
+            source,
+            paused_at: None,
+            paused_total: 0,
+        }
+    }
+
+    pub fn match_time(&self) -> u64 {
+        paused_clock(self.source.now(), self.paused_at, self.paused_total)
+    }
+
+    pub fn pause(&mut self) {
+        self.paused_at.get_or_insert(self.source.now());
+    }
+
+    // Returns the seconds the pause lasted; 0 when the clock was running.
+    pub fn resume(&mut self) -> u64 {
+        let Some(paused_at) = self.paused_at.take() else {
+            return 0;
+        };
+        let paused_for = elapsed(paused_at, self.source.now());
+        self.paused_total += paused_for;
+        paused_for
+    }
+}
+
+impl MatchClock<VirtualClock> {
+    pub fn advance(&mut self, secs: u64) {
+        self.source.advance(secs);
+    }
+}
@@ -0,0 +1,10 @@
+#![no_std]
+
+// Match rules that need no ledger. The contract reads time from the ledger through its
+// own adapter; simulators drive the same code from a `VirtualClock`.
+pub mod clock;
+
+pub use clock::{MatchClock, TimeSource, VirtualClock};
+
+#[cfg(test)]
+mod test;
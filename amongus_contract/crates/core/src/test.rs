@@ -0,0 +1,30 @@
+use crate::clock::{deadline, has_elapsed};
+use crate::{MatchClock, TimeSource, VirtualClock};
+
+#[test]
+fn virtual_clock_fast_forwards_the_same_timers_the_ledger_drives() {
+    let mut clock = VirtualClock::default();
+    clock.advance(100);
+    // Paused at 40 after 10 paused seconds, the match clock holds at 30.
+    assert_eq!(crate::clock::paused_clock(clock.now(), Some(40), 10), 30);
+    assert!(!has_elapsed(30, 80, clock.now()));
+    clock.advance(10);
+    assert!(has_elapsed(30, 80, clock.now()));
+}
+
+#[test]
+fn match_clock_holds_a_cooldown_through_a_pause() {
+    let mut clock = MatchClock::new(VirtualClock { now: 1_000 });
+    let ready_at = deadline(clock.match_time(), 30);
+    clock.advance(10);
+    clock.pause();
+    clock.advance(500);
+    assert_eq!(clock.match_time(), 1_010);
+    assert_eq!(clock.resume(), 500);
+    assert_eq!(clock.resume(), 0);
+
+    clock.advance(19);
+    assert!(clock.match_time() < ready_at);
+    clock.advance(1);
+    assert_eq!(clock.match_time(), ready_at);
+}
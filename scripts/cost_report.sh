@@ -0,0 +1,18 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+cd "$(dirname "$0")/../amongus_contract"
+
+combos=(
+  ""
+  "economy"
+  "cosmetics"
+  "anonymous-voting"
+  "fog-of-war"
+  "economy,cosmetics,anonymous-voting,fog-of-war"
+)
+
+for features in "${combos[@]}"; do
+  cargo build -q -p game --release --target wasm32-unknown-unknown --no-default-features --features "$features"
+  cargo bench -q -p game --bench cost_report --no-default-features --features "$features"
+done
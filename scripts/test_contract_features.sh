@@ -0,0 +1,19 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+cd "$(dirname "$0")/../amongus_contract"
+
+combos=(
+  ""
+  "economy"
+  "cosmetics"
+  "anonymous-voting"
+  "fog-of-war"
+  "economy,cosmetics,anonymous-voting,fog-of-war"
+)
+
+for features in "${combos[@]}"; do
+  echo "== features: ${features:-<none>}"
+  cargo clippy -p game --all-targets --no-default-features --features "$features" -- -D warnings
+  cargo test -p game --no-default-features --features "$features"
+done
@@ -17,6 +17,7 @@ Flow
   3. Call submit_signed_xdr(signed_xdr) → broadcasts on-chain.
 """
 
+import hashlib
 import os
 from dataclasses import dataclass, field
 
@@ -52,6 +53,35 @@ class StellarConfig:
         return cls(rpc_url=rpc_url, network_passphrase=passphrase, contract_id=contract_id)
 
 
+# ── status codes ─────────────────────────────────────────────────────────────
+
+# Stable codes returned by get_match_status(); codes are never reused.
+MATCH_PHASES = {0: "lobby", 1: "playing", 2: "meeting", 3: "ended"}
+MATCH_WINNERS = {0: "undecided", 1: "crew", 2: "impostors"}
+
+
+# ── nullifier derivation ─────────────────────────────────────────────────────
+
+def derive_nullifier(
+    game_scope_hex: str, commitment_hex: str, action: str, round_id: int, seq: int = 0
+) -> str:
+    """
+    Off-chain twin of the contract's derive_nullifier:
+    sha256(game_scope || commitment || Symbol(action) XDR || round_id || seq), with
+    round_id and seq as big-endian u32s.
+    game_scope_hex is the contract's get_game_scope() value. seq counts the player's
+    earlier tasks or kills this round; it is 0 for every other action.
+    """
+    preimage = (
+        bytes.fromhex(game_scope_hex)
+        + bytes.fromhex(commitment_hex)
+        + scval.to_symbol(action).to_xdr_bytes()
+        + round_id.to_bytes(4, "big")
+        + seq.to_bytes(4, "big")
+    )
+    return hashlib.sha256(preimage).hexdigest()
+
+
 # ── client ───────────────────────────────────────────────────────────────────
 
 class StellarGameClient:
@@ -85,10 +115,10 @@ class StellarGameClient:
         return assembled.to_xdr()
 
     @staticmethod
-    def _proof_struct(proof_hash_hex: str, nullifier_hex: str, public_inputs_hex: list) -> object:
-        """Encode a ProofInput { proof_hash, nullifier, public_inputs } struct."""
+    def _proof_struct(proof_hex: str, nullifier_hex: str, public_inputs_hex: list) -> object:
+        """Encode a ProofInput { proof, nullifier, public_inputs } struct."""
         return scval.to_struct({
-            "proof_hash": scval.to_bytes(bytes.fromhex(proof_hash_hex)),
+            "proof": scval.to_bytes(bytes.fromhex(proof_hex)),
             "nullifier":  scval.to_bytes(bytes.fromhex(nullifier_hex)),
             "public_inputs": scval.to_vec(
                 [scval.to_bytes(bytes.fromhex(h)) for h in public_inputs_hex]
@@ -131,7 +161,7 @@ class StellarGameClient:
     def build_task_xdr(
         self,
         player_address: str,
-        proof_hash_hex: str,
+        proof_hex: str,
         nullifier_hex: str,
         public_inputs_hex: list,
     ) -> str:
@@ -141,7 +171,7 @@ class StellarGameClient:
         """
         params = [
             scval.to_address(player_address),
-            self._proof_struct(proof_hash_hex, nullifier_hex, public_inputs_hex),
+            self._proof_struct(proof_hex, nullifier_hex, public_inputs_hex),
         ]
         return self._build_xdr("submit_task_proof", params, player_address)
 
@@ -149,7 +179,7 @@ class StellarGameClient:
         self,
         voter_address: str,
         target_hash_hex: str,
-        proof_hash_hex: str,
+        proof_hex: str,
         nullifier_hex: str,
     ) -> str:
         """
@@ -158,7 +188,7 @@ class StellarGameClient:
         """
         vote_struct = scval.to_struct({
             "target_hash": scval.to_bytes(bytes.fromhex(target_hash_hex)),
-            "proof_hash":  scval.to_bytes(bytes.fromhex(proof_hash_hex)),
+            "proof":       scval.to_bytes(bytes.fromhex(proof_hex)),
             "nullifier":   scval.to_bytes(bytes.fromhex(nullifier_hex)),
         })
         params = [scval.to_address(voter_address), vote_struct]
@@ -168,18 +198,21 @@ class StellarGameClient:
         self,
         killer_address: str,
         victim_address: str,
-        proof_hash_hex: str,
+        proof_hex: str,
         nullifier_hex: str,
         public_inputs_hex: list,
+        flavor: int = 0,
     ) -> str:
         """
         Build a submit_kill_proof transaction XDR.
         Kill proof values come from nargo prove on noir_circuits/kill_proof.
+        flavor is an animation ID from get_kill_flavors(); 0 is the plain kill.
         """
         params = [
             scval.to_address(killer_address),
             scval.to_address(victim_address),
-            self._proof_struct(proof_hash_hex, nullifier_hex, public_inputs_hex),
+            self._proof_struct(proof_hex, nullifier_hex, public_inputs_hex),
+            scval.to_uint32(flavor),
         ]
         return self._build_xdr("submit_kill_proof", params, killer_address)
 
@@ -222,3 +255,19 @@ class StellarGameClient:
             return {}
         return scval.to_native(raw)
 
+    def get_match_status(self) -> dict:
+        """Fetch phase and winner as numeric codes via get_match_status(), with names added."""
+        assembled = self._contract.invoke(
+            function_name="get_match_status",
+            parameters=[],
+            source="GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF",
+            signer=None,
+            simulate=True,
+        )
+        raw = assembled.result
+        if raw is None:
+            return {}
+        status = scval.to_native(raw)
+        status["phase_name"] = MATCH_PHASES.get(status["phase"], "unknown")
+        status["winner_name"] = MATCH_WINNERS.get(status["winner"], "unknown")
+        return status
@@ -72,13 +72,25 @@ def _run_nargo_prove(circuit_dir: str, prover_toml_content: str) -> Optional[byt
 
 # ── per-action proof generation ───────────────────────────────────────────────
 
-def _make_role_proof(circuits_root: str, player_secret: int, role_secret: int, round_id: int):
-    """Generate a role ZK proof via nargo. Returns (proof_hash_hex, nullifier_hex)."""
-    role_commitment = role_secret * role_secret + player_secret * 19 + 17
+def _role_secret(player_secret: int) -> int:
+    """Derive the seat's role secret; the commitment holds for the whole match."""
+    return int(hashlib.sha256(f"role:{player_secret}".encode()).hexdigest()[:8], 16)
+
+
+def _role_commitment(role_secret: int, player_secret: int, is_impostor: bool) -> int:
+    """The seat's `role_hash`, as role_proof and task_proof open it; the role bit is bound in."""
+    return role_secret * role_secret + player_secret * 19 + 17 + int(is_impostor) * 29
+
+
+def _make_role_proof(circuits_root: str, player_secret: int, role_secret: int, is_impostor: bool,
+                     round_id: int):
+    """Generate a role ZK proof via nargo. Returns (proof_hex, nullifier_hex)."""
+    role_commitment = _role_commitment(role_secret, player_secret, is_impostor)
     nullifier = player_secret * 31 + round_id * 97 + 7
     toml = (
         f'role_secret = "{role_secret}"\n'
         f'player_secret = "{player_secret}"\n'
+        f'is_impostor = "{int(is_impostor)}"\n'
         f'round_id = "{round_id}"\n'
         f'role_commitment = "{role_commitment}"\n'
         f'action_nullifier = "{nullifier}"\n'
@@ -86,18 +98,23 @@ def _make_role_proof(circuits_root: str, player_secret: int, role_secret: int, r
     proof_bytes = _run_nargo_prove(os.path.join(circuits_root, "role_proof"), toml)
     if proof_bytes is None:
         raise RuntimeError("nargo prove failed for role_proof")
-    proof_hash = hashlib.sha256(proof_bytes).hexdigest()
-    return proof_hash, format(nullifier & 0xFFFFFFFFFFFFFFFF, "064x")
+    return proof_bytes.hex(), format(nullifier & 0xFFFFFFFFFFFFFFFF, "064x")
 
 
-def _make_task_proof(circuits_root: str, task_id: int, task_secret: int, player_secret: int, round_id: int):
-    """Generate a task ZK proof via nargo. Returns (proof_hash_hex, nullifier_hex)."""
+def _make_task_proof(circuits_root: str, task_id: int, task_secret: int, player_secret: int,
+                     role_secret: int, round_id: int):
+    """Generate a task ZK proof via nargo. Returns (proof_hex, nullifier_hex)."""
     task_commitment = task_id * 131 + task_secret * 17 + player_secret * 23
     nullifier = player_secret * 41 + task_id * 13 + round_id * 101
+    # Only a crew seat's commitment opens with is_impostor = 0.
+    crew_role_commitment = _role_commitment(role_secret, player_secret, False)
     toml = (
         f'task_id = "{task_id}"\n'
         f'task_secret = "{task_secret}"\n'
         f'player_secret = "{player_secret}"\n'
+        f'role_secret = "{role_secret}"\n'
+        f'is_impostor = "0"\n'
+        f'crew_role_commitment = "{crew_role_commitment}"\n'
         f'round_id = "{round_id}"\n'
         f'task_commitment = "{task_commitment}"\n'
         f'action_nullifier = "{nullifier}"\n'
@@ -105,12 +122,11 @@ def _make_task_proof(circuits_root: str, task_id: int, task_secret: int, player_
     proof_bytes = _run_nargo_prove(os.path.join(circuits_root, "task_proof"), toml)
     if proof_bytes is None:
         raise RuntimeError("nargo prove failed for task_proof")
-    proof_hash = hashlib.sha256(proof_bytes).hexdigest()
-    return proof_hash, format(nullifier & 0xFFFFFFFFFFFFFFFF, "064x")
+    return proof_bytes.hex(), format(nullifier & 0xFFFFFFFFFFFFFFFF, "064x")
 
 
 def _make_kill_proof(circuits_root: str, dx: int, dy: int, player_secret: int, round_id: int):
-    """Generate a kill ZK proof via nargo. Returns (proof_hash_hex, nullifier_hex)."""
+    """Generate a kill ZK proof via nargo. Returns (proof_hex, nullifier_hex)."""
     distance = dx * dx + dy * dy
     kill_commitment = distance * 11 + 1 + 97 + player_secret * 5
     nullifier = player_secret * 67 + round_id * 17
@@ -127,12 +143,11 @@ def _make_kill_proof(circuits_root: str, dx: int, dy: int, player_secret: int, r
     proof_bytes = _run_nargo_prove(os.path.join(circuits_root, "kill_proof"), toml)
     if proof_bytes is None:
         raise RuntimeError("nargo prove failed for kill_proof")
-    proof_hash = hashlib.sha256(proof_bytes).hexdigest()
-    return proof_hash, format(nullifier & 0xFFFFFFFFFFFFFFFF, "064x")
+    return proof_bytes.hex(), format(nullifier & 0xFFFFFFFFFFFFFFFF, "064x")
 
 
 def _make_vote_proof(circuits_root: str, target_index: int, player_secret: int, meeting_round: int):
-    """Generate a vote ZK proof via nargo. Returns (proof_hash_hex, nullifier_hex)."""
+    """Generate a vote ZK proof via nargo. Returns (proof_hex, nullifier_hex)."""
     vote_commitment = target_index * 257 + player_secret * 29 + meeting_round * 3
     nullifier = player_secret * 53 + meeting_round * 11
     toml = (
@@ -145,8 +160,7 @@ def _make_vote_proof(circuits_root: str, target_index: int, player_secret: int,
     proof_bytes = _run_nargo_prove(os.path.join(circuits_root, "vote_proof"), toml)
     if proof_bytes is None:
         raise RuntimeError("nargo prove failed for vote_proof")
-    proof_hash = hashlib.sha256(proof_bytes).hexdigest()
-    return proof_hash, format(nullifier & 0xFFFFFFFFFFFFFFFF, "064x")
+    return proof_bytes.hex(), format(nullifier & 0xFFFFFFFFFFFFFFFF, "064x")
 
 
 # ── Web3 game mode ─────────────────────────────────────────────────────────────
@@ -375,14 +389,15 @@ class Web3GameMode:
             return False
         return True
 
-    def on_join(self, color: str, name: str):
-        """Call after the local player enters the game."""
+    def on_join(self, color: str, name: str, is_impostor: bool = False):
+        """Call after the local player enters the game with the role they were dealt."""
         if not self._require_wallet("join_game"):
             return
         addr = self.wallet_address
         secret = self.player_secret
         player_hash = hashlib.sha256(f"{addr}:{color}:{name}".encode()).hexdigest()
-        role_hash = hashlib.sha256(f"role:{secret}:{self.round_id}".encode()).hexdigest()
+        role_secret = _role_secret(secret)
+        role_hash = format(_role_commitment(role_secret, secret, is_impostor), "064x")
         self._set_status("⏳ Registering player on-chain…", ok=True, seconds=10)
 
         def _go():
@@ -415,21 +430,22 @@ class Web3GameMode:
             try:
                 if self._nargo_available:
                     task_secret = (secret ^ task_id) & 0xFFFFFFFF
-                    proof_hash, nullifier = _make_task_proof(
-                        self.circuits_root, task_id, task_secret, secret, round_id,
+                    proof, nullifier = _make_task_proof(
+                        self.circuits_root, task_id, task_secret, secret,
+                        _role_secret(secret), round_id,
                     )
                     public_inputs = [format(task_id & 0xFFFFFFFF, "064x")]
                 else:
                     task_secret = (secret ^ task_id) & 0xFFFFFFFF
                     raw_nullifier = secret * 41 + task_id * 13 + round_id * 101
-                    proof_hash = hashlib.sha256(
+                    proof = hashlib.sha256(
                         f"task:{task_id}:{task_secret}:{secret}".encode()
                     ).hexdigest()
                     nullifier = format(raw_nullifier & ((1 << 64) - 1), "064x")
                     public_inputs = [format(round_id, "064x")]
 
                 if self.stellar:
-                    xdr = self.stellar.build_task_xdr(addr, proof_hash, nullifier, public_inputs)
+                    xdr = self.stellar.build_task_xdr(addr, proof, nullifier, public_inputs)
                     self._dispatch("submit_task_proof", xdr, {"task_id": task_id})
                 else:
                     self._set_status(f"✓ Task {task_id} proof generated (proof-only mode)", ok=True)
@@ -454,13 +470,13 @@ class Web3GameMode:
         def _go():
             try:
                 if self._nargo_available:
-                    proof_hash, nullifier = _make_kill_proof(
+                    proof, nullifier = _make_kill_proof(
                         self.circuits_root, dx, dy, secret, round_id,
                     )
                     public_inputs = [format(round_id, "064x")]
                 else:
                     raw_nullifier = secret * 67 + round_id * 17
-                    proof_hash = hashlib.sha256(
+                    proof = hashlib.sha256(
                         f"kill:{dx}:{dy}:{secret}:{round_id}".encode()
                     ).hexdigest()
                     nullifier = format(raw_nullifier & ((1 << 64) - 1), "064x")
@@ -470,13 +486,13 @@ class Web3GameMode:
                     xdr = self.stellar.build_kill_xdr(
                         killer_address=addr,
                         victim_address=victim_wallet,
-                        proof_hash_hex=proof_hash,
+                        proof_hex=proof,
                         nullifier_hex=nullifier,
                         public_inputs_hex=public_inputs,
                     )
                     self._dispatch("submit_kill_proof", xdr, {"victim": victim_wallet[:8]})
                 else:
-                    self._set_status(f"✓ Kill proof generated (proof-only mode)  hash={proof_hash[:10]}…", ok=True)
+                    self._set_status(f"✓ Kill proof generated (proof-only mode)  hash={hashlib.sha256(bytes.fromhex(proof)).hexdigest()[:10]}…", ok=True)
             except Exception as exc:
                 self._set_status(f"✗ kill_proof: {exc}", ok=False, seconds=8)
 
@@ -495,19 +511,19 @@ class Web3GameMode:
         def _go():
             try:
                 if self._nargo_available:
-                    proof_hash, nullifier = _make_vote_proof(
+                    proof, nullifier = _make_vote_proof(
                         self.circuits_root, target_index, secret, meeting_round,
                     )
                 else:
                     raw_nullifier = secret * 53 + meeting_round * 11
-                    proof_hash = hashlib.sha256(
+                    proof = hashlib.sha256(
                         f"vote:{target_index}:{secret}:{meeting_round}".encode()
                     ).hexdigest()
                     nullifier = format(raw_nullifier & ((1 << 64) - 1), "064x")
 
                 target_hash = hashlib.sha256(target_wallet.encode()).hexdigest()
                 if self.stellar:
-                    xdr = self.stellar.build_vote_xdr(addr, target_hash, proof_hash, nullifier)
+                    xdr = self.stellar.build_vote_xdr(addr, target_hash, proof, nullifier)
                     self._dispatch("submit_vote", xdr, {"target_index": target_index})
                 else:
                     self._set_status(f"✓ Vote proof generated (proof-only mode)", ok=True)